@@ -1,7 +1,7 @@
 use std::sync::{Arc, LazyLock, Mutex};
 
 use log::Log;
-use raphael_sim::{ActionMask, Settings};
+use raphael_sim::{ActionMask, CpCostOverrides, RoundingMode, Settings};
 use raphael_solver::{AtomicFlag, MacroSolver, SolverSettings};
 
 #[repr(C)]
@@ -138,6 +138,11 @@ impl From<SolveArgs> for SolverSettings {
             allowed_actions: ActionMask::from_bits(value.action_mask),
             adversarial: value.adversarial,
             backload_progress: value.backload_progress,
+            quality_increase_cap: None,
+            progress_target: None,
+            progress_optional: false,
+            rounding_mode: RoundingMode::Floor,
+            cp_cost_overrides: CpCostOverrides::none(),
         };
         Self { simulator_settings }
     }
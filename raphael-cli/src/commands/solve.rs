@@ -1,7 +1,8 @@
 use clap::Args;
 use log::error;
 use raphael_data::{
-    CrafterStats, CustomRecipeOverrides, MEALS, POTIONS, RECIPES, get_game_settings,
+    CrafterStats, CustomRecipeOverrides, Locale, MEALS, POTIONS, RECIPES, action_name,
+    get_game_settings,
 };
 use raphael_sim::SimulationState;
 use raphael_solver::{AtomicFlag, MacroSolver, SolverSettings};
@@ -88,6 +89,10 @@ pub struct SolveArgs {
     #[arg(long, default_value_t = false)]
     pub backload_progress: bool,
 
+    /// Print action names as they appear in the game client of the given language (EN, DE, FR or JP) instead of their identifiers
+    #[arg(long)]
+    pub locale: Option<Locale>,
+
     /// Maximum number of threads available to the solver
     #[arg(long)]
     pub threads: Option<usize>,
@@ -370,7 +375,10 @@ pub fn execute(args: &SolveArgs) {
         println!("Duration: {} seconds", duration);
         println!("\nActions:");
         for action in actions {
-            println!("{:?}", action);
+            match args.locale {
+                Some(locale) => println!("{}", action_name(action, locale)),
+                None => println!("{:?}", action),
+            }
         }
     } else {
         let mut output_string = "".to_owned();
@@ -390,7 +398,17 @@ pub fn execute(args: &SolveArgs) {
                 "initial_quality" => format!("{:?}", initial_quality),
                 "target_quality" => format!("{:?}", target_quality),
                 "recipe_max_quality" => format!("{:?}", recipe_max_quality),
-                "actions" => format!("\"{:?}\"", actions),
+                "actions" => match args.locale {
+                    Some(locale) => format!(
+                        "\"[{}]\"",
+                        actions
+                            .iter()
+                            .map(|action| action_name(*action, locale))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                    None => format!("\"{:?}\"", actions),
+                },
                 "final_state" => format!("\"{:?}\"", final_state),
                 "state_quality" => format!("{:?}", state_quality),
                 "final_quality" => format!("{:?}", final_quality),
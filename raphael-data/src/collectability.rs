@@ -0,0 +1,28 @@
+/// Collectability tier reached by a craft, see `collectability_result`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CollectabilityTier {
+    /// Number of tiers reached, 0 if the Quality is below the first tier.
+    pub tier: u8,
+    /// Quality that is still missing to reach the next tier, `None` if the highest tier is reached.
+    pub quality_to_next_tier: Option<u32>,
+}
+
+/// Minimum Quality of the three collectability tiers at 55%, 75% and 95% of `max_quality`.
+pub const fn collectability_tiers(max_quality: u16) -> [u32; 3] {
+    let max_quality = max_quality as u32;
+    [
+        max_quality * 55 / 100,
+        max_quality * 75 / 100,
+        max_quality * 95 / 100,
+    ]
+}
+
+/// Returns the highest of `tiers` that is reached with `quality`, where `tiers` holds the minimum Quality
+/// of each tier in ascending order. A Quality that is exactly on a tier boundary reaches that tier.
+pub fn collectability_result(quality: u32, tiers: &[u32]) -> CollectabilityTier {
+    let tier = tiers.partition_point(|min_quality| *min_quality <= quality);
+    CollectabilityTier {
+        tier: tier as u8,
+        quality_to_next_tier: tiers.get(tier).map(|min_quality| min_quality - quality),
+    }
+}
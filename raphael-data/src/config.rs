@@ -1,3 +1,5 @@
+use crate::Recipe;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CrafterStats {
@@ -30,3 +32,27 @@ impl Default for CrafterStats {
         }
     }
 }
+
+/// Difference in stats between two [`CrafterStats`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StatDelta {
+    pub craftsmanship: i32,
+    pub control: i32,
+    pub cp: i32,
+}
+
+impl CrafterStats {
+    /// Returns the stat difference `self - other`.
+    pub fn delta(&self, other: &Self) -> StatDelta {
+        StatDelta {
+            craftsmanship: i32::from(self.craftsmanship) - i32::from(other.craftsmanship),
+            control: i32::from(self.control) - i32::from(other.control),
+            cp: i32::from(self.cp) - i32::from(other.cp),
+        }
+    }
+
+    /// Checks whether the Craftsmanship and Control requirements of the recipe are met.
+    pub fn meets(&self, recipe: &Recipe) -> bool {
+        self.craftsmanship >= recipe.req_craftsmanship && self.control >= recipe.req_control
+    }
+}
@@ -1,3 +1,6 @@
+mod collectability;
+pub use collectability::*;
+
 mod consumables;
 pub use consumables::*;
 
@@ -10,7 +13,7 @@ pub use locales::*;
 mod search;
 pub use search::*;
 
-use raphael_sim::{Action, ActionMask, Settings};
+use raphael_sim::{Action, ActionMask, CpCostOverrides, RoundingMode, Settings};
 
 pub const HQ_ICON_CHAR: char = '\u{e03c}';
 pub const CL_ICON_CHAR: char = '\u{e03d}';
@@ -98,12 +101,14 @@ pub fn get_game_settings(
     let control = crafter_stats.control + control_bonus(crafter_stats.control, &[food, potion]);
     let cp = crafter_stats.cp + cp_bonus(crafter_stats.cp, &[food, potion]);
 
-    let mut base_progress = craftsmanship as f32 * 10.0 / rlvl_record.progress_div as f32 + 2.0;
-    let mut base_quality = control as f32 * 10.0 / rlvl_record.quality_div as f32 + 35.0;
-    if crafter_stats.level <= rlvl_record.job_level {
-        base_progress = base_progress * rlvl_record.progress_mod as f32 / 100.0;
-        base_quality = base_quality * rlvl_record.quality_mod as f32 / 100.0;
-    }
+    // the recipe level modifiers only apply if the crafter isn't above the recipe's job level
+    let (progress_mod, quality_mod) = if crafter_stats.level <= rlvl_record.job_level {
+        (rlvl_record.progress_mod, rlvl_record.quality_mod)
+    } else {
+        (100, 100)
+    };
+    let base_progress = base_progress(craftsmanship, rlvl_record.progress_div, progress_mod);
+    let base_quality = base_quality(control, rlvl_record.quality_div, quality_mod);
 
     let mut allowed_actions = ActionMask::all();
     if !crafter_stats.manipulation {
@@ -127,32 +132,60 @@ pub fn get_game_settings(
             max_quality: overrides.max_quality_override,
             base_progress: match overrides.base_progress_override {
                 Some(override_value) => override_value,
-                None => base_progress as u16,
+                None => base_progress,
             },
             base_quality: match overrides.base_quality_override {
                 Some(override_value) => override_value,
-                None => base_quality as u16,
+                None => base_quality,
             },
             job_level: crafter_stats.level,
             allowed_actions,
             adversarial: false,
             backload_progress: false,
+            quality_increase_cap: None,
+            progress_target: None,
+            progress_optional: false,
+            rounding_mode: RoundingMode::Floor,
+            cp_cost_overrides: CpCostOverrides::none(),
         },
         None => Settings {
             max_cp: cp as _,
             max_durability: rlvl_record.max_durability * recipe.durability_factor / 100,
             max_progress: (rlvl_record.max_progress * recipe.progress_factor / 100) as u16,
             max_quality: (rlvl_record.max_quality * recipe.quality_factor / 100) as u16,
-            base_progress: base_progress as u16,
-            base_quality: base_quality as u16,
+            base_progress,
+            base_quality,
             job_level: crafter_stats.level,
             allowed_actions,
             adversarial: false,
             backload_progress: false,
+            quality_increase_cap: None,
+            progress_target: None,
+            progress_optional: false,
+            rounding_mode: RoundingMode::Floor,
+            cp_cost_overrides: CpCostOverrides::none(),
         },
     }
 }
 
+/// Progress increase of a 100% efficiency action for the given Craftsmanship.
+///
+/// `progress_div` and `progress_mod` are the factors of the recipe's `RecipeLevel`.
+/// The modifier only applies if the crafter's level is at most the recipe's job level, pass 100 otherwise.
+pub fn base_progress(craftsmanship: u16, progress_div: u32, progress_mod: u32) -> u16 {
+    let base_progress = craftsmanship as f32 * 10.0 / progress_div as f32 + 2.0;
+    (base_progress * progress_mod as f32 / 100.0) as u16
+}
+
+/// Quality increase of a 100% efficiency action for the given Control.
+///
+/// `quality_div` and `quality_mod` are the factors of the recipe's `RecipeLevel`.
+/// The modifier only applies if the crafter's level is at most the recipe's job level, pass 100 otherwise.
+pub fn base_quality(control: u16, quality_div: u32, quality_mod: u32) -> u16 {
+    let base_quality = control as f32 * 10.0 / quality_div as f32 + 35.0;
+    (base_quality * quality_mod as f32 / 100.0) as u16
+}
+
 pub fn get_initial_quality(
     crafter_stats: CrafterStats,
     recipe: Recipe,
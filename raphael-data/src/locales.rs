@@ -21,6 +21,22 @@ impl std::fmt::Display for Locale {
     }
 }
 
+impl std::str::FromStr for Locale {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_uppercase().as_str() {
+            "EN" => Ok(Self::EN),
+            "DE" => Ok(Self::DE),
+            "FR" => Ok(Self::FR),
+            "JP" => Ok(Self::JP),
+            _ => Err(format!(
+                "Unknown locale '{s}'. Supported locales are EN, DE, FR and JP"
+            )),
+        }
+    }
+}
+
 const JOB_NAMES_EN: [&str; 8] = ["CRP", "BSM", "ARM", "GSM", "LTW", "WVR", "ALC", "CUL"];
 const JOB_NAMES_DE: [&str; 8] = ["ZMR", "GRS", "PLA", "GLD", "GER", "WEB", "ALC", "GRM"];
 const JOB_NAMES_FR: [&str; 8] = ["MEN", "FRG", "ARM", "ORF", "TAN", "COU", "ALC", "CUI"];
@@ -34,6 +50,56 @@ pub fn get_job_name(job_id: u8, locale: Locale) -> &'static str {
     }
 }
 
+const JOB_FULL_NAMES_EN: [&str; 8] = [
+    "Carpenter",
+    "Blacksmith",
+    "Armorer",
+    "Goldsmith",
+    "Leatherworker",
+    "Weaver",
+    "Alchemist",
+    "Culinarian",
+];
+const JOB_FULL_NAMES_DE: [&str; 8] = [
+    "Zimmerer",
+    "Grobschmied",
+    "Plattner",
+    "Goldschmied",
+    "Gerber",
+    "Weber",
+    "Alchemist",
+    "Gourmet",
+];
+const JOB_FULL_NAMES_FR: [&str; 8] = [
+    "Menuisier",
+    "Forgeron",
+    "Armurier",
+    "Orfèvre",
+    "Tanneur",
+    "Couturier",
+    "Alchimiste",
+    "Cuisinier",
+];
+const JOB_FULL_NAMES_JP: [&str; 8] = [
+    "木工師",
+    "鍛冶師",
+    "甲冑師",
+    "彫金師",
+    "革細工師",
+    "裁縫師",
+    "錬金術師",
+    "調理師",
+];
+
+pub fn get_job_full_name(job_id: u8, locale: Locale) -> &'static str {
+    match locale {
+        Locale::EN => JOB_FULL_NAMES_EN[job_id as usize],
+        Locale::DE => JOB_FULL_NAMES_DE[job_id as usize],
+        Locale::FR => JOB_FULL_NAMES_FR[job_id as usize],
+        Locale::JP => JOB_FULL_NAMES_JP[job_id as usize],
+    }
+}
+
 pub static ITEM_NAMES_EN: phf::Map<u32, &str> = include!("../data/item_names_en.rs");
 pub static ITEM_NAMES_DE: phf::Map<u32, &str> = include!("../data/item_names_de.rs");
 pub static ITEM_NAMES_FR: phf::Map<u32, &str> = include!("../data/item_names_fr.rs");
@@ -67,6 +133,34 @@ pub const fn action_name(action: Action, locale: Locale) -> &'static str {
     }
 }
 
+/// Localized action names as a method on `Action`, e.g. `Action::MuscleMemory.display_name(Locale::DE)`.
+pub trait ActionDisplayName {
+    /// Name of the action as it appears in the game client of the given language.
+    fn display_name(&self, locale: Locale) -> &'static str;
+}
+
+impl ActionDisplayName for Action {
+    fn display_name(&self, locale: Locale) -> &'static str {
+        action_name(*self, locale)
+    }
+}
+
+/// Exports the rotation as in-game macro text in the given language, with one `/ac` line per action.
+/// Each line waits for the animation of its action to finish.
+pub fn export_macro(actions: &[Action], locale: Locale) -> String {
+    actions
+        .iter()
+        .map(|action| {
+            format!(
+                "/ac \"{}\" <wait.{}>",
+                action.display_name(locale),
+                action.time_cost()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 const fn action_name_en(action: Action) -> &'static str {
     match action {
         Action::BasicSynthesis => "Basic Synthesis",
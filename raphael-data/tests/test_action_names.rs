@@ -0,0 +1,53 @@
+use raphael_data::*;
+use raphael_sim::Action;
+
+#[test]
+fn test_action_names_localized() {
+    assert_eq!(
+        action_name(Action::MuscleMemory, Locale::EN),
+        "Muscle Memory"
+    );
+    assert_eq!(
+        action_name(Action::MuscleMemory, Locale::DE),
+        "Motorisches Gedächtnis"
+    );
+    assert_eq!(action_name(Action::WasteNot2, Locale::EN), "Waste Not II");
+    assert_eq!(
+        action_name(Action::WasteNot2, Locale::DE),
+        "Nachhaltigkeit II"
+    );
+}
+
+#[test]
+fn test_parse_locale() {
+    assert_eq!("EN".parse::<Locale>(), Ok(Locale::EN));
+    assert_eq!("de".parse::<Locale>(), Ok(Locale::DE));
+    assert!("XX".parse::<Locale>().is_err());
+}
+
+#[test]
+fn test_display_name() {
+    assert_eq!(
+        Action::Manipulation.display_name(Locale::EN),
+        "Manipulation"
+    );
+    assert_eq!(
+        Action::Manipulation.display_name(Locale::FR),
+        "Manipulation"
+    );
+    assert_eq!(Action::BasicTouch.display_name(Locale::EN), "Basic Touch");
+    assert_eq!(Action::BasicTouch.display_name(Locale::DE), "Veredelung");
+}
+
+#[test]
+fn test_export_macro() {
+    let actions = [Action::MuscleMemory, Action::BasicTouch];
+    assert_eq!(
+        export_macro(&actions, Locale::EN),
+        "/ac \"Muscle Memory\" <wait.3>\n/ac \"Basic Touch\" <wait.3>"
+    );
+    assert_eq!(
+        export_macro(&actions, Locale::DE),
+        "/ac \"Motorisches Gedächtnis\" <wait.3>\n/ac \"Veredelung\" <wait.3>"
+    );
+}
@@ -0,0 +1,26 @@
+use raphael_data::*;
+
+#[test]
+fn test_collectability_tiers() {
+    assert_eq!(collectability_tiers(1000), [550, 750, 950]);
+    assert_eq!(collectability_tiers(1234), [678, 925, 1172]);
+}
+
+#[test]
+fn test_collectability_result() {
+    let tiers = collectability_tiers(1000);
+    let result = |tier, quality_to_next_tier| CollectabilityTier {
+        tier,
+        quality_to_next_tier,
+    };
+    assert_eq!(collectability_result(0, &tiers), result(0, Some(550)));
+    assert_eq!(collectability_result(549, &tiers), result(0, Some(1)));
+    assert_eq!(collectability_result(550, &tiers), result(1, Some(200)));
+    assert_eq!(collectability_result(749, &tiers), result(1, Some(1)));
+    assert_eq!(collectability_result(750, &tiers), result(2, Some(200)));
+    assert_eq!(collectability_result(949, &tiers), result(2, Some(1)));
+    assert_eq!(collectability_result(950, &tiers), result(3, None));
+    assert_eq!(collectability_result(1000, &tiers), result(3, None));
+    // Without tiers there is no tier to reach
+    assert_eq!(collectability_result(0, &[]), result(0, None));
+}
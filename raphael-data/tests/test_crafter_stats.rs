@@ -0,0 +1,60 @@
+use raphael_data::*;
+
+fn find_recipe(item_name: &'static str) -> Option<Recipe> {
+    RECIPES
+        .values()
+        .find(|recipe| {
+            get_item_name(recipe.item_id, false, Locale::EN).as_deref() == Some(item_name)
+        })
+        .copied()
+}
+
+#[test]
+fn test_delta() {
+    let crafter_stats = CrafterStats {
+        craftsmanship: 4900,
+        control: 4800,
+        cp: 620,
+        ..Default::default()
+    };
+    let other = CrafterStats {
+        craftsmanship: 5000,
+        control: 4700,
+        cp: 620,
+        ..Default::default()
+    };
+    assert_eq!(
+        crafter_stats.delta(&other),
+        StatDelta {
+            craftsmanship: -100,
+            control: 100,
+            cp: 0,
+        }
+    );
+    assert_eq!(other.delta(&crafter_stats).craftsmanship, 100);
+    assert_eq!(crafter_stats.delta(&crafter_stats), StatDelta::default());
+}
+
+#[test]
+fn test_meets_requirement() {
+    let recipe = find_recipe("Coronal Wristband").unwrap();
+    assert_eq!((recipe.req_craftsmanship, recipe.req_control), (391, 374));
+    let crafter_stats = CrafterStats {
+        craftsmanship: 391,
+        control: 374,
+        ..Default::default()
+    };
+    assert!(crafter_stats.meets(&recipe));
+    let crafter_stats = CrafterStats {
+        craftsmanship: 391,
+        control: 373,
+        ..Default::default()
+    };
+    assert!(!crafter_stats.meets(&recipe));
+    let crafter_stats = CrafterStats {
+        craftsmanship: 390,
+        control: 374,
+        ..Default::default()
+    };
+    assert!(!crafter_stats.meets(&recipe));
+}
@@ -1,5 +1,5 @@
 use raphael_data::*;
-use raphael_sim::{Action, ActionMask, Settings};
+use raphael_sim::{Action, ActionMask, CpCostOverrides, RoundingMode, Settings};
 
 fn find_recipe(item_name: &'static str) -> Option<Recipe> {
     for recipe in RECIPES.values() {
@@ -20,6 +20,36 @@ fn ingredient_names(recipe: Recipe) -> Vec<String> {
         .collect()
 }
 
+#[test]
+fn test_base_progress_and_quality() {
+    // Ceremonial Gunblade
+    // 5428/5236/645 + HQ Ceviche (+150 Craftsmanship) + HQ Cunning Tisane
+    let recipe = find_recipe("Ceremonial Gunblade").unwrap();
+    let rlvl = RLVLS[usize::from(recipe.recipe_level)];
+    assert_eq!(
+        base_progress(5428 + 150, rlvl.progress_div, rlvl.progress_mod),
+        297
+    );
+    assert_eq!(base_quality(5236, rlvl.quality_div, rlvl.quality_mod), 288);
+    // Matches the base values of the full game settings
+    let crafter_stats = CrafterStats {
+        craftsmanship: 5428,
+        control: 5236,
+        cp: 645,
+        level: 100,
+        manipulation: true,
+        heart_and_soul: false,
+        quick_innovation: false,
+    };
+    let ceviche = MEALS
+        .iter()
+        .find(|meal| meal.item_id == 44842 && meal.hq)
+        .copied();
+    let settings = get_game_settings(recipe, None, crafter_stats, ceviche, None);
+    assert_eq!(settings.base_progress, 297);
+    assert_eq!(settings.base_quality, 288);
+}
+
 #[test]
 /// Verified in-game (patch 7.05)
 fn test_roast_chicken() {
@@ -51,6 +81,11 @@ fn test_roast_chicken() {
                 .remove(Action::QuickInnovation),
             adversarial: false,
             backload_progress: false,
+            quality_increase_cap: None,
+            progress_target: None,
+            progress_optional: false,
+            rounding_mode: RoundingMode::Floor,
+            cp_cost_overrides: CpCostOverrides::none(),
         }
     );
 }
@@ -87,6 +122,11 @@ fn test_turali_pineapple_ponzecake() {
                 .remove(Action::QuickInnovation),
             adversarial: false,
             backload_progress: false,
+            quality_increase_cap: None,
+            progress_target: None,
+            progress_optional: false,
+            rounding_mode: RoundingMode::Floor,
+            cp_cost_overrides: CpCostOverrides::none(),
         }
     );
     let initial_quality = get_initial_quality(crafter_stats, recipe, [0, 1, 0, 0, 0, 0]);
@@ -124,6 +164,11 @@ fn test_smaller_water_otter_hardware() {
                 .remove(Action::QuickInnovation),
             adversarial: false,
             backload_progress: false,
+            quality_increase_cap: None,
+            progress_target: None,
+            progress_optional: false,
+            rounding_mode: RoundingMode::Floor,
+            cp_cost_overrides: CpCostOverrides::none(),
         }
     );
 }
@@ -156,6 +201,11 @@ fn test_grade_8_tincture() {
             allowed_actions: ActionMask::all().remove(Action::QuickInnovation),
             adversarial: false,
             backload_progress: false,
+            quality_increase_cap: None,
+            progress_target: None,
+            progress_optional: false,
+            rounding_mode: RoundingMode::Floor,
+            cp_cost_overrides: CpCostOverrides::none(),
         }
     );
 }
@@ -192,6 +242,11 @@ fn test_claro_walnut_spinning_wheel() {
                 .remove(Action::HeartAndSoul),
             adversarial: false,
             backload_progress: false,
+            quality_increase_cap: None,
+            progress_target: None,
+            progress_optional: false,
+            rounding_mode: RoundingMode::Floor,
+            cp_cost_overrides: CpCostOverrides::none(),
         }
     );
 }
@@ -225,6 +280,11 @@ fn test_habitat_chair_lv100() {
                 .remove(Action::QuickInnovation),
             adversarial: false,
             backload_progress: false,
+            quality_increase_cap: None,
+            progress_target: None,
+            progress_optional: false,
+            rounding_mode: RoundingMode::Floor,
+            cp_cost_overrides: CpCostOverrides::none(),
         }
     );
 }
@@ -259,6 +319,11 @@ fn test_habitat_chair_lv97() {
                 .remove(Action::QuickInnovation),
             adversarial: false,
             backload_progress: false,
+            quality_increase_cap: None,
+            progress_target: None,
+            progress_optional: false,
+            rounding_mode: RoundingMode::Floor,
+            cp_cost_overrides: CpCostOverrides::none(),
         }
     );
 }
@@ -293,6 +358,11 @@ fn test_habitat_chair_lv98() {
                 .remove(Action::QuickInnovation),
             adversarial: false,
             backload_progress: false,
+            quality_increase_cap: None,
+            progress_target: None,
+            progress_optional: false,
+            rounding_mode: RoundingMode::Floor,
+            cp_cost_overrides: CpCostOverrides::none(),
         }
     );
 }
@@ -327,6 +397,11 @@ fn test_standard_indurate_rings_lv93() {
                 .remove(Action::QuickInnovation),
             adversarial: false,
             backload_progress: false,
+            quality_increase_cap: None,
+            progress_target: None,
+            progress_optional: false,
+            rounding_mode: RoundingMode::Floor,
+            cp_cost_overrides: CpCostOverrides::none(),
         }
     );
 }
@@ -361,6 +436,11 @@ fn test_lunar_alloy_ingots_lv90() {
                 .remove(Action::QuickInnovation),
             adversarial: false,
             backload_progress: false,
+            quality_increase_cap: None,
+            progress_target: None,
+            progress_optional: false,
+            rounding_mode: RoundingMode::Floor,
+            cp_cost_overrides: CpCostOverrides::none(),
         }
     );
 }
@@ -395,6 +475,11 @@ fn test_standard_high_density_fiberboard_lv91() {
                 .remove(Action::QuickInnovation),
             adversarial: false,
             backload_progress: false,
+            quality_increase_cap: None,
+            progress_target: None,
+            progress_optional: false,
+            rounding_mode: RoundingMode::Floor,
+            cp_cost_overrides: CpCostOverrides::none(),
         }
     );
 }
@@ -428,6 +513,11 @@ fn test_lunar_alloy_ingots_lv10() {
                 .remove(Action::QuickInnovation),
             adversarial: false,
             backload_progress: false,
+            quality_increase_cap: None,
+            progress_target: None,
+            progress_optional: false,
+            rounding_mode: RoundingMode::Floor,
+            cp_cost_overrides: CpCostOverrides::none(),
         }
     );
 }
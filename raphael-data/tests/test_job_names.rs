@@ -0,0 +1,27 @@
+use raphael_data::*;
+
+#[test]
+fn test_job_names() {
+    let expected = [
+        ("CRP", "Carpenter"),
+        ("BSM", "Blacksmith"),
+        ("ARM", "Armorer"),
+        ("GSM", "Goldsmith"),
+        ("LTW", "Leatherworker"),
+        ("WVR", "Weaver"),
+        ("ALC", "Alchemist"),
+        ("CUL", "Culinarian"),
+    ];
+    for (job_id, (abbreviation, full_name)) in expected.into_iter().enumerate() {
+        let job_id = job_id as u8;
+        assert_eq!(get_job_name(job_id, Locale::EN), abbreviation);
+        assert_eq!(get_job_full_name(job_id, Locale::EN), full_name);
+    }
+}
+
+#[test]
+fn test_job_full_names_localized() {
+    assert_eq!(get_job_full_name(3, Locale::DE), "Goldschmied");
+    assert_eq!(get_job_full_name(3, Locale::FR), "Orfèvre");
+    assert_eq!(get_job_full_name(3, Locale::JP), "彫金師");
+}
@@ -14,6 +14,11 @@ fn bench_use_action(c: &mut Criterion) {
         allowed_actions: ActionMask::all(),
         adversarial: false,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let state = SimulationState::new(&settings);
 
@@ -37,6 +42,58 @@ fn bench_use_action(c: &mut Criterion) {
     group.finish();
 }
 
+fn bench_simulate_into(c: &mut Criterion) {
+    let settings = Settings {
+        max_cp: 1000,
+        max_durability: 80,
+        max_progress: 50000,
+        max_quality: 50000,
+        base_progress: 123,
+        base_quality: 321,
+        job_level: 100,
+        allowed_actions: ActionMask::all(),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    let actions = [
+        Action::MuscleMemory,
+        Action::Manipulation,
+        Action::Veneration,
+        Action::Groundwork,
+        Action::Innovation,
+        Action::PreparatoryTouch,
+        Action::BasicTouch,
+        Action::StandardTouch,
+        Action::AdvancedTouch,
+        Action::GreatStrides,
+        Action::ByregotsBlessing,
+    ];
+    let random_conditions = || {
+        let mut rng = rand::thread_rng();
+        let conditions: [Condition; 11] = std::array::from_fn(|_| match rng.gen_range(0..4) {
+            0 => Condition::Good,
+            1 => Condition::Excellent,
+            2 => Condition::Poor,
+            _ => Condition::Normal,
+        });
+        (SimulationState::new(&settings), conditions)
+    };
+    c.bench_function("simulate_into", |b| {
+        b.iter_batched_ref(
+            random_conditions,
+            |(state, conditions)| {
+                SimulationState::simulate_into(state, black_box(&actions), conditions, &settings)
+            },
+            BatchSize::SmallInput,
+        );
+    });
+}
+
 fn bench_tick_effects(c: &mut Criterion) {
     fn random_effects() -> Effects {
         let mut rng = rand::thread_rng();
@@ -51,5 +108,10 @@ fn bench_tick_effects(c: &mut Criterion) {
     });
 }
 
-criterion_group!(bench_simulator, bench_use_action, bench_tick_effects);
+criterion_group!(
+    bench_simulator,
+    bench_use_action,
+    bench_simulate_into,
+    bench_tick_effects
+);
 criterion_main!(bench_simulator);
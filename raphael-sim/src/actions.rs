@@ -1,11 +1,40 @@
-use crate::{ActionMask, Condition, Settings, SimulationState};
+use crate::{ActionMask, Condition, Effects, Settings, SimulationState};
+
+/// Percentage by which Inner Quiet scales the Quality increase of actions.
+/// Each stack adds 10% at every job level, unlike the base Progress and Quality, which depend on the level of the recipe.
+const fn inner_quiet_modifier(inner_quiet: u8) -> u64 {
+    100 + 10 * inner_quiet as u64
+}
+
+/// Control scaled by `inner_quiet` stacks of Inner Quiet, i.e. `base_control * (1 + 0.1 * inner_quiet)`, rounded down.
+/// Uses the same scaling as the Quality increase of actions.
+pub const fn effective_control(base_control: u16, inner_quiet: u8) -> u32 {
+    (base_control as u64 * inner_quiet_modifier(inner_quiet) / 100) as u32
+}
+
+/// Quality increase of a Byregot's Blessing at 10 stacks of Inner Quiet with Innovation and Great Strides under Normal condition.
+/// This is a quick theoretical ceiling for the finisher of a rotation that ignores CP and durability, not a reachable Quality.
+pub fn max_quality_from_inner_quiet(settings: &Settings) -> u32 {
+    let state = SimulationState {
+        effects: Effects::new()
+            .with_inner_quiet(10)
+            .with_innovation(1)
+            .with_great_strides(1)
+            .with_allow_quality_actions(true),
+        ..SimulationState::new(settings)
+    };
+    ByregotsBlessing::quality_increase(&state, settings, Condition::Normal)
+}
 
 pub trait ActionImpl {
+    const ACTION: Action;
     const LEVEL_REQUIREMENT: u8;
     /// All bits of this mask must be present in the settings' action mask for the action to be enabled.
     const ACTION_MASK: ActionMask;
     /// Does this action trigger ticking effects (e.g. Manipulation)?
     const TICK_EFFECTS: bool = true;
+    /// Combo that reduces the CP cost of this action, together with the reduced CP cost.
+    const COMBO_CP_COST: Option<(Combo, u16)> = None;
 
     fn precondition(
         _state: &SimulationState,
@@ -28,7 +57,8 @@ pub trait ActionImpl {
         if state.effects.veneration() != 0 {
             effect_mod += 50;
         }
-        (settings.base_progress as u64 * efficiency_mod * effect_mod / 10000) as u32
+        let progress = settings.base_progress as u64 * efficiency_mod * effect_mod;
+        settings.rounding_mode.divide(progress, 10000) as u32
     }
 
     fn quality_increase(state: &SimulationState, settings: &Settings, condition: Condition) -> u32 {
@@ -46,13 +76,13 @@ pub trait ActionImpl {
         if state.effects.great_strides() != 0 {
             effect_mod += 100;
         }
-        let inner_quiet_mod = 100 + 10 * state.effects.inner_quiet() as u64;
-        (settings.base_quality as u64
+        let inner_quiet_mod = inner_quiet_modifier(state.effects.inner_quiet());
+        let quality = settings.base_quality as u64
             * efficieny_mod
             * condition_mod
             * effect_mod
-            * inner_quiet_mod
-            / 100_000_000) as u32
+            * inner_quiet_mod;
+        settings.cap_quality_increase(settings.rounding_mode.divide(quality, 100_000_000) as u32)
     }
 
     fn durability_cost(state: &SimulationState, settings: &Settings, _condition: Condition) -> u16 {
@@ -66,7 +96,13 @@ pub trait ActionImpl {
     }
 
     fn cp_cost(state: &SimulationState, settings: &Settings, _condition: Condition) -> u16 {
-        Self::base_cp_cost(state, settings)
+        match Self::COMBO_CP_COST {
+            Some((combo, cp_cost)) if state.effects.combo() == combo => cp_cost,
+            _ => settings
+                .cp_cost_overrides
+                .get(Self::ACTION)
+                .unwrap_or_else(|| Self::base_cp_cost(state, settings)),
+        }
     }
 
     fn base_progress_increase(_state: &SimulationState, _settings: &Settings) -> u32 {
@@ -92,6 +128,7 @@ pub trait ActionImpl {
 
 pub struct BasicSynthesis {}
 impl ActionImpl for BasicSynthesis {
+    const ACTION: Action = Action::BasicSynthesis;
     const LEVEL_REQUIREMENT: u8 = 1;
     const ACTION_MASK: ActionMask = ActionMask::none().add(Action::BasicSynthesis);
     fn base_progress_increase(_state: &SimulationState, settings: &Settings) -> u32 {
@@ -107,6 +144,7 @@ impl BasicTouch {
     pub const CP_COST: u16 = 18;
 }
 impl ActionImpl for BasicTouch {
+    const ACTION: Action = Action::BasicTouch;
     const LEVEL_REQUIREMENT: u8 = 5;
     const ACTION_MASK: ActionMask = ActionMask::none().add(Action::BasicTouch);
     fn base_quality_increase(_state: &SimulationState, _settings: &Settings) -> u32 {
@@ -128,13 +166,15 @@ impl MasterMend {
     pub const CP_COST: u16 = 88;
 }
 impl ActionImpl for MasterMend {
+    const ACTION: Action = Action::MasterMend;
     const LEVEL_REQUIREMENT: u8 = 7;
     const ACTION_MASK: ActionMask = ActionMask::none().add(Action::MasterMend);
     fn base_cp_cost(_state: &SimulationState, _settings: &Settings) -> u16 {
         Self::CP_COST
     }
     fn transform_post(state: &mut SimulationState, settings: &Settings, _condition: Condition) {
-        state.durability = std::cmp::min(settings.max_durability, state.durability + 30);
+        state.durability =
+            std::cmp::min(settings.max_durability, state.durability.saturating_add(30));
     }
 }
 
@@ -143,6 +183,7 @@ impl Observe {
     pub const CP_COST: u16 = 7;
 }
 impl ActionImpl for Observe {
+    const ACTION: Action = Action::Observe;
     const LEVEL_REQUIREMENT: u8 = 13;
     const ACTION_MASK: ActionMask = ActionMask::none().add(Action::Observe);
     fn base_cp_cost(_state: &SimulationState, _settings: &Settings) -> u16 {
@@ -155,6 +196,7 @@ impl ActionImpl for Observe {
 
 pub struct TricksOfTheTrade {}
 impl ActionImpl for TricksOfTheTrade {
+    const ACTION: Action = Action::TricksOfTheTrade;
     const LEVEL_REQUIREMENT: u8 = 13;
     const ACTION_MASK: ActionMask = ActionMask::none().add(Action::TricksOfTheTrade);
     fn precondition(
@@ -173,7 +215,7 @@ impl ActionImpl for TricksOfTheTrade {
         Ok(())
     }
     fn transform_post(state: &mut SimulationState, settings: &Settings, condition: Condition) {
-        state.cp = std::cmp::min(settings.max_cp, state.cp + 20);
+        state.cp = std::cmp::min(settings.max_cp, state.cp.saturating_add(20));
         if condition != Condition::Good && condition != Condition::Excellent {
             state.effects.set_heart_and_soul_active(false);
         }
@@ -185,6 +227,7 @@ impl WasteNot {
     pub const CP_COST: u16 = 56;
 }
 impl ActionImpl for WasteNot {
+    const ACTION: Action = Action::WasteNot;
     const LEVEL_REQUIREMENT: u8 = 15;
     const ACTION_MASK: ActionMask = ActionMask::none().add(Action::WasteNot);
     fn base_cp_cost(_state: &SimulationState, _settings: &Settings) -> u16 {
@@ -200,6 +243,7 @@ impl Veneration {
     pub const CP_COST: u16 = 18;
 }
 impl ActionImpl for Veneration {
+    const ACTION: Action = Action::Veneration;
     const LEVEL_REQUIREMENT: u8 = 15;
     const ACTION_MASK: ActionMask = ActionMask::none().add(Action::Veneration);
     fn base_cp_cost(_state: &SimulationState, _settings: &Settings) -> u16 {
@@ -212,19 +256,18 @@ impl ActionImpl for Veneration {
 
 pub struct StandardTouch {}
 impl ActionImpl for StandardTouch {
+    const ACTION: Action = Action::StandardTouch;
     const LEVEL_REQUIREMENT: u8 = 18;
     const ACTION_MASK: ActionMask = ActionMask::none().add(Action::StandardTouch);
+    const COMBO_CP_COST: Option<(Combo, u16)> = Some((Combo::BasicTouch, 18));
     fn base_quality_increase(_state: &SimulationState, _settings: &Settings) -> u32 {
         125
     }
     fn base_durability_cost(_state: &SimulationState, _settings: &Settings) -> u16 {
         10
     }
-    fn base_cp_cost(state: &SimulationState, _settings: &Settings) -> u16 {
-        match state.effects.combo() {
-            Combo::BasicTouch => 18,
-            _ => 32,
-        }
+    fn base_cp_cost(_state: &SimulationState, _settings: &Settings) -> u16 {
+        32
     }
     fn combo(state: &SimulationState, _settings: &Settings, _condition: Condition) -> Combo {
         match state.effects.combo() {
@@ -239,6 +282,7 @@ impl GreatStrides {
     pub const CP_COST: u16 = 32;
 }
 impl ActionImpl for GreatStrides {
+    const ACTION: Action = Action::GreatStrides;
     const LEVEL_REQUIREMENT: u8 = 21;
     const ACTION_MASK: ActionMask = ActionMask::none().add(Action::GreatStrides);
     fn base_cp_cost(_state: &SimulationState, _settings: &Settings) -> u16 {
@@ -254,6 +298,7 @@ impl Innovation {
     pub const CP_COST: u16 = 18;
 }
 impl ActionImpl for Innovation {
+    const ACTION: Action = Action::Innovation;
     const LEVEL_REQUIREMENT: u8 = 26;
     const ACTION_MASK: ActionMask = ActionMask::none().add(Action::Innovation);
     fn base_cp_cost(_state: &SimulationState, _settings: &Settings) -> u16 {
@@ -269,6 +314,7 @@ impl WasteNot2 {
     pub const CP_COST: u16 = 98;
 }
 impl ActionImpl for WasteNot2 {
+    const ACTION: Action = Action::WasteNot2;
     const LEVEL_REQUIREMENT: u8 = 47;
     const ACTION_MASK: ActionMask = ActionMask::none().add(Action::WasteNot2);
     fn base_cp_cost(_state: &SimulationState, _settings: &Settings) -> u16 {
@@ -281,6 +327,7 @@ impl ActionImpl for WasteNot2 {
 
 pub struct ByregotsBlessing {}
 impl ActionImpl for ByregotsBlessing {
+    const ACTION: Action = Action::ByregotsBlessing;
     const LEVEL_REQUIREMENT: u8 = 50;
     const ACTION_MASK: ActionMask = ActionMask::none().add(Action::ByregotsBlessing);
     fn precondition(
@@ -309,6 +356,7 @@ impl ActionImpl for ByregotsBlessing {
 
 pub struct PreciseTouch {}
 impl ActionImpl for PreciseTouch {
+    const ACTION: Action = Action::PreciseTouch;
     const LEVEL_REQUIREMENT: u8 = 53;
     const ACTION_MASK: ActionMask = ActionMask::none().add(Action::PreciseTouch);
     fn precondition(
@@ -344,6 +392,7 @@ impl ActionImpl for PreciseTouch {
 
 pub struct MuscleMemory {}
 impl ActionImpl for MuscleMemory {
+    const ACTION: Action = Action::MuscleMemory;
     const LEVEL_REQUIREMENT: u8 = 54;
     const ACTION_MASK: ActionMask = ActionMask::none().add(Action::MuscleMemory);
     fn precondition(
@@ -372,6 +421,7 @@ impl ActionImpl for MuscleMemory {
 
 pub struct CarefulSynthesis {}
 impl ActionImpl for CarefulSynthesis {
+    const ACTION: Action = Action::CarefulSynthesis;
     const LEVEL_REQUIREMENT: u8 = 62;
     const ACTION_MASK: ActionMask = ActionMask::none().add(Action::CarefulSynthesis);
     fn base_progress_increase(_state: &SimulationState, settings: &Settings) -> u32 {
@@ -393,6 +443,7 @@ impl Manipulation {
     pub const CP_COST: u16 = 96;
 }
 impl ActionImpl for Manipulation {
+    const ACTION: Action = Action::Manipulation;
     const LEVEL_REQUIREMENT: u8 = 65;
     const ACTION_MASK: ActionMask = ActionMask::none().add(Action::Manipulation);
     fn base_cp_cost(_state: &SimulationState, _settings: &Settings) -> u16 {
@@ -408,6 +459,7 @@ impl ActionImpl for Manipulation {
 
 pub struct PrudentTouch {}
 impl ActionImpl for PrudentTouch {
+    const ACTION: Action = Action::PrudentTouch;
     const LEVEL_REQUIREMENT: u8 = 66;
     const ACTION_MASK: ActionMask = ActionMask::none().add(Action::PrudentTouch);
     fn precondition(
@@ -433,24 +485,24 @@ impl ActionImpl for PrudentTouch {
 
 pub struct AdvancedTouch {}
 impl ActionImpl for AdvancedTouch {
+    const ACTION: Action = Action::AdvancedTouch;
     const LEVEL_REQUIREMENT: u8 = 68;
     const ACTION_MASK: ActionMask = ActionMask::none().add(Action::AdvancedTouch);
+    const COMBO_CP_COST: Option<(Combo, u16)> = Some((Combo::StandardTouch, 18));
     fn base_quality_increase(_state: &SimulationState, _settings: &Settings) -> u32 {
         150
     }
     fn base_durability_cost(_state: &SimulationState, _settings: &Settings) -> u16 {
         10
     }
-    fn base_cp_cost(state: &SimulationState, _settings: &Settings) -> u16 {
-        match state.effects.combo() {
-            Combo::StandardTouch => 18,
-            _ => 46,
-        }
+    fn base_cp_cost(_state: &SimulationState, _settings: &Settings) -> u16 {
+        46
     }
 }
 
 pub struct Reflect {}
 impl ActionImpl for Reflect {
+    const ACTION: Action = Action::Reflect;
     const LEVEL_REQUIREMENT: u8 = 69;
     const ACTION_MASK: ActionMask = ActionMask::none().add(Action::Reflect);
     fn precondition(
@@ -483,6 +535,7 @@ impl PreparatoryTouch {
     pub const CP_COST: u16 = 40;
 }
 impl ActionImpl for PreparatoryTouch {
+    const ACTION: Action = Action::PreparatoryTouch;
     const LEVEL_REQUIREMENT: u8 = 71;
     const ACTION_MASK: ActionMask = ActionMask::none().add(Action::PreparatoryTouch);
     fn base_quality_increase(_state: &SimulationState, _settings: &Settings) -> u32 {
@@ -502,6 +555,7 @@ impl ActionImpl for PreparatoryTouch {
 
 pub struct Groundwork {}
 impl ActionImpl for Groundwork {
+    const ACTION: Action = Action::Groundwork;
     const LEVEL_REQUIREMENT: u8 = 72;
     const ACTION_MASK: ActionMask = ActionMask::none().add(Action::Groundwork);
     fn base_progress_increase(state: &SimulationState, settings: &Settings) -> u32 {
@@ -524,6 +578,7 @@ impl ActionImpl for Groundwork {
 
 pub struct DelicateSynthesis {}
 impl ActionImpl for DelicateSynthesis {
+    const ACTION: Action = Action::DelicateSynthesis;
     const LEVEL_REQUIREMENT: u8 = 76;
     const ACTION_MASK: ActionMask = ActionMask::none().add(Action::DelicateSynthesis);
     fn base_progress_increase(_state: &SimulationState, settings: &Settings) -> u32 {
@@ -545,6 +600,7 @@ impl ActionImpl for DelicateSynthesis {
 
 pub struct IntensiveSynthesis {}
 impl ActionImpl for IntensiveSynthesis {
+    const ACTION: Action = Action::IntensiveSynthesis;
     const LEVEL_REQUIREMENT: u8 = 78;
     const ACTION_MASK: ActionMask = ActionMask::none().add(Action::IntensiveSynthesis);
     fn precondition(
@@ -580,6 +636,7 @@ impl ActionImpl for IntensiveSynthesis {
 
 pub struct TrainedEye {}
 impl ActionImpl for TrainedEye {
+    const ACTION: Action = Action::TrainedEye;
     const LEVEL_REQUIREMENT: u8 = 80;
     const ACTION_MASK: ActionMask = ActionMask::none().add(Action::TrainedEye);
     fn precondition(
@@ -597,7 +654,7 @@ impl ActionImpl for TrainedEye {
         settings: &Settings,
         _condition: Condition,
     ) -> u32 {
-        u32::from(settings.max_quality)
+        settings.cap_quality_increase(u32::from(settings.max_quality))
     }
     fn base_quality_increase(_state: &SimulationState, settings: &Settings) -> u32 {
         u32::from(settings.max_quality)
@@ -612,6 +669,7 @@ impl ActionImpl for TrainedEye {
 
 pub struct HeartAndSoul {}
 impl ActionImpl for HeartAndSoul {
+    const ACTION: Action = Action::HeartAndSoul;
     const LEVEL_REQUIREMENT: u8 = 86;
     const ACTION_MASK: ActionMask = ActionMask::none().add(Action::HeartAndSoul);
     const TICK_EFFECTS: bool = false;
@@ -633,6 +691,7 @@ impl ActionImpl for HeartAndSoul {
 
 pub struct PrudentSynthesis {}
 impl ActionImpl for PrudentSynthesis {
+    const ACTION: Action = Action::PrudentSynthesis;
     const LEVEL_REQUIREMENT: u8 = 88;
     const ACTION_MASK: ActionMask = ActionMask::none().add(Action::PrudentSynthesis);
     fn precondition(
@@ -658,6 +717,7 @@ impl ActionImpl for PrudentSynthesis {
 
 pub struct TrainedFinesse {}
 impl ActionImpl for TrainedFinesse {
+    const ACTION: Action = Action::TrainedFinesse;
     const LEVEL_REQUIREMENT: u8 = 90;
     const ACTION_MASK: ActionMask = ActionMask::none().add(Action::TrainedFinesse);
     fn precondition(
@@ -683,6 +743,7 @@ impl RefinedTouch {
     pub const CP_COST: u16 = 24;
 }
 impl ActionImpl for RefinedTouch {
+    const ACTION: Action = Action::RefinedTouch;
     const LEVEL_REQUIREMENT: u8 = 92;
     const ACTION_MASK: ActionMask = ActionMask::none().add(Action::RefinedTouch);
     fn precondition(
@@ -712,6 +773,7 @@ impl ActionImpl for RefinedTouch {
 
 pub struct QuickInnovation {}
 impl ActionImpl for QuickInnovation {
+    const ACTION: Action = Action::QuickInnovation;
     const LEVEL_REQUIREMENT: u8 = 96;
     const ACTION_MASK: ActionMask = ActionMask::none().add(Action::QuickInnovation);
     const TICK_EFFECTS: bool = false;
@@ -739,6 +801,7 @@ impl ImmaculateMend {
     pub const CP_COST: u16 = 112;
 }
 impl ActionImpl for ImmaculateMend {
+    const ACTION: Action = Action::ImmaculateMend;
     const LEVEL_REQUIREMENT: u8 = 98;
     const ACTION_MASK: ActionMask = ActionMask::none().add(Action::ImmaculateMend);
     fn base_cp_cost(_state: &SimulationState, _settings: &Settings) -> u16 {
@@ -751,6 +814,7 @@ impl ActionImpl for ImmaculateMend {
 
 pub struct TrainedPerfection {}
 impl ActionImpl for TrainedPerfection {
+    const ACTION: Action = Action::TrainedPerfection;
     const LEVEL_REQUIREMENT: u8 = 100;
     const ACTION_MASK: ActionMask = ActionMask::none().add(Action::TrainedPerfection);
     fn precondition(
@@ -831,9 +895,192 @@ impl Combo {
             _ => Self::SynthesisBegin,
         }
     }
+
+    /// Actions that can only be used in this combo or that are cheaper in this combo.
+    pub const fn next_actions(self) -> &'static [Action] {
+        match self {
+            Self::None => &[],
+            Self::SynthesisBegin => &[Action::MuscleMemory, Action::Reflect, Action::TrainedEye],
+            Self::BasicTouch => &[Action::StandardTouch, Action::RefinedTouch],
+            Self::StandardTouch => &[Action::AdvancedTouch],
+        }
+    }
 }
 
 impl Action {
+    /// Stable numeric id of the action, intended for serialization and FFI.
+    ///
+    /// Ids never change, even if the variants of `Action` are reordered. New actions get new ids.
+    pub const fn id(self) -> u16 {
+        match self {
+            Self::BasicSynthesis => 0,
+            Self::BasicTouch => 1,
+            Self::MasterMend => 2,
+            Self::Observe => 3,
+            Self::TricksOfTheTrade => 4,
+            Self::WasteNot => 5,
+            Self::Veneration => 6,
+            Self::StandardTouch => 7,
+            Self::GreatStrides => 8,
+            Self::Innovation => 9,
+            Self::WasteNot2 => 10,
+            Self::ByregotsBlessing => 11,
+            Self::PreciseTouch => 12,
+            Self::MuscleMemory => 13,
+            Self::CarefulSynthesis => 14,
+            Self::Manipulation => 15,
+            Self::PrudentTouch => 16,
+            Self::AdvancedTouch => 17,
+            Self::Reflect => 18,
+            Self::PreparatoryTouch => 19,
+            Self::Groundwork => 20,
+            Self::DelicateSynthesis => 21,
+            Self::IntensiveSynthesis => 22,
+            Self::TrainedEye => 23,
+            Self::HeartAndSoul => 24,
+            Self::PrudentSynthesis => 25,
+            Self::TrainedFinesse => 26,
+            Self::RefinedTouch => 27,
+            Self::QuickInnovation => 28,
+            Self::ImmaculateMend => 29,
+            Self::TrainedPerfection => 30,
+        }
+    }
+
+    /// Inverse of [`Action::id`]. Returns `None` if no action has the given id.
+    pub const fn from_id(id: u16) -> Option<Self> {
+        match id {
+            0 => Some(Self::BasicSynthesis),
+            1 => Some(Self::BasicTouch),
+            2 => Some(Self::MasterMend),
+            3 => Some(Self::Observe),
+            4 => Some(Self::TricksOfTheTrade),
+            5 => Some(Self::WasteNot),
+            6 => Some(Self::Veneration),
+            7 => Some(Self::StandardTouch),
+            8 => Some(Self::GreatStrides),
+            9 => Some(Self::Innovation),
+            10 => Some(Self::WasteNot2),
+            11 => Some(Self::ByregotsBlessing),
+            12 => Some(Self::PreciseTouch),
+            13 => Some(Self::MuscleMemory),
+            14 => Some(Self::CarefulSynthesis),
+            15 => Some(Self::Manipulation),
+            16 => Some(Self::PrudentTouch),
+            17 => Some(Self::AdvancedTouch),
+            18 => Some(Self::Reflect),
+            19 => Some(Self::PreparatoryTouch),
+            20 => Some(Self::Groundwork),
+            21 => Some(Self::DelicateSynthesis),
+            22 => Some(Self::IntensiveSynthesis),
+            23 => Some(Self::TrainedEye),
+            24 => Some(Self::HeartAndSoul),
+            25 => Some(Self::PrudentSynthesis),
+            26 => Some(Self::TrainedFinesse),
+            27 => Some(Self::RefinedTouch),
+            28 => Some(Self::QuickInnovation),
+            29 => Some(Self::ImmaculateMend),
+            30 => Some(Self::TrainedPerfection),
+            _ => None,
+        }
+    }
+
+    /// Whether the action reduces durability when used without any durability-related effects.
+    pub const fn costs_durability(self) -> bool {
+        matches!(
+            self,
+            Self::BasicSynthesis
+                | Self::BasicTouch
+                | Self::StandardTouch
+                | Self::ByregotsBlessing
+                | Self::PreciseTouch
+                | Self::MuscleMemory
+                | Self::CarefulSynthesis
+                | Self::PrudentTouch
+                | Self::AdvancedTouch
+                | Self::Reflect
+                | Self::PreparatoryTouch
+                | Self::Groundwork
+                | Self::DelicateSynthesis
+                | Self::IntensiveSynthesis
+                | Self::TrainedEye
+                | Self::PrudentSynthesis
+                | Self::RefinedTouch
+        )
+    }
+
+    /// Whether the action only applies an effect, without directly changing Progress, Quality or durability.
+    pub const fn is_buff(self) -> bool {
+        matches!(
+            self,
+            Self::WasteNot
+                | Self::Veneration
+                | Self::GreatStrides
+                | Self::Innovation
+                | Self::WasteNot2
+                | Self::Manipulation
+                | Self::HeartAndSoul
+                | Self::QuickInnovation
+                | Self::TrainedPerfection
+        )
+    }
+
+    /// Whether the action is redundant right after `prev`, where `effects` are the effects after using `prev`.
+    /// Using only one of the two actions then leads to a state that is at least as good as using both,
+    /// in one step less and with no more CP, so the transition can never be part of an optimal rotation in any solve mode.
+    ///
+    /// Re-applying a buff is only redundant if Manipulation has no turns left after `prev`. Otherwise the extra step
+    /// restores durability that the single buff doesn't, which may be needed to finish the craft.
+    /// Combining the mends is always redundant because Immaculate Mend alone already restores all durability.
+    pub const fn redundant_after(self, prev: Self, effects: Effects) -> bool {
+        match (prev, self) {
+            (Self::Innovation, Self::Innovation)
+            | (Self::Veneration, Self::Veneration)
+            | (Self::GreatStrides, Self::GreatStrides)
+            | (Self::WasteNot | Self::WasteNot2, Self::WasteNot | Self::WasteNot2) => {
+                effects.manipulation() == 0
+            }
+            (Self::MasterMend | Self::ImmaculateMend, Self::ImmaculateMend)
+            | (Self::ImmaculateMend, Self::MasterMend) => true,
+            _ => false,
+        }
+    }
+
+    /// The combo in which the action can be used or is discounted, i.e. the inverse of `Combo::next_actions`.
+    pub const fn required_combo(self) -> Option<Combo> {
+        match self {
+            Self::MuscleMemory | Self::Reflect | Self::TrainedEye => Some(Combo::SynthesisBegin),
+            Self::StandardTouch | Self::RefinedTouch => Some(Combo::BasicTouch),
+            Self::AdvancedTouch => Some(Combo::StandardTouch),
+            _ => None,
+        }
+    }
+
+    /// The combo that is active after using the action, assuming that the `required_combo` of the action was active.
+    /// Without the required combo, Standard Touch doesn't continue into Advanced Touch.
+    pub const fn to_combo(self) -> Combo {
+        match self {
+            Self::BasicTouch => Combo::BasicTouch,
+            Self::StandardTouch | Self::Observe => Combo::StandardTouch,
+            _ => Combo::None,
+        }
+    }
+
+    /// Inner Quiet stacks granted by the action, not taking the maximum of 10 stacks into account.
+    /// Byregot's Blessing consumes all stacks and Trained Finesse can only be used at the maximum, so both grant none.
+    pub const fn grants_inner_quiet(self) -> u8 {
+        match self {
+            Self::PreciseTouch | Self::Reflect | Self::PreparatoryTouch | Self::RefinedTouch => 2,
+            Self::BasicTouch
+            | Self::StandardTouch
+            | Self::PrudentTouch
+            | Self::AdvancedTouch
+            | Self::DelicateSynthesis
+            | Self::TrainedEye => 1,
+            _ => 0,
+        }
+    }
+
     pub const fn time_cost(self) -> u8 {
         match self {
             Self::BasicSynthesis => 3,
@@ -0,0 +1,67 @@
+use crate::{Action, Condition, Settings, SimulationState};
+
+/// CP and steps spent on a group of actions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ActionBudget {
+    pub cp: u16,
+    pub steps: u8,
+}
+
+/// Partition of the CP and steps of a rotation by what each action contributed to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RotationBudget {
+    /// Actions that increased Progress but not Quality.
+    pub progress: ActionBudget,
+    /// Actions that increased Quality. Actions that increased both Progress and Quality (e.g. Delicate Synthesis) count here.
+    pub quality: ActionBudget,
+    /// Buffs and durability management, i.e. all actions that neither increased Progress nor Quality.
+    pub other: ActionBudget,
+}
+
+/// Simulates the rotation under Normal conditions and partitions the CP and steps spent.
+/// CP restored by Tricks of the Trade is not subtracted, so the CP of the buckets sums up to `total_cp_cost`.
+/// Returns the index of the first action that cannot be used together with the reason.
+pub fn rotation_budget(
+    settings: &Settings,
+    actions: &[Action],
+) -> Result<RotationBudget, (usize, &'static str)> {
+    let mut budget = RotationBudget::default();
+    let mut state = SimulationState::new(settings);
+    for (index, action) in actions.iter().enumerate() {
+        let new_state = state
+            .use_action(*action, Condition::Normal, settings)
+            .map_err(|err| (index, err))?;
+        let quality_increased = new_state.quality + new_state.unreliable_quality
+            > state.quality + state.unreliable_quality;
+        let bucket = if quality_increased {
+            &mut budget.quality
+        } else if new_state.progress > state.progress {
+            &mut budget.progress
+        } else {
+            &mut budget.other
+        };
+        bucket.cp += state.cp.saturating_sub(new_state.cp);
+        bucket.steps += 1;
+        state = new_state;
+    }
+    Ok(budget)
+}
+
+/// Simulates the rotation under Normal conditions and returns the total CP spent, including combo discounts.
+/// CP restored by Tricks of the Trade is not subtracted.
+/// Returns the index of the first action that cannot be used together with the reason.
+pub fn total_cp_cost(
+    settings: &Settings,
+    actions: &[Action],
+) -> Result<u16, (usize, &'static str)> {
+    let mut cp_cost = 0;
+    let mut state = SimulationState::new(settings);
+    for (index, action) in actions.iter().enumerate() {
+        let new_state = state
+            .use_action(*action, Condition::Normal, settings)
+            .map_err(|err| (index, err))?;
+        cp_cost += state.cp.saturating_sub(new_state.cp);
+        state = new_state;
+    }
+    Ok(cp_cost)
+}
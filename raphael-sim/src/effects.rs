@@ -1,7 +1,13 @@
 use crate::{Combo, Settings};
 
+/// Effects of a state, packed into 32 bits.
+///
+/// Fields are laid out in declaration order, starting from the least significant bit.
+/// With the `serde` feature, `Effects` is serialized as the packed `u32`, so reordering or resizing fields
+/// changes the serialized form of stored states (see `test_effects_layout`).
 #[bitfield_struct::bitfield(u32, default = false)]
 #[derive(PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Effects {
     #[bits(4)]
     pub inner_quiet: u8,
@@ -31,7 +37,150 @@ pub struct Effects {
     pub combo: Combo,
 }
 
+/// Remaining duration (in steps) of each buff.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct BuffDurations {
+    pub innovation: u8,
+    pub veneration: u8,
+    pub great_strides: u8,
+    pub waste_not: u8,
+    pub manipulation: u8,
+}
+
+/// Field of `Effects`, for code that handles effects generically, see `Effects::get` and `Effects::set`.
+///
+/// `TrainedPerfection` and `HeartAndSoul` are the active effects, the `*Available` kinds are whether the action can still be used.
+/// The remaining kinds are flags, which have a value of 1 if set. The combo is not an effect and Final Appraisal is not modeled by the simulator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EffectKind {
+    InnerQuiet,
+    WasteNot,
+    Innovation,
+    Veneration,
+    GreatStrides,
+    MuscleMemory,
+    Manipulation,
+    TrainedPerfection,
+    HeartAndSoul,
+    TrainedPerfectionAvailable,
+    HeartAndSoulAvailable,
+    QuickInnovationAvailable,
+    AdversarialGuard,
+    AllowQualityActions,
+}
+
+impl EffectKind {
+    pub const ALL: [Self; 14] = [
+        Self::InnerQuiet,
+        Self::WasteNot,
+        Self::Innovation,
+        Self::Veneration,
+        Self::GreatStrides,
+        Self::MuscleMemory,
+        Self::Manipulation,
+        Self::TrainedPerfection,
+        Self::HeartAndSoul,
+        Self::TrainedPerfectionAvailable,
+        Self::HeartAndSoulAvailable,
+        Self::QuickInnovationAvailable,
+        Self::AdversarialGuard,
+        Self::AllowQualityActions,
+    ];
+
+    /// Whether the effect shows up as a buff in game, i.e. whether it can be listed by `Effects::active`.
+    /// The availability of actions and the flags of the solver are not buffs.
+    pub const fn is_buff(self) -> bool {
+        match self {
+            Self::InnerQuiet
+            | Self::WasteNot
+            | Self::Innovation
+            | Self::Veneration
+            | Self::GreatStrides
+            | Self::MuscleMemory
+            | Self::Manipulation
+            | Self::TrainedPerfection
+            | Self::HeartAndSoul => true,
+            Self::TrainedPerfectionAvailable
+            | Self::HeartAndSoulAvailable
+            | Self::QuickInnovationAvailable
+            | Self::AdversarialGuard
+            | Self::AllowQualityActions => false,
+        }
+    }
+}
+
 impl Effects {
+    /// Returns all active effects, each with its remaining duration (in steps) or, for Inner Quiet, its number of stacks.
+    /// Trained Perfection and Heart and Soul don't expire after a number of steps and are listed with a value of 1 while active.
+    /// Availability of actions and other flags are not listed.
+    pub fn active(self) -> Vec<(EffectKind, u8)> {
+        EffectKind::ALL
+            .into_iter()
+            .filter(|kind| kind.is_buff())
+            .map(|kind| (kind, self.get(kind)))
+            .filter(|(_, value)| *value != 0)
+            .collect()
+    }
+
+    /// Value of the effect, which is 0 or 1 for flags.
+    pub const fn get(self, kind: EffectKind) -> u8 {
+        match kind {
+            EffectKind::InnerQuiet => self.inner_quiet(),
+            EffectKind::WasteNot => self.waste_not(),
+            EffectKind::Innovation => self.innovation(),
+            EffectKind::Veneration => self.veneration(),
+            EffectKind::GreatStrides => self.great_strides(),
+            EffectKind::MuscleMemory => self.muscle_memory(),
+            EffectKind::Manipulation => self.manipulation(),
+            EffectKind::TrainedPerfection => self.trained_perfection_active() as u8,
+            EffectKind::HeartAndSoul => self.heart_and_soul_active() as u8,
+            EffectKind::TrainedPerfectionAvailable => self.trained_perfection_available() as u8,
+            EffectKind::HeartAndSoulAvailable => self.heart_and_soul_available() as u8,
+            EffectKind::QuickInnovationAvailable => self.quick_innovation_available() as u8,
+            EffectKind::AdversarialGuard => self.adversarial_guard() as u8,
+            EffectKind::AllowQualityActions => self.allow_quality_actions() as u8,
+        }
+    }
+
+    /// Sets the value of the effect. Flags are set if `value` is not 0.
+    /// Like the setters of the individual fields, panics if `value` doesn't fit into the field.
+    pub const fn set(&mut self, kind: EffectKind, value: u8) {
+        match kind {
+            EffectKind::InnerQuiet => self.set_inner_quiet(value),
+            EffectKind::WasteNot => self.set_waste_not(value),
+            EffectKind::Innovation => self.set_innovation(value),
+            EffectKind::Veneration => self.set_veneration(value),
+            EffectKind::GreatStrides => self.set_great_strides(value),
+            EffectKind::MuscleMemory => self.set_muscle_memory(value),
+            EffectKind::Manipulation => self.set_manipulation(value),
+            EffectKind::TrainedPerfection => self.set_trained_perfection_active(value != 0),
+            EffectKind::HeartAndSoul => self.set_heart_and_soul_active(value != 0),
+            EffectKind::TrainedPerfectionAvailable => {
+                self.set_trained_perfection_available(value != 0)
+            }
+            EffectKind::HeartAndSoulAvailable => self.set_heart_and_soul_available(value != 0),
+            EffectKind::QuickInnovationAvailable => self.set_quick_innovation_available(value != 0),
+            EffectKind::AdversarialGuard => self.set_adversarial_guard(value != 0),
+            EffectKind::AllowQualityActions => self.set_allow_quality_actions(value != 0),
+        }
+    }
+
+    pub const fn buff_durations(self) -> BuffDurations {
+        BuffDurations {
+            innovation: self.innovation(),
+            veneration: self.veneration(),
+            great_strides: self.great_strides(),
+            waste_not: self.waste_not(),
+            manipulation: self.manipulation(),
+        }
+    }
+
+    /// Number of Quality actions that can still benefit from the active Innovation before it expires.
+    /// Every action that ticks effects uses up one step of Innovation, so this assumes that only Quality actions are used from now on.
+    pub const fn remaining_innovation_actions(self) -> usize {
+        self.innovation() as usize
+    }
+
     /// Effects at synthesis begin
     pub fn initial(settings: &Settings) -> Self {
         Self::new()
@@ -75,6 +224,27 @@ impl Effects {
         Self::from_bits(self.into_bits() - (normal_effects_tick | adversarial_guard_tick))
     }
 
+    /// Returns the representative of all effects that behave identically for the next `step_horizon` actions.
+    ///
+    /// Buff durations beyond the horizon are clamped to the horizon, and Quality effects are removed
+    /// if Quality actions are no longer allowed.
+    /// States that only differ in canonically equal effects reach the same Progress, Quality, CP and durability with the same actions.
+    #[must_use]
+    pub const fn canonicalize(self, step_horizon: u8) -> Self {
+        let effects = self
+            .with_waste_not(min(self.waste_not(), step_horizon))
+            .with_innovation(min(self.innovation(), step_horizon))
+            .with_veneration(min(self.veneration(), step_horizon))
+            .with_great_strides(min(self.great_strides(), step_horizon))
+            .with_muscle_memory(min(self.muscle_memory(), step_horizon))
+            .with_manipulation(min(self.manipulation(), step_horizon));
+        if effects.allow_quality_actions() {
+            effects
+        } else {
+            effects.strip_quality_effects()
+        }
+    }
+
     /// Removes all effects that are only relevant for Quality.
     pub const fn strip_quality_effects(self) -> Self {
         self.with_allow_quality_actions(false)
@@ -86,6 +256,10 @@ impl Effects {
     }
 }
 
+const fn min(a: u8, b: u8) -> u8 {
+    if a < b { a } else { b }
+}
+
 const EFFECTS_BIT_0: u32 = Effects::new()
     .with_waste_not(1)
     .with_innovation(1)
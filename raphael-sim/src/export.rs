@@ -0,0 +1,147 @@
+use crate::{Action, Settings};
+
+/// Name of the action in the rotation format of the Teamcraft crafting simulator.
+pub const fn crafting_simulator_action_name(action: Action) -> &'static str {
+    match action {
+        Action::BasicSynthesis => "BasicSynthesis",
+        Action::BasicTouch => "BasicTouch",
+        Action::MasterMend => "MastersMend",
+        Action::Observe => "Observe",
+        Action::TricksOfTheTrade => "TricksOfTheTrade",
+        Action::WasteNot => "WasteNot",
+        Action::Veneration => "Veneration",
+        Action::StandardTouch => "StandardTouch",
+        Action::GreatStrides => "GreatStrides",
+        Action::Innovation => "Innovation",
+        Action::WasteNot2 => "WasteNotII",
+        Action::ByregotsBlessing => "ByregotsBlessing",
+        Action::PreciseTouch => "PreciseTouch",
+        Action::MuscleMemory => "MuscleMemory",
+        Action::CarefulSynthesis => "CarefulSynthesis",
+        Action::Manipulation => "Manipulation",
+        Action::PrudentTouch => "PrudentTouch",
+        Action::AdvancedTouch => "AdvancedTouch",
+        Action::Reflect => "Reflect",
+        Action::PreparatoryTouch => "PreparatoryTouch",
+        Action::Groundwork => "Groundwork",
+        Action::DelicateSynthesis => "DelicateSynthesis",
+        Action::IntensiveSynthesis => "IntensiveSynthesis",
+        Action::TrainedEye => "TrainedEye",
+        Action::HeartAndSoul => "HeartAndSoul",
+        Action::PrudentSynthesis => "PrudentSynthesis",
+        Action::TrainedFinesse => "TrainedFinesse",
+        Action::RefinedTouch => "RefinedTouch",
+        Action::QuickInnovation => "QuickInnovation",
+        Action::ImmaculateMend => "ImmaculateMend",
+        Action::TrainedPerfection => "TrainedPerfection",
+    }
+}
+
+/// Exports the rotation as JSON for the crafting simulator, e.g.
+/// `{"rotation":["MuscleMemory",...],"recipe":{"progress":...,"quality":...,"durability":...},"stats":{"level":...,"cp":...}}`.
+///
+/// `rotation` can be imported into the crafting simulator as-is.
+/// `recipe` and `stats` hold the stats that are known to the simulator settings, since Craftsmanship and Control are not.
+pub fn to_crafting_simulator_json(settings: &Settings, actions: &[Action]) -> String {
+    let rotation: Vec<String> = actions
+        .iter()
+        .map(|action| format!("\"{}\"", crafting_simulator_action_name(*action)))
+        .collect();
+    format!(
+        "{{\"rotation\":[{}],\"recipe\":{{\"progress\":{},\"quality\":{},\"durability\":{}}},\"stats\":{{\"level\":{},\"cp\":{}}}}}",
+        rotation.join(","),
+        settings.max_progress,
+        settings.max_quality,
+        settings.max_durability,
+        settings.job_level,
+        settings.max_cp,
+    )
+}
+
+/// Whether `next` continues the combo started by `prev`, e.g. Basic Touch > Standard Touch.
+fn continues_combo(prev: Action, next: Action) -> bool {
+    next.required_combo() == Some(prev.to_combo())
+}
+
+/// Splits the rotation into in-game macros of at most `lines_per_macro` actions each.
+///
+/// Each macro ends early instead of splitting a combo (e.g. Basic Touch > Standard Touch > Advanced Touch)
+/// across two macros. A combo only straddles a split if it is longer than `lines_per_macro`, in which case a warning is logged.
+pub fn split_into_macros(actions: &[Action], lines_per_macro: usize) -> Vec<Vec<Action>> {
+    assert!(lines_per_macro != 0);
+    let mut macros = Vec::new();
+    let mut start = 0;
+    while start < actions.len() {
+        let mut end = std::cmp::min(start + lines_per_macro, actions.len());
+        if end < actions.len() {
+            let mut split = end;
+            while split > start && continues_combo(actions[split - 1], actions[split]) {
+                split -= 1;
+            }
+            if split == start {
+                log::warn!("Combo at step {end} straddles a macro split");
+            } else {
+                end = split;
+            }
+        }
+        macros.push(actions[start..end].to_vec());
+        start = end;
+    }
+    macros
+}
+
+/// Encodes `variant` as a compact delta against `base`, e.g. for sharing many similar rotations.
+///
+/// The delta has the form `prefix:suffix:ids`, where `prefix` and `suffix` are the lengths of the longest common prefix and suffix
+/// of both rotations and `ids` holds the remaining actions of `variant` as two hex digits of their `Action::id` each.
+/// Because action ids are stable, so is the delta. Decode it with [`decode_rotation_delta`] and the same `base`.
+pub fn encode_rotation_delta(base: &[Action], variant: &[Action]) -> String {
+    let prefix = std::iter::zip(base, variant)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let max_suffix = std::cmp::min(base.len(), variant.len()) - prefix;
+    let suffix = std::iter::zip(base.iter().rev(), variant.iter().rev())
+        .take(max_suffix)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let ids: String = variant[prefix..variant.len() - suffix]
+        .iter()
+        .map(|action| format!("{:02x}", action.id()))
+        .collect();
+    format!("{prefix}:{suffix}:{ids}")
+}
+
+/// Inverse of [`encode_rotation_delta`]. `base` must be the rotation the delta was encoded against.
+pub fn decode_rotation_delta(base: &[Action], delta: &str) -> Result<Vec<Action>, &'static str> {
+    let mut parts = delta.split(':');
+    let (Some(prefix), Some(suffix), Some(ids), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err("Rotation delta must have the form prefix:suffix:ids.");
+    };
+    let prefix: usize = prefix
+        .parse()
+        .map_err(|_| "Invalid prefix length in rotation delta.")?;
+    let suffix: usize = suffix
+        .parse()
+        .map_err(|_| "Invalid suffix length in rotation delta.")?;
+    if prefix
+        .checked_add(suffix)
+        .is_none_or(|len| len > base.len())
+    {
+        return Err("Rotation delta doesn't match the base rotation.");
+    }
+    if ids.len() % 2 != 0 || !ids.is_ascii() {
+        return Err("Invalid action ids in rotation delta.");
+    }
+    let mut actions = base[..prefix].to_vec();
+    for i in (0..ids.len()).step_by(2) {
+        let action = u16::from_str_radix(&ids[i..i + 2], 16)
+            .ok()
+            .and_then(Action::from_id)
+            .ok_or("Invalid action ids in rotation delta.")?;
+        actions.push(action);
+    }
+    actions.extend_from_slice(&base[base.len() - suffix..]);
+    Ok(actions)
+}
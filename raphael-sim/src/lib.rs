@@ -1,14 +1,26 @@
 mod actions;
 pub use actions::*;
 
+mod budget;
+pub use budget::{ActionBudget, RotationBudget, rotation_budget, total_cp_cost};
+
 mod conditions;
 pub use conditions::Condition;
 
 mod effects;
-pub use effects::Effects;
+pub use effects::{BuffDurations, EffectKind, Effects};
+
+mod export;
+pub use export::{
+    crafting_simulator_action_name, decode_rotation_delta, encode_rotation_delta,
+    split_into_macros, to_crafting_simulator_json,
+};
+
+mod lint;
+pub use lint::{RotationLint, lint_rotation};
 
 pub mod state;
-pub use state::SimulationState;
+pub use state::{CraftOutcome, SimulationState};
 
 mod settings;
-pub use settings::{ActionMask, Settings};
+pub use settings::{ActionMask, CpCostOverrides, RoundingMode, Settings};
@@ -0,0 +1,37 @@
+use crate::{Action, Condition, Settings, SimulationState};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RotationLint {
+    /// Great Strides used at `step` expired (or was overwritten) before any action increased Quality.
+    GreatStridesWasted { step: usize },
+}
+
+/// Simulates the rotation under Normal conditions and reports common mistakes.
+/// Linting stops at the first action that cannot be used.
+pub fn lint_rotation(settings: &Settings, actions: &[Action]) -> Vec<RotationLint> {
+    let mut lints = Vec::new();
+    let mut state = SimulationState::new(settings);
+    let mut great_strides_step: Option<usize> = None;
+    for (step, action) in actions.iter().enumerate() {
+        let Ok(new_state) = state.use_action(*action, Condition::Normal, settings) else {
+            break;
+        };
+        let quality_increased = new_state.quality + new_state.unreliable_quality
+            > state.quality + state.unreliable_quality;
+        if let Some(active_step) = great_strides_step {
+            let overwritten = *action == Action::GreatStrides;
+            let expired = !quality_increased && new_state.effects.great_strides() == 0;
+            if overwritten || expired {
+                lints.push(RotationLint::GreatStridesWasted { step: active_step });
+            }
+            if overwritten || expired || quality_increased {
+                great_strides_step = None;
+            }
+        }
+        if *action == Action::GreatStrides {
+            great_strides_step = Some(step);
+        }
+        state = new_state;
+    }
+    lints
+}
@@ -11,12 +11,121 @@ pub struct Settings {
     pub base_quality: u16,
     pub job_level: u8,
     pub allowed_actions: ActionMask,
+    /// If `adversarial` is set, Quality is the Quality that is guaranteed regardless of condition procs.
+    /// The adversary may place Excellent procs, each of which forces the following step to be Poor,
+    /// so Poor steps are at least 3 steps apart as dictated by the game's Excellent > Poor > Normal chain.
     pub adversarial: bool,
     /// If `backload_progress` is set, after using any action that increases Progress, the simulator will forbid the use of actions that directly increase Quality.
     pub backload_progress: bool,
+    /// Upper limit on the Quality increase of a single action. Only used by a few special recipes.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub quality_increase_cap: Option<u16>,
+    /// Progress at which the craft counts as complete, if lower than `max_progress`.
+    /// Used to stop at exactly enough Progress instead of overshooting.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub progress_target: Option<u16>,
+    /// If `progress_optional` is set, a craft counts as complete regardless of its Progress.
+    /// This does not correspond to any in-game mechanic and is meant for theorycrafting.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub progress_optional: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub rounding_mode: RoundingMode,
+    /// CP costs that replace the default CP costs of actions, e.g. to simulate a balance patch ahead of time.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub cp_cost_overrides: CpCostOverrides,
+}
+
+/// CP costs that replace the default CP costs of individual actions, see `Settings::cp_cost_overrides`.
+/// Only the regular CP cost is replaced; combo-discounted costs (e.g. Standard Touch after Basic Touch) stay the same.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CpCostOverrides {
+    costs: [Option<u16>; 32],
+}
+
+impl CpCostOverrides {
+    pub const fn none() -> Self {
+        Self { costs: [None; 32] }
+    }
+
+    #[must_use]
+    pub const fn with(mut self, action: Action, cp_cost: u16) -> Self {
+        self.costs[action.id() as usize] = Some(cp_cost);
+        self
+    }
+
+    /// Returns the overridden CP cost of the action, or `None` if the default CP cost applies.
+    pub const fn get(self, action: Action) -> Option<u16> {
+        self.costs[action.id() as usize]
+    }
+}
+
+/// Rounding applied to the Progress and Quality increase of an action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RoundingMode {
+    /// Rounds down. This is what the game does.
+    #[default]
+    Floor,
+    /// Rounds to the nearest integer, rounding halves up. Some third-party simulators use this.
+    RoundHalfUp,
+}
+
+impl RoundingMode {
+    pub const fn divide(self, numerator: u64, denominator: u64) -> u64 {
+        match self {
+            Self::Floor => numerator / denominator,
+            Self::RoundHalfUp => (numerator + denominator / 2) / denominator,
+        }
+    }
 }
 
 impl Settings {
+    /// Clamps the Quality increase of a single action to `quality_increase_cap`.
+    pub fn cap_quality_increase(&self, quality_increase: u32) -> u32 {
+        self.quality_increase_cap.map_or(quality_increase, |cap| {
+            std::cmp::min(quality_increase, u32::from(cap))
+        })
+    }
+
+    /// Checks that the settings describe a craft that can exist in-game.
+    /// Durability is always a multiple of 5, which the durability arithmetic of the solvers relies on.
+    /// Max durability and max CP are bounded so that the solvers can add restored durability and CP without overflowing.
+    /// A craft that requires Progress must also allow at least one action that increases Progress, otherwise it can never be finished.
+    pub fn validate(&self) -> Result<(), &'static str> {
+        const PROGRESS_ACTIONS: ActionMask = crate::action_mask!(
+            Action::BasicSynthesis,
+            Action::MuscleMemory,
+            Action::CarefulSynthesis,
+            Action::Groundwork,
+            Action::DelicateSynthesis,
+            Action::IntensiveSynthesis,
+            Action::PrudentSynthesis
+        );
+        if !self.max_durability.is_multiple_of(5) {
+            return Err("Max durability must be a multiple of 5");
+        }
+        if !(10..=100).contains(&self.max_durability) {
+            return Err("Max durability must be between 10 and 100");
+        }
+        if self.max_cp > 10000 {
+            return Err("Max CP must be at most 10000");
+        }
+        if self.completion_progress() != 0
+            && !self.progress_optional
+            && self.allowed_actions.intersection(PROGRESS_ACTIONS) == ActionMask::none()
+        {
+            return Err("Allowed actions must contain at least one action that increases Progress");
+        }
+        Ok(())
+    }
+
+    /// Progress at which the craft counts as complete, i.e. `progress_target` capped at `max_progress`.
+    pub fn completion_progress(&self) -> u32 {
+        let progress_target = self.progress_target.unwrap_or(self.max_progress);
+        u32::from(std::cmp::min(progress_target, self.max_progress))
+    }
+
     pub fn is_action_allowed<ACTION: ActionImpl>(&self) -> bool {
         self.job_level >= ACTION::LEVEL_REQUIREMENT
             && self.allowed_actions.has_mask(ACTION::ACTION_MASK)
@@ -30,12 +139,35 @@ pub struct ActionMask {
 }
 
 impl ActionMask {
-    pub const fn from_bits(mask: u64) -> Self {
-        Self { mask }
+    /// Inverse of [`ActionMask::to_bits`]. Bits that don't correspond to the id of any action are ignored.
+    pub const fn from_bits(bits: u64) -> Self {
+        let mut mask = Self::none();
+        let mut i = 0;
+        while i < ALL_ACTIONS.len() {
+            let action = ALL_ACTIONS[i];
+            if (bits >> action.id()) & 1 != 0 {
+                mask = mask.add(action);
+            }
+            i += 1;
+        }
+        mask
     }
 
+    /// Compact representation of the mask, intended for FFI and share codes.
+    ///
+    /// Bit `n` is set if and only if the mask contains the action with `Action::id` `n`.
+    /// Because action ids are stable, so is this representation.
     pub const fn to_bits(self) -> u64 {
-        self.mask
+        let mut bits = 0;
+        let mut i = 0;
+        while i < ALL_ACTIONS.len() {
+            let action = ALL_ACTIONS[i];
+            if self.has(action) {
+                bits |= 1 << action.id();
+            }
+            i += 1;
+        }
+        bits
     }
 
     pub const fn none() -> Self {
@@ -43,7 +175,13 @@ impl ActionMask {
     }
 
     pub const fn all() -> Self {
-        Self { mask: u64::MAX }
+        let mut mask = Self::none();
+        let mut i = 0;
+        while i < ALL_ACTIONS.len() {
+            mask = mask.add(ALL_ACTIONS[i]);
+            i += 1;
+        }
+        mask
     }
 
     pub const fn has(self, action: Action) -> bool {
@@ -91,6 +229,7 @@ impl ActionMask {
         }
     }
 
+    /// Returns the actions contained in the mask, in ascending order of their `Action` discriminant.
     pub fn actions(self) -> Box<[Action]> {
         ALL_ACTIONS
             .iter()
@@ -99,12 +238,60 @@ impl ActionMask {
             .collect()
     }
 
+    /// Iterates over the actions contained in the mask, in ascending order of their `Action` discriminant.
     pub fn actions_iter(self) -> impl Iterator<Item = Action> {
         ALL_ACTIONS
             .iter()
             .copied()
             .filter(move |action| ((self.mask >> *action as u64) & 1) != 0)
     }
+
+    /// Returns the actions of a well-known rotation family, together with the openers, buffs and Byregot's Blessing.
+    ///
+    /// Supported templates:
+    /// - `"standard manipulation"`: Manipulation and Master Mend for durability, with the regular touch combo.
+    /// - `"waste not"`: Waste Not and Waste Not II for durability, with Prudent Touch and Prudent Synthesis.
+    /// - `"groundwork"`: Groundwork and Preparatory Touch with Manipulation.
+    ///
+    /// Returns `None` if the template is unknown.
+    pub fn from_rotation_template(name: &str) -> Option<Self> {
+        const COMMON_ACTIONS: ActionMask = crate::action_mask!(
+            Action::MuscleMemory,
+            Action::Reflect,
+            Action::Veneration,
+            Action::Innovation,
+            Action::GreatStrides,
+            Action::ByregotsBlessing
+        );
+        let actions = match name {
+            "standard manipulation" => crate::action_mask!(
+                Action::Manipulation,
+                Action::MasterMend,
+                Action::BasicSynthesis,
+                Action::CarefulSynthesis,
+                Action::BasicTouch,
+                Action::StandardTouch,
+                Action::AdvancedTouch
+            ),
+            "waste not" => crate::action_mask!(
+                Action::WasteNot,
+                Action::WasteNot2,
+                Action::BasicSynthesis,
+                Action::CarefulSynthesis,
+                Action::PrudentSynthesis,
+                Action::PrudentTouch
+            ),
+            "groundwork" => crate::action_mask!(
+                Action::Manipulation,
+                Action::MasterMend,
+                Action::CarefulSynthesis,
+                Action::Groundwork,
+                Action::PreparatoryTouch
+            ),
+            _ => return None,
+        };
+        Some(actions.union(COMMON_ACTIONS))
+    }
 }
 
 #[macro_export]
@@ -120,6 +307,7 @@ macro_rules! action_mask {
     };
 }
 
+/// All actions, in ascending order of their `Action` discriminant.
 const ALL_ACTIONS: &[Action] = &[
     Action::BasicSynthesis,
     Action::BasicTouch,
@@ -138,18 +326,27 @@ const ALL_ACTIONS: &[Action] = &[
     Action::CarefulSynthesis,
     Action::Manipulation,
     Action::PrudentTouch,
-    Action::TrainedEye,
+    Action::AdvancedTouch,
     Action::Reflect,
     Action::PreparatoryTouch,
     Action::Groundwork,
     Action::DelicateSynthesis,
     Action::IntensiveSynthesis,
-    Action::AdvancedTouch,
+    Action::TrainedEye,
     Action::HeartAndSoul,
     Action::PrudentSynthesis,
     Action::TrainedFinesse,
     Action::RefinedTouch,
+    Action::QuickInnovation,
     Action::ImmaculateMend,
     Action::TrainedPerfection,
-    Action::QuickInnovation,
 ];
+
+// `ActionMask::actions` and `ActionMask::actions_iter` rely on `ALL_ACTIONS` being sorted by discriminant
+const _: () = {
+    let mut i = 0;
+    while i < ALL_ACTIONS.len() {
+        assert!(ALL_ACTIONS[i] as usize == i);
+        i += 1;
+    }
+};
@@ -2,7 +2,21 @@ use crate::actions::*;
 use crate::effects::*;
 use crate::{Condition, Settings};
 
+/// Whether a craft is ongoing, completed or failed, see `SimulationState::outcome`.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CraftOutcome {
+    InProgress,
+    Completed,
+    /// Durability ran out before Progress was completed.
+    /// `quality_so_far` is the Quality that was reached before the craft failed.
+    Failed {
+        missing_progress: u32,
+        quality_so_far: u32,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SimulationState {
     pub cp: u16,
     pub durability: u16,
@@ -24,12 +38,219 @@ impl SimulationState {
         }
     }
 
-    pub fn from_macro(settings: &Settings, actions: &[Action]) -> Result<Self, &'static str> {
+    /// Same as `new`, but returns an error if the settings don't describe a valid craft (see `Settings::validate`).
+    pub fn try_new(settings: &Settings) -> Result<Self, &'static str> {
+        settings.validate()?;
+        Ok(Self::new(settings))
+    }
+
+    /// Creates a state for a craft that is already in progress, e.g. when continuing an imported macro.
+    /// Unlike `new`, the combo is set explicitly, so a continued craft doesn't allow opener actions such as Muscle Memory
+    /// and can continue a touch combo that was started before.
+    ///
+    /// Returns an error if the settings are invalid (see `Settings::validate`), if `cp` or `durability` exceed their maximum,
+    /// if `combo` cannot be the result of an action used at the craft's level,
+    /// or if `combo` is `Combo::SynthesisBegin` and the craft has already progressed.
+    pub fn from_parts(
+        settings: &Settings,
+        cp: u16,
+        durability: u16,
+        progress: u32,
+        quality: u32,
+        combo: Combo,
+    ) -> Result<Self, &'static str> {
+        settings.validate()?;
+        if cp > settings.max_cp {
+            return Err("CP cannot exceed max CP.");
+        }
+        if durability > settings.max_durability {
+            return Err("Durability cannot exceed max durability.");
+        }
+        match combo {
+            Combo::None => (),
+            Combo::SynthesisBegin => {
+                if cp != settings.max_cp
+                    || durability != settings.max_durability
+                    || progress != 0
+                    || quality != 0
+                {
+                    return Err("Synthesis Begin combo requires a fresh craft.");
+                }
+            }
+            Combo::BasicTouch => {
+                if settings.job_level < BasicTouch::LEVEL_REQUIREMENT {
+                    return Err("Basic Touch combo requires Basic Touch to be unlocked.");
+                }
+            }
+            Combo::StandardTouch => {
+                // Observe is the earliest action that continues into Advanced Touch
+                if settings.job_level < Observe::LEVEL_REQUIREMENT {
+                    return Err("Standard Touch combo requires Observe to be unlocked.");
+                }
+            }
+        }
+        Ok(Self {
+            cp,
+            durability,
+            progress,
+            quality,
+            unreliable_quality: 0,
+            effects: Effects::initial(settings).with_combo(combo),
+        })
+    }
+
+    /// Uses the actions of a macro under Normal conditions, starting from the beginning of the craft.
+    ///
+    /// If an action cannot be used, returns the index of that action in `actions` together with the reason.
+    pub fn from_macro(
+        settings: &Settings,
+        actions: &[Action],
+    ) -> Result<Self, (usize, &'static str)> {
+        let mut state = Self::new(settings);
+        for (index, action) in actions.iter().enumerate() {
+            state = state
+                .use_action(*action, Condition::Normal, settings)
+                .map_err(|err| (index, err))?;
+        }
+        Ok(state)
+    }
+
+    /// Uses the actions with an explicit condition for each step. Steps beyond the end of `schedule` use the Normal condition.
+    /// If an action cannot be used, returns the index of that action together with the reason.
+    pub fn use_actions_with_condition_schedule(
+        self,
+        actions: &[Action],
+        schedule: &[Condition],
+        settings: &Settings,
+    ) -> Result<Self, (usize, &'static str)> {
+        let mut state = self;
+        Self::simulate_into(&mut state, actions, schedule, settings)?;
+        Ok(state)
+    }
+
+    /// Uses the actions in place with an explicit condition for each step, without allocating. Steps beyond the end of `conditions` use the Normal condition.
+    /// Meant for tight loops such as Monte Carlo simulations of random conditions.
+    ///
+    /// If an action cannot be used, returns the index of that action together with the reason and leaves `state` as it was before that action.
+    pub fn simulate_into(
+        state: &mut Self,
+        actions: &[Action],
+        conditions: &[Condition],
+        settings: &Settings,
+    ) -> Result<(), (usize, &'static str)> {
+        let conditions = conditions
+            .iter()
+            .copied()
+            .chain(std::iter::repeat(Condition::Normal));
+        for (index, (action, condition)) in actions.iter().zip(conditions).enumerate() {
+            *state = state
+                .use_action(*action, condition, settings)
+                .map_err(|err| (index, err))?;
+        }
+        Ok(())
+    }
+
+    /// Uses the actions under Normal conditions until `predicate` holds for the current state or all actions have been used.
+    /// Returns the resulting state together with the number of actions that were used.
+    pub fn use_actions_until(
+        self,
+        actions: &[Action],
+        settings: &Settings,
+        predicate: impl Fn(&Self) -> bool,
+    ) -> Result<(Self, usize), &'static str> {
+        let mut state = self;
+        for (index, action) in actions.iter().enumerate() {
+            if predicate(&state) {
+                return Ok((state, index));
+            }
+            state = state.use_action(*action, Condition::Normal, settings)?;
+        }
+        Ok((state, actions.len()))
+    }
+
+    /// Returns the remaining buff durations after each action of the macro.
+    pub fn buff_timeline(
+        settings: &Settings,
+        actions: &[Action],
+    ) -> Result<Vec<BuffDurations>, &'static str> {
         let mut state = Self::new(settings);
+        let mut timeline = Vec::with_capacity(actions.len());
         for action in actions {
             state = state.use_action(*action, Condition::Normal, settings)?;
+            timeline.push(state.effects.buff_durations());
         }
-        Ok(state)
+        Ok(timeline)
+    }
+
+    /// Returns the condition sequence under which the macro reaches the least Quality, i.e. the conditions that the `adversarial` setting guards against.
+    /// Conditions follow the forced Excellent > Poor > Normal chain and the first condition is always Normal.
+    /// Using the actions under the returned conditions without `adversarial` reaches the Quality that `from_macro` reports with `adversarial`.
+    pub fn worst_case_conditions(
+        settings: &Settings,
+        actions: &[Action],
+    ) -> Result<Vec<Condition>, &'static str> {
+        let settings = Settings {
+            adversarial: false,
+            ..*settings
+        };
+        // Lowest-Quality branch for each possible previous condition: Normal, Excellent, Poor.
+        let mut branches: [Option<(Self, Vec<Condition>)>; 3] =
+            [Some((Self::new(&settings), Vec::new())), None, None];
+        for (index, action) in actions.iter().enumerate() {
+            let mut next_branches: [Option<(Self, Vec<Condition>)>; 3] = [None, None, None];
+            for (previous, branch) in branches.iter().enumerate() {
+                let Some((state, conditions)) = branch else {
+                    continue;
+                };
+                let next_conditions: &[Condition] = match previous {
+                    0 if index == 0 => &[Condition::Normal],
+                    0 => &[Condition::Normal, Condition::Excellent],
+                    1 => &[Condition::Poor],
+                    _ => &[Condition::Normal],
+                };
+                for condition in next_conditions {
+                    let state = state.use_action(*action, *condition, &settings)?;
+                    let slot = match condition {
+                        Condition::Excellent => 1,
+                        Condition::Poor => 2,
+                        _ => 0,
+                    };
+                    if next_branches[slot]
+                        .as_ref()
+                        .is_none_or(|(other, _)| state.quality < other.quality)
+                    {
+                        let mut conditions = conditions.clone();
+                        conditions.push(*condition);
+                        next_branches[slot] = Some((state, conditions));
+                    }
+                }
+            }
+            branches = next_branches;
+        }
+        Ok(branches
+            .into_iter()
+            .flatten()
+            .min_by_key(|(state, _)| state.quality)
+            .map(|(_, conditions)| conditions)
+            .unwrap_or_default())
+    }
+
+    /// Returns the index of the action with which the macro reaches the maximum Quality, or `None` if it never does.
+    /// All following actions can only contribute to Progress.
+    pub fn quality_capped_at_step(
+        settings: &Settings,
+        actions: &[Action],
+    ) -> Result<Option<usize>, (usize, &'static str)> {
+        let mut state = Self::new(settings);
+        for (index, action) in actions.iter().enumerate() {
+            state = state
+                .use_action(*action, Condition::Normal, settings)
+                .map_err(|err| (index, err))?;
+            if state.quality >= u32::from(settings.max_quality) {
+                return Ok(Some(index));
+            }
+        }
+        Ok(None)
     }
 
     pub fn from_macro_continue_on_error(
@@ -54,7 +275,34 @@ impl SimulationState {
     }
 
     pub fn is_final(&self, settings: &Settings) -> bool {
-        self.durability == 0 || self.progress >= u32::from(settings.max_progress)
+        self.durability == 0 || self.progress >= settings.completion_progress()
+    }
+
+    /// Whether the craft is ongoing, completed or failed. A failed craft reports the Quality it had reached.
+    pub fn outcome(&self, settings: &Settings) -> CraftOutcome {
+        let completion_progress = settings.completion_progress();
+        if self.progress >= completion_progress
+            || (self.durability == 0 && settings.progress_optional)
+        {
+            CraftOutcome::Completed
+        } else if self.durability == 0 {
+            CraftOutcome::Failed {
+                missing_progress: completion_progress - self.progress,
+                quality_so_far: self.quality,
+            }
+        } else {
+            CraftOutcome::InProgress
+        }
+    }
+
+    /// Progress beyond what is required to complete the craft.
+    pub fn progress_overkill(&self, settings: &Settings) -> u32 {
+        self.progress.saturating_sub(settings.completion_progress())
+    }
+
+    /// Quality beyond the maximum Quality of the craft.
+    pub fn quality_overkill(&self, settings: &Settings) -> u32 {
+        self.quality.saturating_sub(u32::from(settings.max_quality))
     }
 
     fn check_common_preconditions<A: ActionImpl>(
@@ -143,7 +391,8 @@ impl SimulationState {
 
         if A::TICK_EFFECTS {
             if state.effects.manipulation() != 0 {
-                state.durability = std::cmp::min(settings.max_durability, state.durability + 5);
+                state.durability =
+                    std::cmp::min(settings.max_durability, state.durability.saturating_add(5));
             }
             state.effects = state.effects.tick_down();
         }
@@ -222,4 +471,18 @@ impl SimulationState {
             }
         }
     }
+
+    /// Iterates over the allowed actions that can be used in this state under `condition`,
+    /// in ascending order of their `Action` discriminant.
+    /// Condition-gated actions such as Precise Touch are only included under Good or Excellent.
+    pub fn legal_actions<'a>(
+        &'a self,
+        condition: Condition,
+        settings: &'a Settings,
+    ) -> impl Iterator<Item = Action> + 'a {
+        settings
+            .allowed_actions
+            .actions_iter()
+            .filter(move |action| self.use_action(*action, condition, settings).is_ok())
+    }
 }
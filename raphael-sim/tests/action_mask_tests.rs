@@ -0,0 +1,83 @@
+use raphael_sim::*;
+
+#[test]
+fn test_all_actions_iter_order() {
+    let actions: Vec<Action> = ActionMask::all().actions_iter().collect();
+    assert_eq!(actions.len(), 31);
+    for (index, action) in actions.iter().enumerate() {
+        assert_eq!(*action as usize, index);
+    }
+    assert_eq!(actions[0], Action::BasicSynthesis);
+    assert_eq!(actions[30], Action::TrainedPerfection);
+    assert_eq!(ActionMask::all().actions().as_ref(), actions.as_slice());
+}
+
+#[test]
+fn test_partial_mask_iter_order() {
+    let mask = action_mask!(
+        Action::TrainedPerfection,
+        Action::QuickInnovation,
+        Action::Manipulation,
+        Action::BasicSynthesis
+    );
+    let actions: Vec<Action> = mask.actions_iter().collect();
+    assert_eq!(
+        actions,
+        [
+            Action::BasicSynthesis,
+            Action::Manipulation,
+            Action::QuickInnovation,
+            Action::TrainedPerfection
+        ]
+    );
+}
+
+#[test]
+fn test_rotation_template() {
+    let mask = ActionMask::from_rotation_template("standard manipulation").unwrap();
+    assert!(mask.has(Action::Manipulation));
+    assert!(mask.has(Action::AdvancedTouch));
+    assert!(mask.has(Action::Innovation));
+    assert!(!mask.has(Action::WasteNot));
+    assert!(!mask.has(Action::PrudentTouch));
+
+    let mask = ActionMask::from_rotation_template("waste not").unwrap();
+    assert!(mask.has(Action::WasteNot2));
+    assert!(mask.has(Action::PrudentTouch));
+    assert!(mask.has(Action::Veneration));
+    assert!(!mask.has(Action::Manipulation));
+    assert!(!mask.has(Action::BasicTouch));
+
+    let mask = ActionMask::from_rotation_template("groundwork").unwrap();
+    assert!(mask.has(Action::Groundwork));
+    assert!(mask.has(Action::PreparatoryTouch));
+    assert!(!mask.has(Action::BasicSynthesis));
+
+    assert_eq!(ActionMask::from_rotation_template("hasty touch spam"), None);
+}
+
+#[test]
+fn test_bits_round_trip() {
+    let masks = [
+        ActionMask::none(),
+        ActionMask::all(),
+        ActionMask::all().remove(Action::TrainedEye),
+        action_mask!(Action::BasicSynthesis, Action::TrainedPerfection),
+        ActionMask::from_rotation_template("groundwork").unwrap(),
+    ];
+    for mask in masks {
+        assert_eq!(ActionMask::from_bits(mask.to_bits()), mask);
+    }
+    assert_eq!(ActionMask::all().to_bits(), (1 << 31) - 1);
+}
+
+#[test]
+fn test_bits_keyed_on_action_id() {
+    for action in ActionMask::all().actions_iter() {
+        let mask = ActionMask::none().add(action);
+        assert_eq!(mask.to_bits(), 1 << action.id());
+        assert_eq!(ActionMask::from_bits(1 << action.id()), mask);
+    }
+    // Bits that don't belong to any action are dropped
+    assert_eq!(ActionMask::from_bits(u64::MAX), ActionMask::all());
+}
@@ -11,6 +11,11 @@ const SETTINGS: Settings = Settings {
     allowed_actions: ActionMask::all(),
     adversarial: false,
     backload_progress: false,
+    quality_increase_cap: None,
+    progress_target: None,
+    progress_optional: false,
+    rounding_mode: RoundingMode::Floor,
+    cp_cost_overrides: CpCostOverrides::none(),
 };
 
 /// Returns the 4 primary stats of a state:
@@ -90,6 +95,18 @@ fn test_observe() {
     assert_eq!(state.effects.combo(), Combo::StandardTouch);
 }
 
+#[test]
+fn test_observe_advanced_touch_combo() {
+    // Observe -> Advanced Touch gets the same CP discount as Standard Touch -> Advanced Touch
+    let state = SimulationState::new(&SETTINGS)
+        .use_action(Action::Observe, Condition::Normal, &SETTINGS)
+        .unwrap()
+        .use_action(Action::AdvancedTouch, Condition::Normal, &SETTINGS)
+        .unwrap();
+    assert_eq!(primary_stats(&state, &SETTINGS), (0, 150, 10, 25));
+    assert_eq!(state.effects.combo(), Combo::None);
+}
+
 #[test]
 fn test_tricks_of_the_trade() {
     // Precondition not fulfilled
@@ -440,6 +457,30 @@ fn test_groundwork() {
     );
 }
 
+#[test]
+fn test_groundwork_durability_threshold() {
+    let groundwork_progress = |durability: u16, effects: Effects| {
+        let initial_state = SimulationState {
+            durability,
+            effects,
+            ..SimulationState::new(&SETTINGS)
+        };
+        initial_state
+            .use_action(Action::Groundwork, Condition::Normal, &SETTINGS)
+            .unwrap()
+            .progress
+    };
+    // Full potency if durability is at least the durability cost (20)
+    assert_eq!(groundwork_progress(19, Effects::new()), 180);
+    assert_eq!(groundwork_progress(20, Effects::new()), 360);
+    assert_eq!(groundwork_progress(21, Effects::new()), 360);
+    // Waste Not halves the durability cost, and thereby the threshold
+    let waste_not = Effects::new().with_waste_not(1);
+    assert_eq!(groundwork_progress(9, waste_not), 180);
+    assert_eq!(groundwork_progress(10, waste_not), 360);
+    assert_eq!(groundwork_progress(11, waste_not), 360);
+}
+
 #[test]
 fn test_delicate_synthesis() {
     // Low level, potency-increasing trait not unlocked
@@ -525,7 +566,10 @@ fn test_prudent_synthesis() {
         SimulationState::from_macro(&SETTINGS, &[Action::WasteNot, Action::PrudentSynthesis]);
     assert_eq!(
         state,
-        Err("Prudent Synthesis cannot be used while Waste Not is active.")
+        Err((
+            1,
+            "Prudent Synthesis cannot be used while Waste Not is active."
+        ))
     );
 }
 
@@ -541,7 +585,10 @@ fn test_trained_finesse() {
     );
     assert_eq!(
         state,
-        Err("Trained Finesse can only be used when Inner Quiet is 10.")
+        Err((
+            2,
+            "Trained Finesse can only be used when Inner Quiet is 10."
+        ))
     );
 }
 
@@ -552,13 +599,16 @@ fn test_refined_touch() {
         Ok(state) => {
             assert_eq!(state.effects.inner_quiet(), 3);
         }
-        Err(e) => panic!("Unexpected error: {}", e),
+        Err((_, e)) => panic!("Unexpected error: {}", e),
     }
     assert!(matches!(state, Ok(_)));
     let state = SimulationState::from_macro(&SETTINGS, &[Action::RefinedTouch]);
     assert_eq!(
         state,
-        Err("Refined Touch can only be used after Observe or Basic Touch.")
+        Err((
+            0,
+            "Refined Touch can only be used after Observe or Basic Touch."
+        ))
     );
 }
 
@@ -577,7 +627,7 @@ fn test_immaculate_mend() {
         Ok(state) => {
             assert_eq!(state.durability, SETTINGS.max_durability);
         }
-        Err(e) => panic!("Unexpected error: {}", e),
+        Err((_, e)) => panic!("Unexpected error: {}", e),
     }
 }
 
@@ -595,7 +645,7 @@ fn test_trained_perfection() {
         Ok(state) => {
             assert_eq!(state.durability, SETTINGS.max_durability);
         }
-        Err(e) => panic!("Unexpected error: {}", e),
+        Err((_, e)) => panic!("Unexpected error: {}", e),
     };
     let state = SimulationState::from_macro(
         &SETTINGS,
@@ -603,7 +653,7 @@ fn test_trained_perfection() {
     );
     assert_eq!(
         state,
-        Err("Trained Perfection can only be used once per synthesis.")
+        Err((1, "Trained Perfection can only be used once per synthesis."))
     );
 }
 
@@ -629,7 +679,7 @@ fn test_heart_and_soul() {
             assert_eq!(state.effects.heart_and_soul_available(), false);
             assert_eq!(state.effects.heart_and_soul_active(), true);
         }
-        Err(e) => panic!("Unexpected error: {}", e),
+        Err((_, e)) => panic!("Unexpected error: {}", e),
     }
     let state = SimulationState::from_macro(
         &settings,
@@ -643,7 +693,7 @@ fn test_heart_and_soul() {
         Ok(state) => {
             assert_eq!(state.effects.heart_and_soul_active(), true); // effect stays active until used
         }
-        Err(e) => panic!("Unexpected error: {}", e),
+        Err((_, e)) => panic!("Unexpected error: {}", e),
     }
     let state = SimulationState::from_macro(
         &settings,
@@ -653,7 +703,7 @@ fn test_heart_and_soul() {
         Ok(state) => {
             assert_eq!(state.effects.heart_and_soul_active(), false); // effect is used up
         }
-        Err(e) => panic!("Unexpected error: {}", e),
+        Err((_, e)) => panic!("Unexpected error: {}", e),
     }
     let state =
         SimulationState::from_macro(&settings, &[Action::HeartAndSoul, Action::PreciseTouch]);
@@ -661,7 +711,7 @@ fn test_heart_and_soul() {
         Ok(state) => {
             assert_eq!(state.effects.heart_and_soul_active(), false); // effect is used up
         }
-        Err(e) => panic!("Unexpected error: {}", e),
+        Err((_, e)) => panic!("Unexpected error: {}", e),
     }
     let state = SimulationState::from_macro(
         &settings,
@@ -673,7 +723,7 @@ fn test_heart_and_soul() {
     );
     assert_eq!(
         state,
-        Err("Heart and Sould can only be used once per synthesis.")
+        Err((2, "Heart and Sould can only be used once per synthesis."))
     );
 }
 
@@ -698,7 +748,7 @@ fn test_quick_innovation() {
             assert_eq!(state.effects.manipulation(), 7); // effects are not ticked
             assert_eq!(state.effects.innovation(), 1);
         }
-        Err(e) => panic!("Unexpected error: {}", e),
+        Err((_, e)) => panic!("Unexpected error: {}", e),
     }
     let state = SimulationState::from_macro(
         &setings,
@@ -710,12 +760,278 @@ fn test_quick_innovation() {
     );
     assert_eq!(
         state,
-        Err("Quick Innovation can only be used once per synthesis.")
+        Err((2, "Quick Innovation can only be used once per synthesis."))
     );
     let state =
         SimulationState::from_macro(&setings, &[Action::Innovation, Action::QuickInnovation]);
     assert_eq!(
         state,
-        Err("Quick Innovation cannot be used while Innovation is active.")
+        Err((
+            1,
+            "Quick Innovation cannot be used while Innovation is active."
+        ))
     );
 }
+
+#[test]
+fn test_action_id_round_trip() {
+    for action in ActionMask::all().actions_iter() {
+        assert_eq!(Action::from_id(action.id()), Some(action));
+    }
+    let mut ids: Vec<u16> = ActionMask::all().actions_iter().map(Action::id).collect();
+    ids.dedup();
+    assert_eq!(ids.len(), 31);
+    assert_eq!(Action::from_id(31), None);
+    // ids are stable and must never change
+    assert_eq!(Action::BasicSynthesis.id(), 0);
+    assert_eq!(Action::Manipulation.id(), 15);
+    assert_eq!(Action::TrainedPerfection.id(), 30);
+}
+
+#[test]
+fn test_quality_increase_cap() {
+    let mut initial_state = SimulationState::new(&SETTINGS);
+    initial_state.effects.set_inner_quiet(10);
+    initial_state.effects.set_great_strides(3);
+    let state = initial_state
+        .use_action(Action::ByregotsBlessing, Condition::Normal, &SETTINGS)
+        .unwrap();
+    assert_eq!(state.quality, 1200);
+    // Quality increase is clamped to the cap
+    let settings = Settings {
+        quality_increase_cap: Some(1000),
+        progress_target: None,
+        ..SETTINGS
+    };
+    let state = initial_state
+        .use_action(Action::ByregotsBlessing, Condition::Normal, &settings)
+        .unwrap();
+    assert_eq!(state.quality, 1000);
+    // Actions below the cap are not affected
+    let state = initial_state
+        .use_action(Action::BasicTouch, Condition::Normal, &settings)
+        .unwrap();
+    assert_eq!(state.quality, 400);
+}
+
+#[test]
+fn test_action_predicates() {
+    // Starting states that cover the preconditions of all actions
+    let initial_states = [
+        SimulationState::new(&SETTINGS),
+        SimulationState {
+            effects: Effects::initial(&SETTINGS)
+                .with_inner_quiet(5)
+                .with_heart_and_soul_active(true)
+                .with_combo(Combo::BasicTouch),
+            ..SimulationState::new(&SETTINGS)
+        },
+        SimulationState {
+            effects: Effects::initial(&SETTINGS)
+                .with_inner_quiet(10)
+                .with_combo(Combo::None),
+            ..SimulationState::new(&SETTINGS)
+        },
+    ];
+    for action in ActionMask::all().actions_iter() {
+        let (initial_state, state) = initial_states
+            .iter()
+            .find_map(|initial_state| {
+                let state = initial_state
+                    .use_action(action, Condition::Normal, &SETTINGS)
+                    .ok()?;
+                Some((initial_state, state))
+            })
+            .unwrap();
+        let durability_cost = initial_state.durability != state.durability;
+        assert_eq!(action.costs_durability(), durability_cost, "{action:?}");
+        if action.is_buff() {
+            assert!(!durability_cost, "{action:?}");
+            assert_eq!(state.progress, initial_state.progress, "{action:?}");
+            assert_eq!(state.quality, initial_state.quality, "{action:?}");
+        }
+        let inner_quiet_diff =
+            i16::from(state.effects.inner_quiet()) - i16::from(initial_state.effects.inner_quiet());
+        match action {
+            Action::ByregotsBlessing => assert_eq!(state.effects.inner_quiet(), 0),
+            _ => assert_eq!(
+                inner_quiet_diff,
+                i16::from(action.grants_inner_quiet()),
+                "{action:?}"
+            ),
+        }
+    }
+}
+
+#[test]
+fn test_prudent_actions_under_waste_not() {
+    let state = SimulationState::new(&SETTINGS)
+        .use_action(Action::WasteNot, Condition::Normal, &SETTINGS)
+        .unwrap();
+    assert_eq!(
+        state.use_action(Action::PrudentTouch, Condition::Normal, &SETTINGS),
+        Err("Prudent Touch cannot be used while Waste Not is active.")
+    );
+    assert_eq!(
+        state.use_action(Action::PrudentSynthesis, Condition::Normal, &SETTINGS),
+        Err("Prudent Synthesis cannot be used while Waste Not is active.")
+    );
+    // Prudent actions become usable again once Waste Not wears off
+    let mut state = state;
+    for _ in 0..4 {
+        state = state
+            .use_action(Action::Observe, Condition::Normal, &SETTINGS)
+            .unwrap();
+    }
+    assert_eq!(state.effects.waste_not(), 0);
+    assert!(
+        state
+            .use_action(Action::PrudentTouch, Condition::Normal, &SETTINGS)
+            .is_ok()
+    );
+}
+
+#[test]
+fn test_combo_graph() {
+    let combos = [
+        Combo::None,
+        Combo::SynthesisBegin,
+        Combo::BasicTouch,
+        Combo::StandardTouch,
+    ];
+    let state_with_combo = |combo| {
+        SimulationState::from_parts(
+            &SETTINGS,
+            SETTINGS.max_cp,
+            SETTINGS.max_durability,
+            0,
+            0,
+            combo,
+        )
+        .unwrap()
+    };
+    for action in ActionMask::all().actions_iter() {
+        for combo in combos {
+            let with_combo =
+                state_with_combo(combo).use_action(action, Condition::Normal, &SETTINGS);
+            let without_combo =
+                state_with_combo(Combo::None).use_action(action, Condition::Normal, &SETTINGS);
+            // The action can only be used in the combo or is cheaper in the combo
+            let combo_matters = match (with_combo, without_combo) {
+                (Ok(_), Err(_)) => true,
+                (Ok(with_combo), Ok(without_combo)) => with_combo.cp > without_combo.cp,
+                _ => false,
+            };
+            if combo_matters {
+                assert_eq!(action.required_combo(), Some(combo), "{action:?}");
+            }
+            assert_eq!(
+                combo_matters,
+                combo.next_actions().contains(&action),
+                "{action:?} in {combo:?}"
+            );
+        }
+        let combo = action.required_combo().unwrap_or(Combo::None);
+        let state = state_with_combo(combo)
+            .use_action(action, Condition::Normal, &SETTINGS)
+            .ok();
+        if let Some(state) = state {
+            assert_eq!(state.effects.combo(), action.to_combo(), "{action:?}");
+        }
+    }
+}
+
+#[test]
+fn test_redundant_after() {
+    let use_actions = |initial_state: SimulationState, actions: &[Action]| {
+        let mut state = initial_state;
+        for action in actions {
+            state = state
+                .use_action(*action, Condition::Normal, &SETTINGS)
+                .unwrap();
+        }
+        state
+    };
+    let dominates = |lhs: SimulationState, rhs: SimulationState| {
+        let lhs_buffs = lhs.effects.buff_durations();
+        let rhs_buffs = rhs.effects.buff_durations();
+        lhs.cp >= rhs.cp
+            && lhs.durability >= rhs.durability
+            && lhs.progress >= rhs.progress
+            && lhs.quality >= rhs.quality
+            && lhs.effects.inner_quiet() >= rhs.effects.inner_quiet()
+            && lhs.effects.muscle_memory() >= rhs.effects.muscle_memory()
+            && lhs_buffs.innovation >= rhs_buffs.innovation
+            && lhs_buffs.veneration >= rhs_buffs.veneration
+            && lhs_buffs.great_strides >= rhs_buffs.great_strides
+            && lhs_buffs.waste_not >= rhs_buffs.waste_not
+            && lhs_buffs.manipulation >= rhs_buffs.manipulation
+    };
+    let initial_state = SimulationState {
+        durability: 20,
+        ..SimulationState::new(&SETTINGS)
+    };
+    // Manipulation with 1 turn left (ticks off during `prev`) and with 2 turns left (still active after `prev`)
+    let manipulation_states = [1, 2].map(|manipulation| SimulationState {
+        effects: initial_state.effects.with_manipulation(manipulation),
+        ..initial_state
+    });
+    let mut redundant_pairs = [0; 3];
+    for (initial_state, redundant_pairs) in std::iter::once(initial_state)
+        .chain(manipulation_states)
+        .zip(redundant_pairs.iter_mut())
+    {
+        for prev in ActionMask::all().actions_iter() {
+            let Ok(prev_state) = initial_state.use_action(prev, Condition::Normal, &SETTINGS)
+            else {
+                continue;
+            };
+            for action in ActionMask::all().actions_iter() {
+                if !action.redundant_after(prev, prev_state.effects) {
+                    continue;
+                }
+                *redundant_pairs += 1;
+                // Using only one of the two actions is at least as good as using both
+                let both = use_actions(initial_state, &[prev, action]);
+                assert!(
+                    dominates(prev_state, both)
+                        || dominates(use_actions(initial_state, &[action]), both),
+                    "{prev:?} -> {action:?}"
+                );
+            }
+        }
+    }
+    // Re-applying a buff is not redundant if Manipulation restores durability in the extra step
+    assert_eq!(redundant_pairs, [10, 10, 3]);
+    let effects = Effects::new();
+    // Stacking Manipulation or repeating Master's Mend is not redundant
+    assert!(!Action::Manipulation.redundant_after(Action::Manipulation, effects));
+    assert!(!Action::MasterMend.redundant_after(Action::MasterMend, effects));
+}
+
+#[test]
+fn test_cp_cost_overrides() {
+    let settings = Settings {
+        cp_cost_overrides: CpCostOverrides::none().with(Action::StandardTouch, 20),
+        ..SETTINGS
+    };
+    assert_eq!(
+        settings.cp_cost_overrides.get(Action::StandardTouch),
+        Some(20)
+    );
+    assert_eq!(settings.cp_cost_overrides.get(Action::BasicTouch), None);
+    let state = SimulationState::new(&settings)
+        .use_action(Action::StandardTouch, Condition::Normal, &settings)
+        .unwrap();
+    assert_eq!(primary_stats(&state, &settings), (0, 125, 10, 20));
+    // Other actions keep their default CP costs
+    let state = state
+        .use_action(Action::BasicTouch, Condition::Normal, &settings)
+        .unwrap();
+    assert_eq!(settings.max_cp - state.cp, 38);
+    // The combo-discounted CP cost is not overridden
+    let state = state
+        .use_action(Action::StandardTouch, Condition::Normal, &settings)
+        .unwrap();
+    assert_eq!(settings.max_cp - state.cp, 56);
+}
@@ -1,4 +1,6 @@
-use raphael_sim::{Action, ActionMask, Condition, Settings, SimulationState};
+use raphael_sim::{
+    Action, ActionMask, Condition, CpCostOverrides, RoundingMode, Settings, SimulationState,
+};
 const SETTINGS: Settings = Settings {
     max_cp: 1000,
     max_durability: 80,
@@ -10,6 +12,11 @@ const SETTINGS: Settings = Settings {
     allowed_actions: ActionMask::all(),
     adversarial: true,
     backload_progress: false,
+    quality_increase_cap: None,
+    progress_target: None,
+    progress_optional: false,
+    rounding_mode: RoundingMode::Floor,
+    cp_cost_overrides: CpCostOverrides::none(),
 };
 
 /// Calculate the minimum achievable Quality across all possible Condition rolls
@@ -67,7 +74,7 @@ fn test_simple() {
         assert_eq!(guaranteed_quality(SETTINGS, &actions).unwrap(), 100);
         assert_eq!(state.quality, 100);
     } else {
-        panic!("Unexpected err: {}", state.err().unwrap());
+        panic!("Unexpected err: {}", state.err().unwrap().1);
     }
 }
 
@@ -79,7 +86,7 @@ fn test_short_quality_opener() {
         assert_eq!(guaranteed_quality(SETTINGS, &actions).unwrap(), 300);
         assert_eq!(state.quality, 300);
     } else {
-        panic!("Unexpected err: {}", state.err().unwrap());
+        panic!("Unexpected err: {}", state.err().unwrap().1);
     }
 }
 
@@ -96,7 +103,7 @@ fn test_long_quality_opener() {
         assert_eq!(guaranteed_quality(SETTINGS, &actions).unwrap(), 1140);
         assert_eq!(state.quality, 1140);
     } else {
-        panic!("Unexpected err: {}", state.err().unwrap());
+        panic!("Unexpected err: {}", state.err().unwrap().1);
     }
 }
 
@@ -116,7 +123,7 @@ fn test_alternating_quality_actions() {
         assert_eq!(guaranteed_quality(SETTINGS, &actions).unwrap(), 440);
         assert_eq!(state.quality, 440);
     } else {
-        panic!("Unexpected err: {}", state.err().unwrap());
+        panic!("Unexpected err: {}", state.err().unwrap().1);
     }
 }
 
@@ -137,7 +144,7 @@ fn test_double_status_drops() {
         assert_eq!(guaranteed_quality(SETTINGS, &actions).unwrap(), 525);
         assert_eq!(state.quality, 525);
     } else {
-        panic!("Unexpected err: {}", state.err().unwrap());
+        panic!("Unexpected err: {}", state.err().unwrap().1);
     }
 }
 
@@ -158,7 +165,7 @@ fn test_two_action_drops() {
         assert_eq!(guaranteed_quality(SETTINGS, &actions).unwrap(), 607);
         assert_eq!(state.quality, 607);
     } else {
-        panic!("Unexpected err: {}", state.err().unwrap());
+        panic!("Unexpected err: {}", state.err().unwrap().1);
     }
 }
 
@@ -180,7 +187,7 @@ fn test_dp() {
         assert_eq!(guaranteed_quality(SETTINGS, &actions).unwrap(), 952);
         assert_eq!(state.quality, 952);
     } else {
-        panic!("Unexpected err: {}", state.err().unwrap());
+        panic!("Unexpected err: {}", state.err().unwrap().1);
     }
 }
 
@@ -211,7 +218,84 @@ fn test_long_sequence() {
         assert_eq!(guaranteed_quality(SETTINGS, &actions).unwrap(), 2924);
         assert_eq!(state.quality, 2924);
     } else {
-        panic!("Unexpected err: {}", state.err().unwrap());
+        panic!("Unexpected err: {}", state.err().unwrap().1);
+    }
+}
+
+/// Quality reached when using the actions under the given conditions without the adversarial setting
+fn quality_under_conditions(
+    settings: Settings,
+    actions: &[Action],
+    conditions: &[Condition],
+) -> Result<u32, &'static str> {
+    let settings = Settings {
+        adversarial: false,
+        ..settings
+    };
+    let mut state = SimulationState::new(&settings);
+    for (action, condition) in actions.iter().zip(conditions) {
+        state = state.use_action(*action, *condition, &settings)?;
+    }
+    Ok(state.quality)
+}
+
+#[test]
+fn test_worst_case_conditions() {
+    let actions = [
+        Action::Reflect,
+        Action::Manipulation,
+        Action::Innovation,
+        Action::WasteNot2,
+        Action::BasicTouch,
+        Action::StandardTouch,
+        Action::PreparatoryTouch,
+        Action::Veneration,
+        Action::DelicateSynthesis,
+        Action::Groundwork,
+        Action::Groundwork,
+        Action::Groundwork,
+        Action::Innovation,
+        Action::BasicTouch,
+        Action::StandardTouch,
+        Action::AdvancedTouch,
+        Action::ByregotsBlessing,
+        Action::CarefulSynthesis,
+    ];
+    let conditions = SimulationState::worst_case_conditions(&SETTINGS, &actions).unwrap();
+    assert_eq!(conditions.len(), actions.len());
+    assert_eq!(conditions[0], Condition::Normal);
+    for window in conditions.windows(2) {
+        if window[0] == Condition::Excellent {
+            assert_eq!(window[1], Condition::Poor);
+        }
+        if window[1] == Condition::Poor {
+            assert_eq!(window[0], Condition::Excellent);
+        }
+    }
+    assert_eq!(
+        quality_under_conditions(SETTINGS, &actions, &conditions).unwrap(),
+        2924
+    );
+    // Excellent procs are only granted when they make the next action Poor
+    assert!(conditions.contains(&Condition::Poor));
+}
+
+#[test]
+fn test_worst_case_conditions_exhaustive() {
+    const STEPS: usize = 10;
+    for mask in 0..(1 << STEPS) {
+        let actions: Vec<Action> = (0..STEPS)
+            .map(|index| match (mask >> index) & 1 {
+                0 => Action::Observe,
+                _ => Action::PrudentTouch,
+            })
+            .collect();
+        let state = SimulationState::from_macro(&SETTINGS, &actions).unwrap();
+        let conditions = SimulationState::worst_case_conditions(&SETTINGS, &actions).unwrap();
+        assert_eq!(
+            quality_under_conditions(SETTINGS, &actions, &conditions).unwrap(),
+            state.quality
+        );
     }
 }
 
@@ -234,7 +318,7 @@ fn test_exhaustive() {
                 guaranteed_quality(SETTINGS, &actions).unwrap()
             );
         } else {
-            panic!("Unexpected err: {}", state.err().unwrap());
+            panic!("Unexpected err: {}", state.err().unwrap().1);
         }
     }
 }
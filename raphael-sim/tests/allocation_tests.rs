@@ -0,0 +1,82 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use raphael_sim::{
+    Action, ActionMask, Condition, CpCostOverrides, RoundingMode, Settings, SimulationState,
+};
+
+/// Counts the allocations of each thread so that tests running in parallel don't interfere.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|allocations| allocations.set(allocations.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations() -> usize {
+    ALLOCATIONS.with(Cell::get)
+}
+
+#[test]
+fn test_simulate_into_does_not_allocate() {
+    let settings = Settings {
+        max_cp: 500,
+        max_durability: 60,
+        max_progress: 2000,
+        max_quality: 4000,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 100,
+        allowed_actions: ActionMask::all(),
+        adversarial: true,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    let actions = [
+        Action::MuscleMemory,
+        Action::Manipulation,
+        Action::Innovation,
+        Action::PreparatoryTouch,
+        Action::PreciseTouch,
+        Action::GreatStrides,
+        Action::ByregotsBlessing,
+        Action::CarefulSynthesis,
+    ];
+    let conditions = [
+        Condition::Normal,
+        Condition::Normal,
+        Condition::Good,
+        Condition::Normal,
+        Condition::Excellent,
+        Condition::Poor,
+    ];
+    let before = allocations();
+    for _ in 0..1000 {
+        let mut state = SimulationState::new(&settings);
+        let result = SimulationState::simulate_into(
+            std::hint::black_box(&mut state),
+            &actions,
+            &conditions,
+            &settings,
+        );
+        std::hint::black_box(result).ok();
+    }
+    assert_eq!(allocations() - before, 0);
+}
@@ -1,4 +1,6 @@
-use raphael_sim::{Action, ActionMask, Condition, Settings, SimulationState};
+use raphael_sim::{
+    Action, ActionMask, Condition, CpCostOverrides, RoundingMode, Settings, SimulationState,
+};
 
 const SETTINGS: Settings = Settings {
     max_cp: 500,
@@ -11,6 +13,11 @@ const SETTINGS: Settings = Settings {
     allowed_actions: ActionMask::all(),
     adversarial: true,
     backload_progress: true,
+    quality_increase_cap: None,
+    progress_target: None,
+    progress_optional: false,
+    rounding_mode: RoundingMode::Floor,
+    cp_cost_overrides: CpCostOverrides::none(),
 };
 
 #[test]
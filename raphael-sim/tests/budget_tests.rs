@@ -0,0 +1,122 @@
+use raphael_sim::*;
+
+const SETTINGS: Settings = Settings {
+    max_cp: 500,
+    max_durability: 80,
+    max_progress: 2000,
+    max_quality: 20000,
+    base_progress: 100,
+    base_quality: 100,
+    job_level: 100,
+    allowed_actions: ActionMask::all(),
+    adversarial: false,
+    backload_progress: false,
+    quality_increase_cap: None,
+    progress_target: None,
+    progress_optional: false,
+    rounding_mode: RoundingMode::Floor,
+    cp_cost_overrides: CpCostOverrides::none(),
+};
+
+#[test]
+fn test_rotation_budget() {
+    let actions = [
+        Action::MuscleMemory,
+        Action::Manipulation,
+        Action::Veneration,
+        Action::Groundwork,
+        Action::Innovation,
+        Action::BasicTouch,
+        Action::StandardTouch,
+        Action::DelicateSynthesis,
+        Action::MasterMend,
+        Action::GreatStrides,
+        Action::ByregotsBlessing,
+        Action::CarefulSynthesis,
+    ];
+    let budget = rotation_budget(&SETTINGS, &actions).unwrap();
+    assert_eq!(
+        budget,
+        RotationBudget {
+            progress: ActionBudget { cp: 31, steps: 3 },
+            quality: ActionBudget { cp: 92, steps: 4 },
+            other: ActionBudget { cp: 252, steps: 5 },
+        }
+    );
+    let state = SimulationState::from_macro(&SETTINGS, &actions).unwrap();
+    assert_eq!(
+        budget.progress.cp + budget.quality.cp + budget.other.cp,
+        SETTINGS.max_cp - state.cp
+    );
+    assert_eq!(
+        budget.progress.steps + budget.quality.steps + budget.other.steps,
+        actions.len() as u8
+    );
+}
+
+#[test]
+fn test_rotation_budget_tricks_of_the_trade() {
+    let actions = [
+        Action::BasicTouch,
+        Action::HeartAndSoul,
+        Action::TricksOfTheTrade,
+    ];
+    let budget = rotation_budget(&SETTINGS, &actions).unwrap();
+    assert_eq!(
+        budget,
+        RotationBudget {
+            progress: ActionBudget::default(),
+            quality: ActionBudget { cp: 18, steps: 1 },
+            other: ActionBudget { cp: 0, steps: 2 },
+        }
+    );
+    // CP restored by Tricks of the Trade doesn't offset the CP spent on Basic Touch
+    assert_eq!(
+        budget.progress.cp + budget.quality.cp + budget.other.cp,
+        total_cp_cost(&SETTINGS, &actions).unwrap()
+    );
+}
+
+#[test]
+fn test_rotation_budget_invalid_action() {
+    let actions = [Action::BasicSynthesis, Action::MuscleMemory];
+    assert_eq!(
+        rotation_budget(&SETTINGS, &actions),
+        Err((1, "Muscle Memory can only be used at synthesis begin."))
+    );
+}
+
+#[test]
+fn test_total_cp_cost() {
+    let actions = [
+        Action::BasicTouch,
+        Action::StandardTouch,
+        Action::AdvancedTouch,
+        Action::BasicSynthesis,
+    ];
+    let cp_cost = total_cp_cost(&SETTINGS, &actions).unwrap();
+    let state = SimulationState::from_macro(&SETTINGS, &actions).unwrap();
+    assert_eq!(cp_cost, SETTINGS.max_cp - state.cp);
+    // Standard Touch and Advanced Touch are discounted by the combo
+    assert_eq!(cp_cost, 18 + 18 + 18);
+}
+
+#[test]
+fn test_total_cp_cost_tricks_of_the_trade() {
+    let actions = [
+        Action::BasicTouch,
+        Action::HeartAndSoul,
+        Action::TricksOfTheTrade,
+    ];
+    let state = SimulationState::from_macro(&SETTINGS, &actions).unwrap();
+    assert_eq!(state.cp, SETTINGS.max_cp);
+    // CP restored by Tricks of the Trade doesn't offset the CP spent on Basic Touch
+    assert_eq!(total_cp_cost(&SETTINGS, &actions), Ok(18));
+    assert_eq!(
+        total_cp_cost(&SETTINGS, &[Action::TricksOfTheTrade]),
+        Err((
+            0,
+            "Tricks of the Trade can only be used when the condition is Good or Excellent."
+        ))
+    );
+}
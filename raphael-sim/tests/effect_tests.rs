@@ -11,6 +11,11 @@ const SETTINGS: Settings = Settings {
     allowed_actions: ActionMask::all(),
     adversarial: false,
     backload_progress: false,
+    quality_increase_cap: None,
+    progress_target: None,
+    progress_optional: false,
+    rounding_mode: RoundingMode::Floor,
+    cp_cost_overrides: CpCostOverrides::none(),
 };
 
 /// Returns the 4 primary stats of a state:
@@ -45,3 +50,276 @@ fn test_trained_perfection() {
         .unwrap();
     assert_eq!(state.effects.trained_perfection_active(), true);
 }
+
+#[test]
+fn test_buff_timeline() {
+    let actions = [
+        Action::Manipulation,
+        Action::Innovation,
+        Action::Veneration,
+        Action::GreatStrides,
+        Action::WasteNot,
+        Action::Innovation,
+        Action::BasicTouch,
+    ];
+    let settings = Settings {
+        max_cp: 500,
+        ..SETTINGS
+    };
+    let timeline = SimulationState::buff_timeline(&settings, &actions).unwrap();
+    let expected = [
+        (0, 0, 0, 0, 8),
+        (4, 0, 0, 0, 7),
+        (3, 4, 0, 0, 6),
+        (2, 3, 3, 0, 5),
+        (1, 2, 2, 4, 4),
+        (4, 1, 1, 3, 3), // Innovation is reapplied
+        (3, 0, 0, 2, 2), // Great Strides is consumed by Basic Touch
+    ];
+    let timeline: Vec<_> = timeline
+        .into_iter()
+        .map(|durations| {
+            (
+                durations.innovation,
+                durations.veneration,
+                durations.great_strides,
+                durations.waste_not,
+                durations.manipulation,
+            )
+        })
+        .collect();
+    assert_eq!(timeline, expected);
+}
+
+#[test]
+fn test_remaining_innovation_actions() {
+    for innovation in 0..=4 {
+        let initial_state = SimulationState {
+            effects: Effects::new()
+                .with_allow_quality_actions(true)
+                .with_innovation(innovation),
+            ..SimulationState::new(&SETTINGS)
+        };
+        let remaining_actions = initial_state.effects.remaining_innovation_actions();
+        assert_eq!(remaining_actions, usize::from(innovation));
+        // Count the Prudent Touches that get the Innovation bonus
+        let mut state = initial_state;
+        let mut boosted_actions = 0;
+        for _ in 0..5 {
+            let next_state = state
+                .use_action(Action::PrudentTouch, Condition::Normal, &SETTINGS)
+                .unwrap();
+            let inner_quiet_mod = 100 + 10 * u32::from(state.effects.inner_quiet());
+            if next_state.quality - state.quality > inner_quiet_mod {
+                boosted_actions += 1;
+            }
+            state = next_state;
+        }
+        assert_eq!(boosted_actions, remaining_actions);
+    }
+}
+
+#[test]
+fn test_canonicalize() {
+    let effects = Effects::new()
+        .with_allow_quality_actions(true)
+        .with_inner_quiet(2)
+        .with_waste_not(2);
+    let short_buffs = effects.with_innovation(2).with_manipulation(2);
+    let long_buffs = effects.with_innovation(4).with_manipulation(8);
+    assert_ne!(short_buffs.canonicalize(3), long_buffs.canonicalize(3));
+    assert_eq!(short_buffs.canonicalize(2), long_buffs.canonicalize(2));
+    // Both reach the same outcome within the horizon
+    let actions = [Action::CarefulSynthesis, Action::BasicTouch];
+    let outcome = |effects: Effects| {
+        let mut state = SimulationState {
+            durability: 30,
+            effects,
+            ..SimulationState::new(&SETTINGS)
+        };
+        for action in actions {
+            state = state
+                .use_action(action, Condition::Normal, &SETTINGS)
+                .unwrap();
+        }
+        (state.progress, state.quality, state.cp, state.durability)
+    };
+    assert_eq!(outcome(short_buffs), outcome(long_buffs));
+
+    // Quality effects don't matter once Quality actions are forbidden
+    let effects = Effects::new().with_veneration(3);
+    assert_eq!(
+        effects
+            .with_inner_quiet(5)
+            .with_innovation(2)
+            .canonicalize(8),
+        effects.with_great_strides(1).canonicalize(8)
+    );
+}
+
+#[test]
+fn test_effective_control() {
+    assert_eq!(effective_control(4321, 0), 4321);
+    assert_eq!(effective_control(4321, 5), 6481);
+    assert_eq!(effective_control(4321, 10), 8642);
+    // Matches the Quality increase of a 100% efficiency action with Control as base Quality
+    let settings = Settings {
+        base_quality: 4321,
+        ..SETTINGS
+    };
+    for inner_quiet in [0, 5, 10] {
+        let state = SimulationState {
+            effects: Effects::initial(&settings).with_inner_quiet(inner_quiet),
+            ..SimulationState::new(&settings)
+        }
+        .use_action(Action::BasicTouch, Condition::Normal, &settings)
+        .unwrap();
+        assert_eq!(state.quality, effective_control(4321, inner_quiet));
+    }
+}
+
+#[test]
+fn test_inner_quiet_level_independent() {
+    for job_level in [90, 100] {
+        let settings = Settings {
+            job_level,
+            ..SETTINGS
+        };
+        for (inner_quiet, expected_quality) in [(0, 100), (1, 110), (5, 150), (10, 200)] {
+            let state = SimulationState {
+                effects: Effects::initial(&settings).with_inner_quiet(inner_quiet),
+                ..SimulationState::new(&settings)
+            }
+            .use_action(Action::BasicTouch, Condition::Normal, &settings)
+            .unwrap();
+            assert_eq!(state.quality, expected_quality);
+            assert_eq!(state.quality, effective_control(100, inner_quiet));
+        }
+    }
+}
+
+#[test]
+fn test_active_effects() {
+    assert_eq!(Effects::new().active(), []);
+    let effects = Effects::new()
+        .with_inner_quiet(7)
+        .with_innovation(2)
+        .with_manipulation(5)
+        .with_trained_perfection_active(true)
+        .with_allow_quality_actions(true);
+    assert_eq!(
+        effects.active(),
+        [
+            (EffectKind::InnerQuiet, 7),
+            (EffectKind::Innovation, 2),
+            (EffectKind::Manipulation, 5),
+            (EffectKind::TrainedPerfection, 1),
+        ]
+    );
+    // Effects that only become active after an action are not listed
+    let effects = Effects::initial(&SETTINGS);
+    assert_eq!(effects.active(), []);
+    // Only buffs are listed, even if every effect is set
+    let mut effects = Effects::new();
+    for kind in EffectKind::ALL {
+        effects.set(kind, 1);
+    }
+    let active_kinds: Vec<EffectKind> =
+        effects.active().into_iter().map(|(kind, _)| kind).collect();
+    let buffs: Vec<EffectKind> = EffectKind::ALL
+        .into_iter()
+        .filter(|kind| kind.is_buff())
+        .collect();
+    assert_eq!(active_kinds, buffs);
+    assert_eq!(buffs.len(), 9);
+}
+
+#[test]
+fn test_effect_kind_get_set() {
+    for kind in EffectKind::ALL {
+        assert_eq!(Effects::new().get(kind), 0);
+        let mut effects = Effects::new();
+        effects.set(kind, 1);
+        assert_eq!(effects.get(kind), 1);
+        // No other effect is changed
+        for other in EffectKind::ALL.into_iter().filter(|other| *other != kind) {
+            assert_eq!(effects.get(other), 0);
+        }
+        effects.set(kind, 0);
+        assert_eq!(effects, Effects::new());
+    }
+    let mut effects = Effects::new();
+    effects.set(EffectKind::InnerQuiet, 10);
+    effects.set(EffectKind::Manipulation, 8);
+    assert_eq!(
+        effects,
+        Effects::new().with_inner_quiet(10).with_manipulation(8)
+    );
+    assert_eq!(effects.get(EffectKind::InnerQuiet), 10);
+    assert_eq!(effects.get(EffectKind::Manipulation), 8);
+    // Flags are set by any value other than 0
+    effects.set(EffectKind::AdversarialGuard, 2);
+    assert_eq!(effects.get(EffectKind::AdversarialGuard), 1);
+}
+
+#[test]
+fn test_max_quality_from_inner_quiet() {
+    // 100 base Quality * 300% efficiency * 250% buffs * 200% Inner Quiet
+    assert_eq!(max_quality_from_inner_quiet(&SETTINGS), 1500);
+    let settings = Settings {
+        base_quality: 345,
+        ..SETTINGS
+    };
+    assert_eq!(max_quality_from_inner_quiet(&settings), 5175);
+    // Matches the finisher of a rotation that builds up 10 stacks
+    let actions = [
+        Action::Reflect,
+        Action::PreparatoryTouch,
+        Action::PreparatoryTouch,
+        Action::PreparatoryTouch,
+        Action::PreparatoryTouch,
+        Action::Innovation,
+        Action::GreatStrides,
+    ];
+    let settings = Settings {
+        max_cp: 1000,
+        max_durability: 100,
+        ..SETTINGS
+    };
+    let state = SimulationState::from_macro(&settings, &actions).unwrap();
+    assert_eq!(state.effects.inner_quiet(), 10);
+    let finished = state
+        .use_action(Action::ByregotsBlessing, Condition::Normal, &settings)
+        .unwrap();
+    assert_eq!(
+        finished.quality - state.quality,
+        max_quality_from_inner_quiet(&settings)
+    );
+}
+
+#[test]
+fn test_effects_layout() {
+    let effects = Effects::new()
+        .with_inner_quiet(10)
+        .with_waste_not(8)
+        .with_innovation(4)
+        .with_veneration(3)
+        .with_great_strides(3)
+        .with_muscle_memory(5)
+        .with_manipulation(8)
+        .with_heart_and_soul_available(true)
+        .with_allow_quality_actions(true)
+        .with_combo(Combo::BasicTouch);
+    assert_eq!(effects.into_bits(), 0x6145_DC8A);
+    assert_eq!(effects.into_bits().to_le_bytes(), [0x8A, 0xDC, 0x45, 0x61]);
+    assert_eq!(Effects::from_bits(0x6145_DC8A), effects);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_effects_serialization() {
+    let effects = Effects::from_bits(0x6145_DC8A);
+    let json = serde_json::to_string(&effects).unwrap();
+    assert_eq!(json, "1631968394");
+    assert_eq!(serde_json::from_str::<Effects>(&json).unwrap(), effects);
+}
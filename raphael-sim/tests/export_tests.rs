@@ -0,0 +1,120 @@
+use raphael_sim::*;
+
+const SETTINGS: Settings = Settings {
+    max_cp: 500,
+    max_durability: 80,
+    max_progress: 2000,
+    max_quality: 20000,
+    base_progress: 100,
+    base_quality: 100,
+    job_level: 100,
+    allowed_actions: ActionMask::all(),
+    adversarial: false,
+    backload_progress: false,
+    quality_increase_cap: None,
+    progress_target: None,
+    progress_optional: false,
+    rounding_mode: RoundingMode::Floor,
+    cp_cost_overrides: CpCostOverrides::none(),
+};
+
+#[test]
+fn test_crafting_simulator_json() {
+    let actions = [
+        Action::MuscleMemory,
+        Action::WasteNot2,
+        Action::MasterMend,
+        Action::ByregotsBlessing,
+    ];
+    let json = to_crafting_simulator_json(&SETTINGS, &actions);
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(
+        value,
+        serde_json::json!({
+            "rotation": ["MuscleMemory", "WasteNotII", "MastersMend", "ByregotsBlessing"],
+            "recipe": { "progress": 2000, "quality": 20000, "durability": 80 },
+            "stats": { "level": 100, "cp": 500 },
+        })
+    );
+}
+
+#[test]
+fn test_crafting_simulator_action_names_are_unique() {
+    let mut names: Vec<&str> = ActionMask::all()
+        .actions_iter()
+        .map(crafting_simulator_action_name)
+        .collect();
+    names.sort_unstable();
+    names.dedup();
+    assert_eq!(names.len(), ActionMask::all().actions().len());
+}
+
+#[test]
+fn test_split_into_macros() {
+    let actions = [
+        Action::MuscleMemory,
+        Action::Manipulation,
+        Action::Veneration,
+        Action::BasicTouch,
+        Action::StandardTouch,
+        Action::AdvancedTouch,
+        Action::Observe,
+        Action::AdvancedTouch,
+        Action::BasicTouch,
+        Action::RefinedTouch,
+        Action::ByregotsBlessing,
+    ];
+    for lines_per_macro in 3..=actions.len() {
+        let macros = split_into_macros(&actions, lines_per_macro);
+        assert_eq!(macros.concat(), actions);
+        for window in macros.windows(2) {
+            assert!(window[0].len() <= lines_per_macro);
+            // No combo is broken across a macro boundary
+            let prev = *window[0].last().unwrap();
+            let next = window[1][0];
+            assert_ne!(next.required_combo(), Some(prev.to_combo()));
+        }
+    }
+    let macros = split_into_macros(&actions, 5);
+    assert_eq!(macros, [&actions[0..3], &actions[3..8], &actions[8..11],]);
+}
+
+#[test]
+fn test_rotation_delta_round_trip() {
+    let base = [
+        Action::MuscleMemory,
+        Action::Manipulation,
+        Action::Veneration,
+        Action::WasteNot,
+        Action::Groundwork,
+        Action::Innovation,
+        Action::PreparatoryTouch,
+        Action::GreatStrides,
+        Action::ByregotsBlessing,
+        Action::BasicSynthesis,
+    ];
+    // one action replaced
+    let mut replaced = base.to_vec();
+    replaced[4] = Action::CarefulSynthesis;
+    // actions inserted in the middle
+    let mut inserted = base.to_vec();
+    inserted.splice(6..6, [Action::BasicTouch, Action::StandardTouch]);
+    // completely different
+    let different = [
+        Action::Reflect,
+        Action::Observe,
+        Action::AdvancedTouch,
+        Action::TrainedFinesse,
+    ];
+    for variant in [&replaced[..], &inserted[..], &different[..]] {
+        let delta = encode_rotation_delta(&base, variant);
+        assert_eq!(decode_rotation_delta(&base, &delta).unwrap(), variant);
+    }
+    assert_eq!(encode_rotation_delta(&base, &replaced), "4:5:0e");
+    assert_eq!(encode_rotation_delta(&base, &inserted), "6:4:0107");
+    assert_eq!(encode_rotation_delta(&base, &base), "10:0:");
+    assert_eq!(decode_rotation_delta(&base, "10:0:").unwrap(), base);
+    assert!(decode_rotation_delta(&base, "8:3:").is_err());
+    assert!(decode_rotation_delta(&base, "0:0:ff").is_err());
+    assert!(decode_rotation_delta(&base, "0:0:1").is_err());
+}
@@ -0,0 +1,60 @@
+use raphael_sim::*;
+
+const SETTINGS: Settings = Settings {
+    max_cp: 500,
+    max_durability: 80,
+    max_progress: 5000,
+    max_quality: 20000,
+    base_progress: 100,
+    base_quality: 100,
+    job_level: 100,
+    allowed_actions: ActionMask::all(),
+    adversarial: false,
+    backload_progress: false,
+    quality_increase_cap: None,
+    progress_target: None,
+    progress_optional: false,
+    rounding_mode: RoundingMode::Floor,
+    cp_cost_overrides: CpCostOverrides::none(),
+};
+
+#[test]
+fn test_great_strides_used() {
+    let actions = [
+        Action::GreatStrides,
+        Action::Innovation,
+        Action::Veneration,
+        Action::BasicTouch,
+    ];
+    assert_eq!(lint_rotation(&SETTINGS, &actions), []);
+}
+
+#[test]
+fn test_great_strides_expired() {
+    let actions = [
+        Action::Reflect,
+        Action::GreatStrides,
+        Action::Innovation,
+        Action::Veneration,
+        Action::WasteNot,
+        Action::BasicTouch,
+    ];
+    assert_eq!(
+        lint_rotation(&SETTINGS, &actions),
+        [RotationLint::GreatStridesWasted { step: 1 }]
+    );
+}
+
+#[test]
+fn test_great_strides_overwritten() {
+    let actions = [
+        Action::GreatStrides,
+        Action::BasicSynthesis,
+        Action::GreatStrides,
+        Action::BasicTouch,
+    ];
+    assert_eq!(
+        lint_rotation(&SETTINGS, &actions),
+        [RotationLint::GreatStridesWasted { step: 0 }]
+    );
+}
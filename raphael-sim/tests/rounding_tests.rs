@@ -0,0 +1,63 @@
+use raphael_sim::*;
+
+const SETTINGS: Settings = Settings {
+    max_cp: 500,
+    max_durability: 80,
+    max_progress: 5000,
+    max_quality: 20000,
+    base_progress: 101,
+    base_quality: 101,
+    job_level: 100,
+    allowed_actions: ActionMask::all(),
+    adversarial: false,
+    backload_progress: false,
+    quality_increase_cap: None,
+    progress_target: None,
+    progress_optional: false,
+    rounding_mode: RoundingMode::Floor,
+    cp_cost_overrides: CpCostOverrides::none(),
+};
+
+fn progress_and_quality(settings: &Settings, actions: &[Action]) -> (u32, u32) {
+    let state = SimulationState::from_macro(settings, actions).unwrap();
+    (state.progress, state.quality)
+}
+
+#[test]
+fn test_floor() {
+    // 101 * 120% * 150% = 181.8
+    let actions = [Action::Veneration, Action::BasicSynthesis];
+    assert_eq!(progress_and_quality(&SETTINGS, &actions), (181, 0));
+    // 101 * 100% * 150% = 151.5
+    let actions = [Action::Innovation, Action::BasicTouch];
+    assert_eq!(progress_and_quality(&SETTINGS, &actions), (0, 151));
+}
+
+#[test]
+fn test_round_half_up() {
+    let settings = Settings {
+        rounding_mode: RoundingMode::RoundHalfUp,
+        cp_cost_overrides: CpCostOverrides::none(),
+        ..SETTINGS
+    };
+    // 101 * 120% * 150% = 181.8
+    let actions = [Action::Veneration, Action::BasicSynthesis];
+    assert_eq!(progress_and_quality(&settings, &actions), (182, 0));
+    // 101 * 100% * 150% = 151.5
+    let actions = [Action::Innovation, Action::BasicTouch];
+    assert_eq!(progress_and_quality(&settings, &actions), (0, 152));
+}
+
+#[test]
+fn test_exact_values_unaffected() {
+    let settings = Settings {
+        rounding_mode: RoundingMode::RoundHalfUp,
+        cp_cost_overrides: CpCostOverrides::none(),
+        ..SETTINGS
+    };
+    let actions = [Action::BasicTouch, Action::BasicTouch];
+    assert_eq!(
+        progress_and_quality(&settings, &actions),
+        progress_and_quality(&SETTINGS, &actions)
+    );
+}
@@ -1,4 +1,7 @@
-use raphael_sim::{Action, ActionMask, Condition, Settings, SimulationState};
+use raphael_sim::{
+    Action, ActionMask, Combo, Condition, CpCostOverrides, CraftOutcome, RoundingMode, Settings,
+    SimulationState,
+};
 
 fn simulate(
     settings: &Settings,
@@ -36,6 +39,11 @@ fn test_level_requirement() {
         allowed_actions: ActionMask::all(),
         adversarial: false,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let error = SimulationState::new(&settings)
         .use_action(Action::ImmaculateMend, Condition::Normal, &settings)
@@ -58,6 +66,11 @@ fn test_random_926ae85b() {
         allowed_actions: ActionMask::all(),
         adversarial: false,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let actions = [
         Action::BasicSynthesis,
@@ -87,6 +100,11 @@ fn test_random_3c721e47() {
         allowed_actions: ActionMask::all(),
         adversarial: false,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let actions = [
         Action::MuscleMemory,
@@ -120,6 +138,11 @@ fn test_random_3ba90d3a() {
         allowed_actions: ActionMask::all(),
         adversarial: false,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let actions = [
         Action::Veneration,
@@ -156,6 +179,11 @@ fn test_random_bce2650c() {
         allowed_actions: ActionMask::all(),
         adversarial: false,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let actions = [
         Action::MuscleMemory,
@@ -207,6 +235,11 @@ fn test_ingame_be9fc5c2() {
         allowed_actions: ActionMask::all(),
         adversarial: false,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let states = simulate(
         &settings,
@@ -279,6 +312,11 @@ fn test_ingame_d11d9c68() {
         allowed_actions: ActionMask::all(),
         adversarial: false,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let actions = [
         Action::Reflect,
@@ -335,6 +373,11 @@ fn test_ingame_f9f0dac7() {
         allowed_actions: ActionMask::all(),
         adversarial: true,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let actions = [
         Action::Reflect,
@@ -422,6 +465,11 @@ fn test_ingame_4866545e() {
         allowed_actions: ActionMask::all(),
         adversarial: false,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let actions = [
         Action::Reflect,
@@ -472,3 +520,615 @@ fn test_ingame_4866545e() {
         ]
     );
 }
+
+#[test]
+fn test_continued_state_rejects_opener() {
+    let settings = Settings {
+        max_cp: 500,
+        max_durability: 60,
+        max_progress: 2000,
+        max_quality: 4000,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 100,
+        allowed_actions: ActionMask::all(),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    let state = SimulationState::from_parts(&settings, 400, 40, 600, 300, Combo::None).unwrap();
+    let error = state
+        .use_action(Action::MuscleMemory, Condition::Normal, &settings)
+        .unwrap_err();
+    assert_eq!(error, "Muscle Memory can only be used at synthesis begin.");
+    let state = state
+        .use_action(Action::BasicSynthesis, Condition::Normal, &settings)
+        .unwrap();
+    assert_eq!((state.progress, state.quality), (720, 300));
+
+    // a fresh craft still allows the opener
+    let state =
+        SimulationState::from_parts(&settings, 500, 60, 0, 0, Combo::SynthesisBegin).unwrap();
+    assert!(
+        state
+            .use_action(Action::MuscleMemory, Condition::Normal, &settings)
+            .is_ok()
+    );
+}
+
+#[test]
+fn test_continued_state_resumes_combo() {
+    let settings = Settings {
+        max_cp: 500,
+        max_durability: 60,
+        max_progress: 2000,
+        max_quality: 4000,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 100,
+        allowed_actions: ActionMask::all(),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    // Advanced Touch after a combo'd Standard Touch (or Observe) costs 18 CP instead of 46 CP
+    let state =
+        SimulationState::from_parts(&settings, 200, 40, 600, 300, Combo::StandardTouch).unwrap();
+    let state = state
+        .use_action(Action::AdvancedTouch, Condition::Normal, &settings)
+        .unwrap();
+    assert_eq!(state.cp, 182);
+    let state = SimulationState::from_parts(&settings, 200, 40, 600, 300, Combo::None).unwrap();
+    let state = state
+        .use_action(Action::AdvancedTouch, Condition::Normal, &settings)
+        .unwrap();
+    assert_eq!(state.cp, 154);
+    // Standard Touch continues a Basic Touch combo
+    let state =
+        SimulationState::from_parts(&settings, 200, 40, 600, 300, Combo::BasicTouch).unwrap();
+    let state = state
+        .use_action(Action::StandardTouch, Condition::Normal, &settings)
+        .unwrap();
+    assert_eq!(state.cp, 182);
+    assert_eq!(state.effects.combo(), Combo::StandardTouch);
+
+    // combos that cannot have happened are rejected
+    let low_level_settings = Settings {
+        job_level: 10,
+        ..settings
+    };
+    assert!(
+        SimulationState::from_parts(&low_level_settings, 200, 40, 0, 0, Combo::StandardTouch)
+            .is_err()
+    );
+    assert!(
+        SimulationState::from_parts(&low_level_settings, 200, 40, 0, 0, Combo::BasicTouch).is_ok()
+    );
+    assert!(
+        SimulationState::from_parts(&settings, 400, 40, 600, 300, Combo::SynthesisBegin).is_err()
+    );
+}
+
+#[test]
+fn test_use_actions_until() {
+    let settings = Settings {
+        max_cp: 500,
+        max_durability: 60,
+        max_progress: 2000,
+        max_quality: 4000,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 100,
+        allowed_actions: ActionMask::all(),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    let actions = [
+        Action::BasicTouch,
+        Action::BasicTouch,
+        Action::BasicTouch,
+        Action::BasicTouch,
+        Action::BasicSynthesis,
+    ];
+    let (state, used_actions) = SimulationState::new(&settings)
+        .use_actions_until(&actions, &settings, |state| state.durability <= 35)
+        .unwrap();
+    assert_eq!(used_actions, 3);
+    assert_eq!(state.durability, 30);
+
+    // the predicate never holds
+    let (state, used_actions) = SimulationState::new(&settings)
+        .use_actions_until(&actions, &settings, |state| state.durability == 0)
+        .unwrap();
+    assert_eq!(used_actions, actions.len());
+    assert_eq!(state.durability, 10);
+
+    // the predicate already holds for the initial state
+    let (_, used_actions) = SimulationState::new(&settings)
+        .use_actions_until(&actions, &settings, |_| true)
+        .unwrap();
+    assert_eq!(used_actions, 0);
+}
+
+#[test]
+fn test_overkill() {
+    let settings = Settings {
+        max_cp: 500,
+        max_durability: 60,
+        max_progress: 250,
+        max_quality: 150,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 100,
+        allowed_actions: ActionMask::all(),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    let state =
+        SimulationState::from_macro(&settings, &[Action::BasicTouch, Action::BasicSynthesis])
+            .unwrap();
+    assert_eq!((state.progress, state.quality), (120, 100));
+    assert_eq!(state.progress_overkill(&settings), 0);
+    assert_eq!(state.quality_overkill(&settings), 0);
+    // the last Basic Synthesis only needs 130 of its 180 Progress
+    let state = SimulationState::from_macro(
+        &settings,
+        &[
+            Action::BasicTouch,
+            Action::StandardTouch,
+            Action::BasicSynthesis,
+            Action::Veneration,
+            Action::BasicSynthesis,
+        ],
+    )
+    .unwrap();
+    assert_eq!((state.progress, state.quality), (300, 237));
+    assert_eq!(state.progress_overkill(&settings), 50);
+    assert_eq!(state.quality_overkill(&settings), 87);
+}
+
+#[test]
+fn test_from_macro() {
+    let settings = Settings {
+        max_cp: 500,
+        max_durability: 60,
+        max_progress: 2000,
+        max_quality: 4000,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 100,
+        allowed_actions: ActionMask::all(),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    let state = SimulationState::from_macro(
+        &settings,
+        &[
+            Action::MuscleMemory,
+            Action::BasicTouch,
+            Action::StandardTouch,
+        ],
+    )
+    .unwrap();
+    assert_eq!((state.progress, state.quality), (300, 237));
+    assert_eq!((state.cp, state.durability), (458, 30));
+    // the index of the first illegal action is reported
+    let error = SimulationState::from_macro(
+        &settings,
+        &[
+            Action::BasicTouch,
+            Action::BasicSynthesis,
+            Action::MuscleMemory,
+            Action::BasicSynthesis,
+        ],
+    )
+    .unwrap_err();
+    assert_eq!(
+        error,
+        (2, "Muscle Memory can only be used at synthesis begin.")
+    );
+}
+
+#[test]
+fn test_quality_capped_at_step() {
+    let settings = Settings {
+        max_cp: 500,
+        max_durability: 60,
+        max_progress: 2000,
+        max_quality: 400,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 100,
+        allowed_actions: ActionMask::all(),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    let actions = [
+        Action::Reflect,
+        Action::Innovation,
+        Action::BasicTouch,
+        Action::StandardTouch,
+        Action::Veneration,
+        Action::Groundwork,
+        Action::Groundwork,
+    ];
+    // Reflect (300) + Basic Touch (180)
+    assert_eq!(
+        SimulationState::quality_capped_at_step(&settings, &actions),
+        Ok(Some(2))
+    );
+    // Quality never reaches the cap
+    assert_eq!(
+        SimulationState::quality_capped_at_step(&settings, &actions[..2]),
+        Ok(None)
+    );
+    assert_eq!(
+        SimulationState::quality_capped_at_step(&settings, &[Action::BasicTouch, Action::Reflect]),
+        Err((1, "Reflect can only be used at synthesis begin."))
+    );
+}
+
+#[test]
+fn test_condition_schedule() {
+    let settings = Settings {
+        max_cp: 500,
+        max_durability: 60,
+        max_progress: 2000,
+        max_quality: 4000,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 100,
+        allowed_actions: ActionMask::all(),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    let actions = [
+        Action::BasicTouch,
+        Action::PreciseTouch,
+        Action::TricksOfTheTrade,
+        Action::BasicTouch,
+        Action::BasicTouch,
+    ];
+    let schedule = [
+        Condition::Normal,
+        Condition::Good,
+        Condition::Excellent,
+        Condition::Poor,
+    ];
+    let state = SimulationState::new(&settings)
+        .use_actions_with_condition_schedule(&actions, &schedule, &settings)
+        .unwrap();
+    // 100 + 247 (Good) + 0 + 65 (Poor) + 140 (Normal)
+    assert_eq!(state.quality, 552);
+    // Precise Touch cannot be used under the Normal condition
+    let error = SimulationState::new(&settings)
+        .use_actions_with_condition_schedule(&actions, &[], &settings)
+        .unwrap_err();
+    assert_eq!(
+        error,
+        (
+            1,
+            "Precise Touch can only be used when the condition is Good or Excellent."
+        )
+    );
+}
+
+#[test]
+fn test_simulate_into() {
+    let settings = Settings {
+        max_cp: 500,
+        max_durability: 60,
+        max_progress: 2000,
+        max_quality: 4000,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 100,
+        allowed_actions: ActionMask::all(),
+        adversarial: true,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    let actions = [
+        Action::Innovation,
+        Action::BasicTouch,
+        Action::PreciseTouch,
+        Action::TricksOfTheTrade,
+        Action::BasicTouch,
+        Action::CarefulSynthesis,
+    ];
+    let conditions = [
+        Condition::Normal,
+        Condition::Normal,
+        Condition::Excellent,
+        Condition::Good,
+        Condition::Poor,
+    ];
+    let steps = actions.iter().copied().zip(
+        conditions
+            .iter()
+            .copied()
+            .chain(std::iter::repeat(Condition::Normal)),
+    );
+    let expected_state = *simulate(&settings, steps).last().unwrap();
+    let mut state = SimulationState::new(&settings);
+    SimulationState::simulate_into(&mut state, &actions, &conditions, &settings).unwrap();
+    assert_eq!(state, expected_state);
+    // On error, the state before the failing action is kept
+    let mut state = SimulationState::new(&settings);
+    let error = SimulationState::simulate_into(&mut state, &actions, &[], &settings).unwrap_err();
+    assert_eq!(
+        error,
+        (
+            2,
+            "Precise Touch can only be used when the condition is Good or Excellent."
+        )
+    );
+    assert_eq!(
+        state,
+        simulate_normal(&settings, actions[..2].iter().copied())[1]
+    );
+}
+
+#[test]
+fn test_progress_target() {
+    let settings = Settings {
+        max_cp: 500,
+        max_durability: 60,
+        max_progress: 2000,
+        max_quality: 1000,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 100,
+        allowed_actions: ActionMask::all(),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: Some(300),
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    let actions = [Action::BasicSynthesis, Action::BasicSynthesis];
+    let state = SimulationState::from_macro(&settings, &actions).unwrap();
+    assert_eq!(state.progress, 240);
+    assert!(!state.is_final(&settings));
+    // the craft is complete once the target is reached, even though max Progress is much higher
+    let state = state
+        .use_action(Action::BasicSynthesis, Condition::Normal, &settings)
+        .unwrap();
+    assert_eq!(state.progress, 360);
+    assert!(state.is_final(&settings));
+    assert_eq!(state.progress_overkill(&settings), 60);
+    assert_eq!(
+        state.use_action(Action::BasicSynthesis, Condition::Normal, &settings),
+        Err("State is final")
+    );
+    // without a target, the craft continues
+    let settings = Settings {
+        progress_target: None,
+        ..settings
+    };
+    assert!(!state.is_final(&settings));
+    // a target above max Progress has no effect
+    let settings = Settings {
+        progress_target: Some(5000),
+        ..settings
+    };
+    assert_eq!(settings.completion_progress(), 2000);
+}
+
+#[test]
+fn test_legal_actions_condition() {
+    let settings = Settings {
+        max_cp: 500,
+        max_durability: 60,
+        max_progress: 2000,
+        max_quality: 40000,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 100,
+        allowed_actions: ActionMask::all(),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    let state = SimulationState::from_macro(&settings, &[Action::Reflect]).unwrap();
+    let normal_actions: Vec<_> = state.legal_actions(Condition::Normal, &settings).collect();
+    let good_actions: Vec<_> = state.legal_actions(Condition::Good, &settings).collect();
+    for action in [
+        Action::PreciseTouch,
+        Action::IntensiveSynthesis,
+        Action::TricksOfTheTrade,
+    ] {
+        assert!(!normal_actions.contains(&action));
+        assert!(good_actions.contains(&action));
+    }
+    // openers can only be used on the first step
+    assert!(!good_actions.contains(&Action::MuscleMemory));
+    assert!(good_actions.contains(&Action::BasicSynthesis));
+    let settings = Settings {
+        allowed_actions: ActionMask::all().remove(Action::PreciseTouch),
+        ..settings
+    };
+    assert!(
+        !state
+            .legal_actions(Condition::Good, &settings)
+            .any(|action| action == Action::PreciseTouch)
+    );
+}
+
+#[test]
+fn test_outcome() {
+    let settings = Settings {
+        max_cp: 500,
+        max_durability: 20,
+        max_progress: 2000,
+        max_quality: 40000,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 100,
+        allowed_actions: ActionMask::all(),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    let state = SimulationState::from_macro(&settings, &[Action::BasicSynthesis]).unwrap();
+    assert_eq!(state.outcome(&settings), CraftOutcome::InProgress);
+    // durability runs out before Progress is completed
+    let state =
+        SimulationState::from_macro(&settings, &[Action::BasicTouch, Action::BasicTouch]).unwrap();
+    assert_eq!(
+        state.outcome(&settings),
+        CraftOutcome::Failed {
+            missing_progress: 2000,
+            quality_so_far: 210,
+        }
+    );
+    let settings = Settings {
+        max_progress: 120,
+        ..settings
+    };
+    let state =
+        SimulationState::from_macro(&settings, &[Action::BasicTouch, Action::BasicSynthesis])
+            .unwrap();
+    assert_eq!(state.outcome(&settings), CraftOutcome::Completed);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_deserialize_settings_without_optional_fields() {
+    let settings = Settings {
+        max_cp: 400,
+        max_durability: 60,
+        max_progress: 2000,
+        max_quality: 2500,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 90,
+        allowed_actions: ActionMask::all(),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    // settings JSON saved before these fields were added must still load
+    let mut value = serde_json::to_value(settings).unwrap();
+    for field in [
+        "quality_increase_cap",
+        "progress_target",
+        "progress_optional",
+        "rounding_mode",
+        "cp_cost_overrides",
+    ] {
+        value.as_object_mut().unwrap().remove(field).unwrap();
+    }
+    let deserialized: Settings = serde_json::from_value(value).unwrap();
+    assert_eq!(deserialized, settings);
+}
+
+#[test]
+fn test_malformed_input() {
+    let settings = Settings {
+        max_cp: 200,
+        max_durability: 60,
+        max_progress: 2000,
+        max_quality: 2000,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 90,
+        allowed_actions: ActionMask::all(),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    assert!(SimulationState::try_new(&settings).is_ok());
+    let malformed_settings = Settings {
+        max_durability: 7,
+        ..settings
+    };
+    assert_eq!(
+        SimulationState::try_new(&malformed_settings),
+        Err("Max durability must be a multiple of 5")
+    );
+    assert!(SimulationState::from_parts(&malformed_settings, 100, 5, 0, 0, Combo::None).is_err());
+    assert_eq!(
+        SimulationState::from_parts(&settings, 201, 60, 0, 0, Combo::None),
+        Err("CP cannot exceed max CP.")
+    );
+    assert_eq!(
+        SimulationState::from_parts(&settings, 200, 65, 0, 0, Combo::None),
+        Err("Durability cannot exceed max durability.")
+    );
+
+    // Restoring CP and durability doesn't overflow, even for settings that don't pass validation
+    let settings = Settings {
+        max_cp: u16::MAX,
+        max_durability: u16::MAX,
+        ..settings
+    };
+    let state = SimulationState {
+        effects: SimulationState::new(&settings).effects.with_manipulation(2),
+        ..SimulationState::new(&settings)
+    };
+    let state = state
+        .use_action(Action::TricksOfTheTrade, Condition::Good, &settings)
+        .unwrap();
+    assert_eq!(state.cp, u16::MAX);
+    assert_eq!(state.durability, u16::MAX);
+    let state = state
+        .use_action(Action::MasterMend, Condition::Normal, &settings)
+        .unwrap();
+    assert_eq!(state.durability, u16::MAX);
+}
@@ -1,4 +1,4 @@
-use raphael_sim::{Action, ActionMask, Settings, SimulationState};
+use raphael_sim::{Action, ActionMask, CpCostOverrides, RoundingMode, Settings, SimulationState};
 use raphael_solver::{AtomicFlag, MacroSolver, SolverSettings};
 
 fn main() {
@@ -23,6 +23,11 @@ fn main() {
             .remove(Action::QuickInnovation),
         adversarial: true,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
 
     let solver_settings = SolverSettings { simulator_settings };
@@ -0,0 +1,45 @@
+use raphael_sim::Action;
+use rustc_hash::FxHashMap as HashMap;
+
+/// Tallies how often each action appears across a batch of rotations, e.g. the solutions for many recipes.
+#[derive(Debug, Clone, Default)]
+pub struct ActionFrequency {
+    counts: HashMap<Action, usize>,
+    rotations: usize,
+}
+
+impl ActionFrequency {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds every action of the rotation to the tally.
+    pub fn add_rotation(&mut self, actions: &[Action]) {
+        for action in actions {
+            *self.counts.entry(*action).or_default() += 1;
+        }
+        self.rotations += 1;
+    }
+
+    /// Number of rotations that have been added.
+    pub fn rotations(&self) -> usize {
+        self.rotations
+    }
+
+    /// Total number of times the action appears in the added rotations.
+    pub fn count(&self, action: Action) -> usize {
+        self.counts.get(&action).copied().unwrap_or(0)
+    }
+
+    /// Returns the actions that appear in the added rotations together with their counts,
+    /// sorted from the most frequent to the least frequent action. Ties are ordered by action id.
+    pub fn histogram(&self) -> Vec<(Action, usize)> {
+        let mut histogram: Vec<(Action, usize)> = self
+            .counts
+            .iter()
+            .map(|(action, count)| (*action, *count))
+            .collect();
+        histogram.sort_by_key(|(action, count)| (std::cmp::Reverse(*count), action.id()));
+        histogram
+    }
+}
@@ -2,7 +2,13 @@ use raphael_sim::*;
 
 use crate::SolverSettings;
 
+/// A sequence of actions that the solvers treat as a single atomic move.
+///
+/// All solvers (including `MacroSolver`) expand states using `ActionCombo`s via `use_action_combo`,
+/// so combo chains such as Basic Touch -> Standard Touch -> Advanced Touch are explored as one step of the search.
+/// The combo state is reset after each `ActionCombo`, which keeps the number of distinct search states small.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ActionCombo {
     TricksOfTheTrade,   // Heart and Soul + Tricks of the Trade
     IntensiveSynthesis, // Heart and Soul + Intensive Synthesis
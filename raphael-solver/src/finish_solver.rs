@@ -4,7 +4,7 @@ use rustc_hash::FxHashMap as HashMap;
 
 use crate::{
     SolverSettings,
-    actions::{PROGRESS_ONLY_SEARCH_ACTIONS, use_action_combo},
+    actions::{ActionCombo, PROGRESS_ONLY_SEARCH_ACTIONS, use_action_combo},
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -35,10 +35,43 @@ impl ReducedState {
     }
 }
 
+/// The resource that prevents Progress from being completed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FinishBlocker {
+    /// Progress can already be completed.
+    None,
+    /// Progress can be completed with `missing_cp` more CP.
+    Cp { missing_cp: u16 },
+    /// Progress can be completed with `missing_durability` more durability.
+    Durability { missing_durability: u16 },
+    /// Progress cannot be completed by adding a reasonable amount of only CP or only durability,
+    /// i.e. the Progress increase of the available actions is too low.
+    Progress,
+}
+
+/// Cost of the cheapest way to add some amount of Progress, see `FinishSolver::solve_min_finish_cost`.
+#[derive(Debug, Clone, Copy)]
+struct FinishCost {
+    steps: u8,
+    // CP left after adding the Progress
+    remaining_cp: u16,
+    first_action: ActionCombo,
+}
+
+impl FinishCost {
+    fn is_better_than(&self, other: &Self) -> bool {
+        (self.steps, std::cmp::Reverse(self.remaining_cp))
+            < (other.steps, std::cmp::Reverse(other.remaining_cp))
+    }
+}
+
+/// Solver for the maximum Progress that can be reached from a state without regard for Quality.
 pub struct FinishSolver {
     settings: SolverSettings,
     // maximum attainable progress for each state
     max_progress: HashMap<ReducedState, u32>,
+    // cheapest way to add the given amount of progress for each state
+    min_finish_cost: HashMap<(ReducedState, u32), Option<FinishCost>>,
 }
 
 impl FinishSolver {
@@ -46,14 +79,156 @@ impl FinishSolver {
         Self {
             settings,
             max_progress: HashMap::default(),
+            min_finish_cost: HashMap::default(),
         }
     }
 
+    /// Checks whether Progress can be completed from the given state.
     pub fn can_finish(&mut self, state: &SimulationState) -> bool {
+        if self.settings.simulator_settings.progress_optional {
+            return true;
+        }
         let max_progress = self.solve_max_progress(ReducedState::from_state(state));
         state.progress + max_progress >= self.settings.max_progress()
     }
 
+    /// Returns the resource that keeps the given state from completing Progress.
+    ///
+    /// CP is checked before durability, so if either additional CP or additional durability would be enough,
+    /// the missing CP is reported.
+    pub fn finish_blocker(&mut self, state: &SimulationState) -> FinishBlocker {
+        const MAX_ADDITIONAL_CP: u16 = 1000;
+        const MAX_ADDITIONAL_DURABILITY: u16 = 200;
+        if self.can_finish(state) {
+            return FinishBlocker::None;
+        }
+        let can_finish_with_cp = |solver: &mut Self, additional_cp: u16| {
+            solver.can_finish(&SimulationState {
+                cp: state.cp.saturating_add(additional_cp),
+                ..*state
+            })
+        };
+        if let Some(missing_cp) =
+            self.min_sufficient_value(1, MAX_ADDITIONAL_CP, can_finish_with_cp)
+        {
+            return FinishBlocker::Cp { missing_cp };
+        }
+        // durability is consumed in multiples of 5
+        let can_finish_with_durability = |solver: &mut Self, additional_durability: u16| {
+            solver.can_finish(&SimulationState {
+                durability: state.durability.saturating_add(additional_durability * 5),
+                ..*state
+            })
+        };
+        if let Some(missing_durability) =
+            self.min_sufficient_value(1, MAX_ADDITIONAL_DURABILITY / 5, can_finish_with_durability)
+        {
+            return FinishBlocker::Durability {
+                missing_durability: missing_durability * 5,
+            };
+        }
+        FinishBlocker::Progress
+    }
+
+    /// Binary search for the smallest value in `low..=high` that satisfies the monotonic predicate.
+    fn min_sufficient_value(
+        &mut self,
+        mut low: u16,
+        mut high: u16,
+        mut predicate: impl FnMut(&mut Self, u16) -> bool,
+    ) -> Option<u16> {
+        if !predicate(self, high) {
+            return None;
+        }
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if predicate(self, mid) {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+        Some(high)
+    }
+
+    /// Returns a sequence of actions that brings the Progress of the given state to `max_progress`,
+    /// or `None` if Progress cannot be completed from the given state.
+    ///
+    /// Only actions that don't increase Quality are considered. The sequence uses as few actions as possible,
+    /// e.g. a single Groundwork instead of two Basic Synthesis if durability allows, and leaves as much CP as possible among those.
+    pub fn finish_sequence(&mut self, state: &SimulationState) -> Option<Vec<Action>> {
+        if !self.can_finish(state) {
+            return None;
+        }
+        let mut actions = Vec::new();
+        let mut state = *state;
+        while state.progress < self.settings.max_progress() {
+            let remaining_progress = self.settings.max_progress() - state.progress;
+            let finish_cost =
+                self.solve_min_finish_cost(ReducedState::from_state(&state), remaining_progress)?;
+            let new_state = use_action_combo(
+                &self.settings,
+                ReducedState::from_state(&state).to_state(),
+                finish_cost.first_action,
+            )
+            .ok()?;
+            actions.extend_from_slice(finish_cost.first_action.actions());
+            state = SimulationState {
+                progress: state.progress + new_state.progress,
+                ..new_state
+            };
+        }
+        Some(actions)
+    }
+
+    /// Returns the cheapest way to add `required_progress` Progress from the given state,
+    /// or `None` if that much Progress cannot be added.
+    fn solve_min_finish_cost(
+        &mut self,
+        state: ReducedState,
+        required_progress: u32,
+    ) -> Option<FinishCost> {
+        if let Some(finish_cost) = self.min_finish_cost.get(&(state, required_progress)) {
+            return *finish_cost;
+        }
+        let mut best: Option<FinishCost> = None;
+        for action in PROGRESS_ONLY_SEARCH_ACTIONS {
+            let Ok(new_state) = use_action_combo(&self.settings, state.to_state(), *action) else {
+                continue;
+            };
+            let finish_cost = if new_state.progress >= required_progress {
+                FinishCost {
+                    steps: action.steps(),
+                    remaining_cp: new_state.cp,
+                    first_action: *action,
+                }
+            } else if new_state.is_final(&self.settings.simulator_settings) {
+                continue;
+            } else {
+                let remaining_progress = required_progress - new_state.progress;
+                let new_state = ReducedState::from_state(&new_state);
+                if self.solve_max_progress(new_state) < remaining_progress {
+                    continue;
+                }
+                let Some(child_cost) = self.solve_min_finish_cost(new_state, remaining_progress)
+                else {
+                    continue;
+                };
+                FinishCost {
+                    steps: action.steps() + child_cost.steps,
+                    remaining_cp: child_cost.remaining_cp,
+                    first_action: *action,
+                }
+            };
+            if best.is_none_or(|best| finish_cost.is_better_than(&best)) {
+                best = Some(finish_cost);
+            }
+        }
+        self.min_finish_cost
+            .insert((state, required_progress), best);
+        best
+    }
+
     fn solve_max_progress(&mut self, state: ReducedState) -> u32 {
         match self.max_progress.get(&state) {
             Some(max_progress) => *max_progress,
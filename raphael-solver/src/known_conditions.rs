@@ -0,0 +1,78 @@
+use raphael_sim::{Action, Condition, CraftOutcome, Settings, SimulationState};
+use rustc_hash::FxHashSet as HashSet;
+
+use crate::{SolverException, SolverSettings};
+
+struct Node {
+    action: Action,
+    parent_index: Option<usize>,
+}
+
+/// Finds the optimal rotation for a craft in which the condition of every step is known in advance, e.g. from a recorded craft.
+/// The `i`-th action of the rotation is used under `conditions[i]`, so the rotation has at most `conditions.len()` actions.
+///
+/// Returns the rotation that reaches the most Quality (capped at the Quality target), preferring fewer steps among rotations with equal Quality.
+/// The `adversarial` setting is ignored because the conditions are known.
+///
+/// All states that are reachable under the given conditions are enumerated, so this is only tractable for short condition sequences.
+pub fn solve_known_conditions(
+    settings: SolverSettings,
+    conditions: &[Condition],
+) -> Result<Vec<Action>, SolverException> {
+    let simulator_settings = Settings {
+        adversarial: false,
+        ..settings.simulator_settings
+    };
+    let mut nodes: Vec<Node> = Vec::new();
+    let mut layer = vec![(SimulationState::new(&simulator_settings), None)];
+    // capped Quality and node index of the best completed state
+    let mut best: Option<(u32, usize)> = None;
+    for condition in conditions {
+        // States are only equivalent if they are reached after the same number of steps,
+        // because the remaining conditions depend on the step.
+        let mut visited: HashSet<SimulationState> = HashSet::default();
+        let mut next_layer = Vec::new();
+        for (state, parent_index) in &layer {
+            for action in simulator_settings.allowed_actions.actions_iter() {
+                let Ok(mut state) = state.use_action(action, *condition, &simulator_settings)
+                else {
+                    continue;
+                };
+                // Quality beyond the Quality target doesn't matter
+                state.quality = std::cmp::min(state.quality, settings.max_quality());
+                if !visited.insert(state) {
+                    continue;
+                }
+                nodes.push(Node {
+                    action,
+                    parent_index: *parent_index,
+                });
+                let node_index = nodes.len() - 1;
+                match state.outcome(&simulator_settings) {
+                    CraftOutcome::Completed => {
+                        // earlier layers have fewer steps, so ties keep the shorter rotation
+                        if best.is_none_or(|(quality, _)| state.quality > quality) {
+                            best = Some((state.quality, node_index));
+                        }
+                    }
+                    CraftOutcome::InProgress => next_layer.push((state, Some(node_index))),
+                    CraftOutcome::Failed { .. } => (),
+                }
+            }
+        }
+        layer = next_layer;
+    }
+
+    let (_, mut node_index) = best.ok_or(SolverException::NoSolution)?;
+    let mut actions = Vec::new();
+    loop {
+        let node = &nodes[node_index];
+        actions.push(node.action);
+        match node.parent_index {
+            Some(parent_index) => node_index = parent_index,
+            None => break,
+        }
+    }
+    actions.reverse();
+    Ok(actions)
+}
@@ -1,16 +1,42 @@
 mod actions;
 
 mod finish_solver;
-use finish_solver::FinishSolver;
+pub use finish_solver::{FinishBlocker, FinishSolver};
 
 mod quality_upper_bound_solver;
 use quality_upper_bound_solver::QualityUbSolver;
+pub use quality_upper_bound_solver::{estimated_precompute_memory_bytes, max_possible_quality};
 
 mod step_lower_bound_solver;
 use step_lower_bound_solver::StepLbSolver;
 
 mod macro_solver;
-pub use macro_solver::MacroSolver;
+pub use macro_solver::{
+    ActionOrdering, MacroSolver, MacroSolverStats, SolveResult, SolverCheckpoint, StepExplanation,
+};
+
+mod rotation_filter;
+pub use rotation_filter::pareto_optimal_rotations;
+
+mod rotation_canonicalizer;
+pub use rotation_canonicalizer::canonicalize_rotation;
+
+mod quality_comparison;
+pub use quality_comparison::{
+    backloaded_and_unconstrained_quality, guaranteed_and_best_case_quality,
+};
+
+mod quality_histogram;
+pub use quality_histogram::terminal_quality_histogram;
+
+mod known_conditions;
+pub use known_conditions::solve_known_conditions;
+
+mod action_frequency;
+pub use action_frequency::ActionFrequency;
+
+mod stat_search;
+pub use stat_search::{min_control_for_target, solve_collectable_tiers};
 
 mod utils;
 pub use utils::AtomicFlag;
@@ -25,37 +51,107 @@ pub enum SolverException {
     AllocError,
 }
 
+/// Settings of the solvers.
+///
+/// Prefer the accessor methods over reading `simulator_settings` directly.
 #[derive(Clone, Copy, Debug)]
 pub struct SolverSettings {
     pub simulator_settings: raphael_sim::Settings,
 }
 
 impl SolverSettings {
+    /// Returns a builder for settings of the given craft.
+    /// Options that are not set on the builder use their default values.
+    pub fn builder(simulator_settings: raphael_sim::Settings) -> SolverSettingsBuilder {
+        SolverSettingsBuilder { simulator_settings }
+    }
+
+    /// Maximum durability of the craft.
     pub fn max_durability(&self) -> u16 {
         self.simulator_settings.max_durability
     }
 
+    /// Maximum CP of the crafter.
     pub fn max_cp(&self) -> u16 {
         self.simulator_settings.max_cp
     }
 
+    /// Progress required to complete the craft.
     pub fn max_progress(&self) -> u32 {
-        #[allow(clippy::useless_conversion)]
-        u32::from(self.simulator_settings.max_progress)
+        self.simulator_settings.completion_progress()
     }
 
+    /// Quality target of the craft. The solver does not try to reach Quality beyond this value.
     pub fn max_quality(&self) -> u32 {
         #[allow(clippy::useless_conversion)]
         u32::from(self.simulator_settings.max_quality)
     }
 
+    /// Progress that must be reached from `progress` for the craft to count as complete.
+    pub fn required_progress(&self, progress: u32) -> u32 {
+        if self.simulator_settings.progress_optional {
+            0
+        } else {
+            self.max_progress().saturating_sub(progress)
+        }
+    }
+
+    /// Progress increase of a 100% efficiency action without any modifiers.
     pub fn base_progress(&self) -> u32 {
         #[allow(clippy::useless_conversion)]
         u32::from(self.simulator_settings.base_progress)
     }
 
+    /// Quality increase of a 100% efficiency action without any modifiers.
     pub fn base_quality(&self) -> u32 {
         #[allow(clippy::useless_conversion)]
         u32::from(self.simulator_settings.base_quality)
     }
+
+    /// Returns a key that captures all settings that affect the precomputed Quality upper bounds.
+    ///
+    /// The key ignores the Quality target, because the upper bounds of a precompute can be reused for any lower
+    /// Quality target. Settings with the same key can therefore share the precompute of the setting with the highest Quality target.
+    pub fn precompute_key(&self) -> PrecomputeKey {
+        PrecomputeKey {
+            simulator_settings: raphael_sim::Settings {
+                max_quality: 0,
+                ..self.simulator_settings
+            },
+        }
+    }
+
+    /// Checks whether Progress cannot be completed from the start of the craft.
+    /// Only Progress-increasing actions are explored, which is much cheaper than the Quality precompute of a full solve.
+    pub fn is_trivially_infeasible(&self) -> bool {
+        let initial_state = raphael_sim::SimulationState::new(&self.simulator_settings);
+        !FinishSolver::new(*self).can_finish(&initial_state)
+    }
+}
+
+/// Cache key for the precomputed Quality upper bounds, see `SolverSettings::precompute_key`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PrecomputeKey {
+    simulator_settings: raphael_sim::Settings,
+}
+
+/// Builder for `SolverSettings`. Created with `SolverSettings::builder`.
+#[derive(Clone, Copy, Debug)]
+pub struct SolverSettingsBuilder {
+    simulator_settings: raphael_sim::Settings,
+}
+
+impl SolverSettingsBuilder {
+    /// Replaces the settings of the craft.
+    #[must_use]
+    pub fn simulator_settings(mut self, simulator_settings: raphael_sim::Settings) -> Self {
+        self.simulator_settings = simulator_settings;
+        self
+    }
+
+    pub fn build(self) -> SolverSettings {
+        SolverSettings {
+            simulator_settings: self.simulator_settings,
+        }
+    }
 }
@@ -0,0 +1,12 @@
+use raphael_sim::{Action, SimulationState};
+
+/// Decides in which order `MacroSolver` expands the actions of a search node.
+///
+/// The order doesn't affect the Quality, steps and duration of the returned rotation, but a good order finds
+/// strong rotations sooner, which improves pruning. Without an ordering, actions are expanded in a fixed default order.
+pub trait ActionOrdering {
+    /// Actions are expanded in ascending order of their key. Actions with equal keys keep the default order.
+    ///
+    /// `actions` contains more than one action if the solver uses a fixed sequence of actions as a single step (e.g. a touch combo).
+    fn sort_key(&self, state: &SimulationState, actions: &[Action]) -> i64;
+}
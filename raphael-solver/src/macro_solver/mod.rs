@@ -1,6 +1,8 @@
+mod action_ordering;
 mod fast_lower_bound;
 mod pareto_front;
 mod search_queue;
 mod solver;
 
-pub use solver::MacroSolver;
+pub use action_ordering::ActionOrdering;
+pub use solver::{MacroSolver, MacroSolverStats, SolveResult, SolverCheckpoint, StepExplanation};
@@ -15,6 +15,7 @@ const EFFECTS_MASK: u32 = Effects::new()
     .into_bits();
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct Key {
     progress: u32,
     quality_div: u16,
@@ -36,6 +37,7 @@ impl From<&SimulationState> for Key {
 }
 
 #[derive(Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct Value {
     cp: u16,
     durability: u16,
@@ -90,7 +92,9 @@ impl Value {
 }
 
 #[derive(Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct ParetoFront {
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::map_as_seq"))]
     buckets: FxHashMap<Key, Vec<Value>>,
 }
 
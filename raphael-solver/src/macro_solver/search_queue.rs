@@ -7,8 +7,10 @@ use crate::{actions::ActionCombo, utils::Backtracking};
 use super::pareto_front::ParetoFront;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SearchScore {
     pub quality_upper_bound: u32,
+    pub penalty: u8,
     pub steps_lower_bound: u8,
     pub duration_lower_bound: u8,
     pub current_steps: u8,
@@ -18,6 +20,7 @@ pub struct SearchScore {
 impl SearchScore {
     pub const MIN: Self = Self {
         quality_upper_bound: 0,
+        penalty: u8::MAX,
         steps_lower_bound: u8::MAX,
         duration_lower_bound: u8::MAX,
         current_steps: u8::MAX,
@@ -26,6 +29,7 @@ impl SearchScore {
 
     pub const MAX: Self = Self {
         quality_upper_bound: u32::MAX,
+        penalty: 0,
         steps_lower_bound: 0,
         duration_lower_bound: 0,
         current_steps: 0,
@@ -43,6 +47,7 @@ impl std::cmp::Ord for SearchScore {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         self.quality_upper_bound
             .cmp(&other.quality_upper_bound)
+            .then(other.penalty.cmp(&self.penalty))
             .then(other.steps_lower_bound.cmp(&self.steps_lower_bound))
             .then(other.duration_lower_bound.cmp(&self.duration_lower_bound))
             .then(other.current_steps.cmp(&self.current_steps))
@@ -51,6 +56,7 @@ impl std::cmp::Ord for SearchScore {
 }
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct SearchNode {
     state: SimulationState,
     action: ActionCombo,
@@ -58,14 +64,17 @@ struct SearchNode {
 }
 
 #[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SearchQueueStats {
     pub processed_nodes: usize,
     pub dropped_nodes: usize,
     pub pareto_buckets_squared_size_sum: usize,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct SearchQueue {
     pareto_front: ParetoFront,
+    #[cfg_attr(feature = "serde", serde(with = "crate::utils::map_as_seq"))]
     buckets: BTreeMap<SearchScore, Vec<SearchNode>>,
     backtracking: Backtracking<ActionCombo>,
     current_score: SearchScore,
@@ -150,10 +159,20 @@ impl SearchQueue {
         Some((state, self.current_score, backtrack_id))
     }
 
+    /// Puts a node returned by `pop` back into the queue, e.g. if its expansion was interrupted.
+    pub fn restore(&mut self, state: SimulationState, backtrack_id: usize) {
+        self.current_nodes.push((state, backtrack_id));
+    }
+
     pub fn backtrack(&self, backtrack_id: usize) -> impl Iterator<Item = ActionCombo> {
         self.backtracking.get_items(backtrack_id)
     }
 
+    /// Returns the action that led to the node, or `None` for the initial node.
+    pub fn last_action(&self, backtrack_id: usize) -> Option<ActionCombo> {
+        self.backtracking.get(backtrack_id)
+    }
+
     pub fn runtime_stats(&self) -> SearchQueueStats {
         SearchQueueStats {
             processed_nodes: self.processed_nodes,
@@ -1,20 +1,23 @@
 use raphael_sim::*;
 
+use super::action_ordering::ActionOrdering;
 use super::search_queue::{SearchQueueStats, SearchScore};
 use crate::actions::{
     ActionCombo, FULL_SEARCH_ACTIONS, PROGRESS_ONLY_SEARCH_ACTIONS, use_action_combo,
 };
 use crate::macro_solver::fast_lower_bound::fast_lower_bound;
 use crate::macro_solver::search_queue::SearchQueue;
-use crate::quality_upper_bound_solver::QualityUbSolverStats;
+use crate::quality_upper_bound_solver::{QualityUbSolverStats, quality_upper_bound_with_combo};
 use crate::step_lower_bound_solver::StepLbSolverStats;
 use crate::utils::AtomicFlag;
 use crate::utils::ScopedTimer;
 use crate::{FinishSolver, QualityUbSolver, SolverException, SolverSettings, StepLbSolver};
 
+use rustc_hash::FxHashSet;
 use std::vec::Vec;
 
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct Solution {
     score: (SearchScore, u32),
     solver_actions: Vec<ActionCombo>,
@@ -32,8 +35,10 @@ impl Solution {
 
 type SolutionCallback<'a> = dyn Fn(&[Action]) + 'a;
 type ProgressCallback<'a> = dyn Fn(usize) + 'a;
+type InitialQualitySolution = (u32, Option<Vec<Action>>);
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MacroSolverStats {
     pub finish_states: usize,
     pub search_queue_stats: SearchQueueStats,
@@ -41,6 +46,49 @@ pub struct MacroSolverStats {
     pub step_lb_stats: StepLbSolverStats,
 }
 
+/// A rotation found by `MacroSolver` together with its final state and the solver statistics.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SolveResult {
+    pub actions: Vec<Action>,
+    pub progress: u32,
+    pub quality: u32,
+    pub remaining_cp: u16,
+    pub remaining_durability: u16,
+    pub stats: MacroSolverStats,
+    /// Number of distinct rotations (up to the order of their actions) that reach the same Quality in the same number
+    /// of steps as `actions`. Only counted if enabled with `MacroSolver::set_count_optimal_solutions`.
+    pub optimal_solution_count: Option<usize>,
+    /// Cheapest action that adds Quality and still fits into the leftover CP and durability of `actions`,
+    /// see `MacroSolver::cheapest_extra_quality_action`.
+    pub extra_quality_action: Option<Action>,
+}
+
+/// Search state of a solve that was interrupted during the search, see `MacroSolver::take_checkpoint`.
+///
+/// With the `serde` feature enabled, a checkpoint can be stored and the solve resumed later, e.g. by another process.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SolverCheckpoint {
+    simulator_settings: Settings,
+    initial_state: SimulationState,
+    search_queue: SearchQueue,
+    solution: Option<Solution>,
+    popped: usize,
+}
+
+/// Quality upper bounds of one step of a rotation, see `MacroSolver::explain_rotation`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StepExplanation {
+    /// Actions of the step. Combos such as Basic Touch > Standard Touch are a single step of the solver.
+    pub chosen: Vec<Action>,
+    /// Upper bound on the Quality that can be reached after the chosen step.
+    pub chosen_bound: u32,
+    /// Highest upper bound on the Quality that can be reached after any other step instead of the chosen step.
+    /// `None` if no other step is possible without making it impossible to complete Progress.
+    pub best_alternative_bound: Option<u32>,
+}
+
 pub struct MacroSolver<'a> {
     settings: SolverSettings,
     solution_callback: Box<SolutionCallback<'a>>,
@@ -50,6 +98,23 @@ pub struct MacroSolver<'a> {
     step_lb_solver: StepLbSolver,
     search_queue_stats: SearchQueueStats, // stats of last solve
     interrupt_signal: AtomicFlag,
+    // actions that count towards the penalty of a solution
+    penalized_actions: ActionMask,
+    action_ordering: Option<Box<dyn ActionOrdering + 'a>>,
+    // actions that every solution must end with
+    locked_suffix: Vec<Action>,
+    // action that must be the last Quality action of every solution
+    quality_finisher: Option<Action>,
+    // rank solutions by duration instead of step count
+    minimize_duration: bool,
+    // rotation that seeds the best solution of the search, if it is a valid solution
+    warm_start_hint: Vec<Action>,
+    // count all optimal rotations in `solve_with_result`
+    count_optimal_solutions: bool,
+    // penalize Quality beyond the max Quality and prefer less Quality among otherwise equal solutions
+    minimize_quality_overshoot: bool,
+    // search state of the last solve if it was interrupted during the search
+    checkpoint: Option<SolverCheckpoint>,
 }
 
 impl<'a> MacroSolver<'a> {
@@ -68,10 +133,72 @@ impl<'a> MacroSolver<'a> {
             step_lb_solver: StepLbSolver::new(settings, interrupt_signal.clone()),
             search_queue_stats: SearchQueueStats::default(),
             interrupt_signal,
+            penalized_actions: ActionMask::none(),
+            action_ordering: None,
+            locked_suffix: Vec::new(),
+            quality_finisher: None,
+            minimize_duration: false,
+            warm_start_hint: Vec::new(),
+            count_optimal_solutions: false,
+            minimize_quality_overshoot: false,
+            checkpoint: None,
         }
     }
 
+    /// Expands the actions of each search node in the order given by `action_ordering` instead of the default order.
+    pub fn set_action_ordering(&mut self, action_ordering: Box<dyn ActionOrdering + 'a>) {
+        self.action_ordering = Some(action_ordering);
+    }
+
+    /// Removes all actions that are not in `allowed_actions` from the allowed actions of the solver.
+    ///
+    /// The quality upper bounds computed by previous solves are reused, which avoids a full precompute
+    /// at the cost of slightly looser bounds.
+    pub fn restrict_actions(&mut self, allowed_actions: ActionMask) {
+        let simulator_settings = &mut self.settings.simulator_settings;
+        simulator_settings.allowed_actions = simulator_settings
+            .allowed_actions
+            .intersection(allowed_actions);
+        self.quality_ub_solver.restrict_actions(allowed_actions);
+        self.finish_solver = FinishSolver::new(self.settings);
+        self.step_lb_solver = StepLbSolver::new(self.settings, self.interrupt_signal.clone());
+    }
+
+    /// Enables counting the optimal rotations in `solve_with_result`, see `SolveResult::optimal_solution_count`.
+    ///
+    /// Counting enumerates all optimal rotations and can take much longer than the solve itself.
+    pub fn set_count_optimal_solutions(&mut self, enabled: bool) {
+        self.count_optimal_solutions = enabled;
+    }
+
     pub fn solve(&mut self) -> Result<Vec<Action>, SolverException> {
+        self.solve_continuation(&[])
+    }
+
+    /// Returns an upper bound on the Quality that can be reached from `state` without searching for a rotation.
+    /// This is much faster than a full solve and can be shown as an estimate while the solve is running.
+    pub fn quick_estimate(&mut self, state: SimulationState) -> Result<u32, SolverException> {
+        quality_upper_bound_with_combo(&mut self.quality_ub_solver, &self.settings, state)
+    }
+
+    /// Finds the optimal actions to continue a craft in which `initial_actions` have already been used.
+    /// The returned actions don't include `initial_actions`.
+    pub fn solve_continuation(
+        &mut self,
+        initial_actions: &[Action],
+    ) -> Result<Vec<Action>, SolverException> {
+        let initial_state =
+            SimulationState::from_macro(&self.settings.simulator_settings, initial_actions)
+                .map_err(|_| SolverException::NoSolution)?;
+        self.solve_from_state(initial_state)
+    }
+
+    /// Finds the optimal actions to continue a craft from `state`, e.g. a state with buffs that are already active
+    /// (see `SimulationState::from_parts`).
+    pub fn solve_from_state(
+        &mut self,
+        state: SimulationState,
+    ) -> Result<Vec<Action>, SolverException> {
         log::debug!(
             "rayon::current_num_threads() = {}",
             rayon::current_num_threads()
@@ -79,7 +206,15 @@ impl<'a> MacroSolver<'a> {
 
         let _total_time = ScopedTimer::new("Total Time");
 
-        let mut initial_state = SimulationState::new(&self.settings.simulator_settings);
+        self.settings
+            .simulator_settings
+            .validate()
+            .map_err(|err| SolverException::InternalError(err.to_owned()))?;
+
+        let mut initial_state = state;
+        if initial_state.progress >= self.settings.max_progress() {
+            return Ok(Vec::new());
+        }
         if initial_state.quality >= self.settings.max_quality() {
             initial_state.effects = initial_state.effects.strip_quality_effects();
         }
@@ -90,6 +225,37 @@ impl<'a> MacroSolver<'a> {
         }
         drop(timer);
 
+        self.precompute(initial_state);
+
+        let _timer = ScopedTimer::new("Search");
+        Ok(self.do_solve(initial_state)?.actions())
+    }
+
+    /// Takes the search state of the last solve if it was interrupted during the search.
+    /// Interrupts before the search starts (e.g. during the precompute) don't leave a checkpoint.
+    pub fn take_checkpoint(&mut self) -> Option<SolverCheckpoint> {
+        self.checkpoint.take()
+    }
+
+    /// Continues an interrupted solve from `checkpoint` and returns the same rotation as the uninterrupted solve.
+    /// The interrupt signal must be cleared before resuming.
+    ///
+    /// The solver must use the same settings and options as the solver that created the checkpoint.
+    pub fn resume(&mut self, checkpoint: SolverCheckpoint) -> Result<Vec<Action>, SolverException> {
+        if checkpoint.simulator_settings != self.settings.simulator_settings {
+            return Err(SolverException::InternalError(
+                "Checkpoint was created with different settings".to_owned(),
+            ));
+        }
+
+        let _total_time = ScopedTimer::new("Total Time");
+        self.precompute(checkpoint.initial_state);
+
+        let _timer = ScopedTimer::new("Search");
+        Ok(self.search(checkpoint)?.actions())
+    }
+
+    fn precompute(&mut self, initial_state: SimulationState) {
         _ = rayon::join(
             || {
                 let _timer = ScopedTimer::new("Quality UB Solver");
@@ -102,20 +268,461 @@ impl<'a> MacroSolver<'a> {
                 self.step_lb_solver.step_lower_bound(seed_state, 0)
             },
         );
+    }
 
-        let _timer = ScopedTimer::new("Search");
-        Ok(self.do_solve(initial_state)?.actions())
+    /// Finds the optimal rotation among all rotations that end with `suffix`, e.g. a fixed finisher.
+    /// The returned actions include `suffix`.
+    ///
+    /// The suffix is used without the combo of the action before it, and Quality actions of the suffix
+    /// cannot be used if the Quality target is already reached before the suffix.
+    pub fn solve_with_suffix(&mut self, suffix: &[Action]) -> Result<Vec<Action>, SolverException> {
+        self.locked_suffix = suffix.to_vec();
+        let result = self.solve();
+        self.locked_suffix.clear();
+        result
+    }
+
+    /// Finds the optimal rotation among all rotations whose last Quality action is `finisher`, e.g. Byregot's Blessing.
+    /// Unlike `solve_with_suffix`, the finisher can be followed by any actions that don't increase Quality.
+    ///
+    /// After the finisher, Progress is completed with as few steps as possible.
+    pub fn solve_with_quality_finisher(
+        &mut self,
+        finisher: Action,
+    ) -> Result<Vec<Action>, SolverException> {
+        self.quality_finisher = Some(finisher);
+        let result = self.solve();
+        self.quality_finisher = None;
+        result
+    }
+
+    /// Same as `solve`, but uses `hint` (e.g. the solution of a previous solve with slightly different settings)
+    /// as the initial best solution, so that the search can prune all rotations that are not better than `hint` right away.
+    ///
+    /// A hint that is not a valid solution under the current settings is ignored.
+    pub fn solve_warm(&mut self, hint: &[Action]) -> Result<Vec<Action>, SolverException> {
+        self.warm_start_hint = hint.to_vec();
+        let result = self.solve();
+        self.warm_start_hint.clear();
+        result
+    }
+
+    /// Solves in two passes: a fast first pass with Quality quantized in steps of `quantization`,
+    /// followed by a full-precision pass that is warm-started (see `solve_warm`) with the rotation of the first pass.
+    ///
+    /// The rotation of the first pass is reported through the solution callback, which gives a near-optimal rotation
+    /// early. The returned rotation is as good as the one returned by `solve`.
+    /// A `quantization` of 0 or 1 doesn't coarsen the Quality, so only a single full-precision pass is done.
+    pub fn solve_two_pass(&mut self, quantization: u16) -> Result<Vec<Action>, SolverException> {
+        if quantization <= 1 {
+            return self.solve();
+        }
+        let quantize = |value: u16| std::cmp::max(1, value / quantization);
+        let mut coarse_settings = self.settings;
+        let simulator_settings = &mut coarse_settings.simulator_settings;
+        simulator_settings.base_quality = quantize(simulator_settings.base_quality);
+        simulator_settings.max_quality = quantize(simulator_settings.max_quality);
+        simulator_settings.quality_increase_cap =
+            simulator_settings.quality_increase_cap.map(quantize);
+        let mut coarse_solver = MacroSolver::new(
+            coarse_settings,
+            Box::new(|actions| (self.solution_callback)(actions)),
+            Box::new(|progress| (self.progress_callback)(progress)),
+            self.interrupt_signal.clone(),
+        );
+        let hint = match coarse_solver.solve() {
+            Ok(actions) => actions,
+            Err(SolverException::NoSolution) => Vec::new(),
+            Err(exception) => return Err(exception),
+        };
+        drop(coarse_solver);
+        self.solve_warm(&hint)
+    }
+
+    /// Solves once for each initial Quality (e.g. from different amounts of HQ ingredients) and returns the rotations
+    /// in the same order as `initial_qualities`. `None` means that Progress cannot be completed.
+    ///
+    /// The Quality upper bounds are precomputed only once and are shared between all solves.
+    pub fn solve_for_initial_qualities(
+        &mut self,
+        initial_qualities: &[u32],
+    ) -> Result<Vec<InitialQualitySolution>, SolverException> {
+        let max_quality = self.settings.simulator_settings.max_quality;
+        // Precompute with the original max Quality, which is an upper bound for all other solves.
+        self.quality_ub_solver.precompute();
+        let mut results = Vec::with_capacity(initial_qualities.len());
+        let mut exception = None;
+        for &initial_quality in initial_qualities {
+            let remaining_quality = u32::from(max_quality).saturating_sub(initial_quality) as u16;
+            self.settings.simulator_settings.max_quality = remaining_quality;
+            self.quality_ub_solver.set_max_quality(remaining_quality);
+            self.step_lb_solver = StepLbSolver::new(self.settings, self.interrupt_signal.clone());
+            match self.solve() {
+                Ok(actions) => results.push((initial_quality, Some(actions))),
+                Err(SolverException::NoSolution) => results.push((initial_quality, None)),
+                Err(err) => {
+                    exception = Some(err);
+                    break;
+                }
+            }
+        }
+        self.settings.simulator_settings.max_quality = max_quality;
+        self.quality_ub_solver.set_max_quality(max_quality);
+        self.step_lb_solver = StepLbSolver::new(self.settings, self.interrupt_signal.clone());
+        exception.map_or(Ok(results), Err)
+    }
+
+    /// Same as `solve`, but also returns the final state of the rotation and the runtime statistics of the solve.
+    pub fn solve_with_result(&mut self) -> Result<SolveResult, SolverException> {
+        let actions = self.solve()?;
+        let state = SimulationState::from_macro(&self.settings.simulator_settings, &actions)
+            .map_err(|(_, err)| SolverException::InternalError(err.to_owned()))?;
+        let stats = self.runtime_stats();
+        let optimal_solution_count = match self.count_optimal_solutions {
+            true => Some(self.count_optimal_rotations(&actions)?),
+            false => None,
+        };
+        let extra_quality_action = self.cheapest_extra_quality_action(&actions);
+        Ok(SolveResult {
+            actions,
+            progress: state.progress,
+            quality: state.quality,
+            remaining_cp: state.cp,
+            remaining_durability: state.durability,
+            stats,
+            optimal_solution_count,
+            extra_quality_action,
+        })
+    }
+
+    /// Returns the action with the lowest CP cost that can be inserted anywhere into `actions` such that the rotation
+    /// still completes the craft with more (capped) Quality, or `None` if the leftover CP and durability don't allow it.
+    pub fn cheapest_extra_quality_action(&self, actions: &[Action]) -> Option<Action> {
+        let simulator_settings = &self.settings.simulator_settings;
+        let capped_quality =
+            |state: SimulationState| std::cmp::min(state.quality, self.settings.max_quality());
+        let final_state = SimulationState::from_macro(simulator_settings, actions).ok()?;
+        let mut best: Option<(i32, Action)> = None;
+        for extra_action in simulator_settings.allowed_actions.actions_iter() {
+            for index in 0..=actions.len() {
+                let mut rotation = actions.to_vec();
+                rotation.insert(index, extra_action);
+                let Ok(state) = SimulationState::from_macro(simulator_settings, &rotation) else {
+                    continue;
+                };
+                let completed = state.progress >= self.settings.max_progress()
+                    || simulator_settings.progress_optional;
+                if !completed || capped_quality(state) <= capped_quality(final_state) {
+                    continue;
+                }
+                // Can be negative if the extra action starts a combo that makes a later action cheaper
+                let cp_cost = i32::from(final_state.cp) - i32::from(state.cp);
+                if best.is_none_or(|(best_cp_cost, _)| cp_cost < best_cp_cost) {
+                    best = Some((cp_cost, extra_action));
+                }
+            }
+        }
+        best.map(|(_, action)| action)
+    }
+
+    /// Counts the rotations that reach the same capped Quality as `solution` in at most as many steps.
+    /// Rotations that only differ in the order of their actions are counted once.
+    fn count_optimal_rotations(&mut self, solution: &[Action]) -> Result<usize, SolverException> {
+        let simulator_settings = &self.settings.simulator_settings;
+        let final_state = SimulationState::from_macro(simulator_settings, solution)
+            .map_err(|(_, err)| SolverException::InternalError(err.to_owned()))?;
+        let target_quality = std::cmp::min(final_state.quality, self.settings.max_quality());
+        let max_steps = solution.len() as u8;
+        let mut rotations = FxHashSet::default();
+        self.collect_optimal_rotations(
+            SimulationState::new(simulator_settings),
+            target_quality,
+            max_steps,
+            &mut Vec::new(),
+            &mut rotations,
+        )?;
+        Ok(rotations.len())
+    }
+
+    fn collect_optimal_rotations(
+        &mut self,
+        state: SimulationState,
+        target_quality: u32,
+        max_steps: u8,
+        actions: &mut Vec<Action>,
+        rotations: &mut FxHashSet<Vec<Action>>,
+    ) -> Result<(), SolverException> {
+        if self.interrupt_signal.is_set() {
+            return Err(SolverException::Interrupted);
+        }
+        let search_actions = match state.effects.allow_quality_actions() {
+            false => PROGRESS_ONLY_SEARCH_ACTIONS,
+            true => FULL_SEARCH_ACTIONS,
+        };
+        for action in search_actions {
+            let steps = actions.len() as u8 + action.steps();
+            if steps > max_steps {
+                continue;
+            }
+            let Ok(state) = use_action_combo(&self.settings, state, *action) else {
+                continue;
+            };
+            let capped_quality = std::cmp::min(state.quality, self.settings.max_quality());
+            if state.is_final(&self.settings.simulator_settings) {
+                if state.progress >= self.settings.max_progress()
+                    && capped_quality >= target_quality
+                {
+                    let mut rotation = actions.clone();
+                    rotation.extend_from_slice(action.actions());
+                    rotation.sort_unstable_by_key(|action| action.id());
+                    rotations.insert(rotation);
+                }
+                continue;
+            }
+            if !self.finish_solver.can_finish(&state)
+                || self.quality_ub_solver.quality_upper_bound(state)? < target_quality
+            {
+                continue;
+            }
+            if target_quality >= self.settings.max_quality()
+                && steps.saturating_add(self.step_lb_solver.step_lower_bound(state, 0)?) > max_steps
+            {
+                continue;
+            }
+            actions.extend_from_slice(action.actions());
+            self.collect_optimal_rotations(state, target_quality, max_steps, actions, rotations)?;
+            actions.truncate(actions.len() - action.actions().len());
+        }
+        Ok(())
+    }
+
+    /// Replays the rotation and, for each step, compares the Quality upper bound of the chosen step
+    /// to the best Quality upper bound of all other steps that the solver could have taken instead.
+    /// Steps after which Progress can no longer be completed are not considered.
+    ///
+    /// The rotation is split into the steps of the solver, so it must be a rotation that the solver could have returned.
+    pub fn explain_rotation(
+        &mut self,
+        actions: &[Action],
+    ) -> Result<Vec<StepExplanation>, SolverException> {
+        let mut explanations = Vec::new();
+        let mut state = SimulationState::new(&self.settings.simulator_settings);
+        let mut remaining_actions = actions;
+        while !remaining_actions.is_empty() {
+            let search_actions = match state.effects.allow_quality_actions() {
+                false => PROGRESS_ONLY_SEARCH_ACTIONS,
+                true => FULL_SEARCH_ACTIONS,
+            };
+            let chosen = search_actions
+                .iter()
+                .filter(|action| remaining_actions.starts_with(action.actions()))
+                .filter(|action| use_action_combo(&self.settings, state, **action).is_ok())
+                .max_by_key(|action| action.steps())
+                .copied()
+                .ok_or_else(|| {
+                    SolverException::InternalError(format!(
+                        "Rotation cannot be split into solver steps at action {}",
+                        actions.len() - remaining_actions.len()
+                    ))
+                })?;
+            let mut chosen_bound = 0;
+            let mut best_alternative_bound = None;
+            for action in search_actions {
+                if let Ok(child) = use_action_combo(&self.settings, state, *action) {
+                    let bound = self.child_quality_upper_bound(child)?;
+                    if *action == chosen {
+                        chosen_bound = bound.unwrap_or(0);
+                    } else if bound.is_some() {
+                        best_alternative_bound = std::cmp::max(best_alternative_bound, bound);
+                    }
+                }
+            }
+            explanations.push(StepExplanation {
+                chosen: chosen.actions().to_vec(),
+                chosen_bound,
+                best_alternative_bound,
+            });
+            state = use_action_combo(&self.settings, state, chosen)
+                .map_err(|err| SolverException::InternalError(err.to_owned()))?;
+            remaining_actions = &remaining_actions[chosen.actions().len()..];
+        }
+        Ok(explanations)
+    }
+
+    /// Same as `solve`, but additionally explains each step of the rotation, see `explain_rotation`.
+    pub fn solve_with_explanation(
+        &mut self,
+    ) -> Result<(Vec<Action>, Vec<StepExplanation>), SolverException> {
+        let actions = self.solve()?;
+        let explanations = self.explain_rotation(&actions)?;
+        Ok((actions, explanations))
+    }
+
+    /// Upper bound on the Quality that can be reached from `state`, or `None` if Progress cannot be completed.
+    fn child_quality_upper_bound(
+        &mut self,
+        state: SimulationState,
+    ) -> Result<Option<u32>, SolverException> {
+        if state.is_final(&self.settings.simulator_settings) {
+            if state.progress < self.settings.max_progress()
+                && !self.settings.simulator_settings.progress_optional
+            {
+                return Ok(None);
+            }
+            return Ok(Some(std::cmp::min(
+                state.quality,
+                self.settings.max_quality(),
+            )));
+        }
+        if !self.finish_solver.can_finish(&state) {
+            return Ok(None);
+        }
+        if state.quality >= self.settings.max_quality() {
+            return Ok(Some(self.settings.max_quality()));
+        }
+        quality_upper_bound_with_combo(&mut self.quality_ub_solver, &self.settings, state).map(Some)
+    }
+
+    /// Same as `solve`, but additionally returns the conditions under which the rotation reaches the least Quality.
+    /// With the `adversarial` setting, this is the condition sequence that realizes the guaranteed Quality of the rotation.
+    pub fn solve_with_worst_case_conditions(
+        &mut self,
+    ) -> Result<(Vec<Action>, Vec<Condition>), SolverException> {
+        let actions = self.solve()?;
+        let conditions =
+            SimulationState::worst_case_conditions(&self.settings.simulator_settings, &actions)
+                .map_err(|err| SolverException::InternalError(err.to_owned()))?;
+        Ok((actions, conditions))
+    }
+
+    /// Finds a rotation that uses as few buff actions (Innovation, Veneration, Great Strides and Manipulation) as possible
+    /// while reaching at most `quality_tolerance` less Quality than the optimal rotation.
+    ///
+    /// The optimal Quality is determined by a regular solve beforehand, so this takes roughly twice as long as `solve`.
+    pub fn solve_with_fewer_buffs(
+        &mut self,
+        quality_tolerance: u32,
+    ) -> Result<Vec<Action>, SolverException> {
+        const BUFF_ACTIONS: ActionMask = action_mask!(
+            Action::Innovation,
+            Action::Veneration,
+            Action::GreatStrides,
+            Action::Manipulation
+        );
+        self.solve_with_penalized_actions(BUFF_ACTIONS, quality_tolerance)
+    }
+
+    /// Finds a rotation that uses as few durability actions (Master's Mend, Immaculate Mend, Manipulation, Waste Not and Waste Not II)
+    /// as possible among all rotations that reach the optimal Quality. Such rotations are easier to execute by hand.
+    ///
+    /// The optimal Quality is determined by a regular solve beforehand, so this takes roughly twice as long as `solve`.
+    pub fn solve_with_fewer_durability_actions(&mut self) -> Result<Vec<Action>, SolverException> {
+        const DURABILITY_ACTIONS: ActionMask = action_mask!(
+            Action::MasterMend,
+            Action::ImmaculateMend,
+            Action::Manipulation,
+            Action::WasteNot,
+            Action::WasteNot2
+        );
+        self.solve_with_penalized_actions(DURABILITY_ACTIONS, 0)
+    }
+
+    /// Finds the rotation with the shortest total duration (see `Action::time_cost`) among all rotations that reach the optimal Quality.
+    /// Unlike `solve`, which minimizes the number of steps first, this prefers e.g. two 2-second buffs over one 3-second action.
+    pub fn solve_minimize_duration(&mut self) -> Result<Vec<Action>, SolverException> {
+        let step_lb_solver = std::mem::replace(
+            &mut self.step_lb_solver,
+            StepLbSolver::new_duration_lb(self.settings, self.interrupt_signal.clone()),
+        );
+        self.minimize_duration = true;
+        let result = self.solve();
+        self.minimize_duration = false;
+        self.step_lb_solver = step_lb_solver;
+        result
+    }
+
+    /// Finds a rotation that overshoots the max Quality as little as possible among all rotations that reach the optimal Quality,
+    /// e.g. by finishing with a smaller touch. Overshoot is penalized in steps of 1% of the max Quality and takes precedence over the number of steps.
+    pub fn solve_minimize_quality_overshoot(&mut self) -> Result<Vec<Action>, SolverException> {
+        self.minimize_quality_overshoot = true;
+        let result = self.solve();
+        self.minimize_quality_overshoot = false;
+        result
+    }
+
+    /// Returns the optimal rotation together with the shortest rotation that reaches at least `100 - quality_tolerance_percent`
+    /// percent of the optimal Quality, e.g. an 18-step rotation that reaches 99% of the Quality of a 26-step optimal rotation.
+    ///
+    /// The shorter rotation is `None` if no rotation within the tolerance has fewer steps than the optimal rotation.
+    /// This takes roughly twice as long as `solve`.
+    pub fn solve_with_shorter_alternative(
+        &mut self,
+        quality_tolerance_percent: u8,
+    ) -> Result<(Vec<Action>, Option<Vec<Action>>), SolverException> {
+        let optimal_actions = self.solve()?;
+        let optimal_quality =
+            SimulationState::from_macro(&self.settings.simulator_settings, &optimal_actions)
+                .map_err(|(_, err)| SolverException::InternalError(err.to_owned()))?
+                .quality;
+
+        let quality_percent = 100u32.saturating_sub(u32::from(quality_tolerance_percent));
+        let mut settings = self.settings;
+        settings.simulator_settings.max_quality =
+            (std::cmp::min(optimal_quality, self.settings.max_quality()) * quality_percent)
+                .div_ceil(100) as u16;
+        let mut solver = MacroSolver::new(
+            settings,
+            Box::new(|actions| (self.solution_callback)(actions)),
+            Box::new(|progress| (self.progress_callback)(progress)),
+            self.interrupt_signal.clone(),
+        );
+        let shorter_actions = solver.solve()?;
+        drop(solver);
+        match shorter_actions.len() < optimal_actions.len() {
+            true => Ok((optimal_actions, Some(shorter_actions))),
+            false => Ok((optimal_actions, None)),
+        }
+    }
+
+    fn solve_with_penalized_actions(
+        &mut self,
+        penalized_actions: ActionMask,
+        quality_tolerance: u32,
+    ) -> Result<Vec<Action>, SolverException> {
+        let optimal_actions = self.solve()?;
+        let optimal_quality =
+            SimulationState::from_macro(&self.settings.simulator_settings, &optimal_actions)
+                .map_err(|(_, err)| SolverException::InternalError(err.to_owned()))?
+                .quality;
+
+        let mut settings = self.settings;
+        settings.simulator_settings.max_quality =
+            std::cmp::min(optimal_quality, self.settings.max_quality())
+                .saturating_sub(quality_tolerance) as u16;
+        let mut solver = MacroSolver::new(
+            settings,
+            Box::new(|actions| (self.solution_callback)(actions)),
+            Box::new(|progress| (self.progress_callback)(progress)),
+            self.interrupt_signal.clone(),
+        );
+        solver.penalized_actions = penalized_actions;
+        solver.solve()
     }
 
     fn do_solve(&mut self, state: SimulationState) -> Result<Solution, SolverException> {
+        self.checkpoint = None;
+        let has_suffix = !self.locked_suffix.is_empty() || self.quality_finisher.is_some();
         let mut search_queue = {
-            let quality_lower_bound = fast_lower_bound(
-                state,
-                self.settings,
-                self.interrupt_signal.clone(),
-                &mut self.finish_solver,
-                &mut self.quality_ub_solver,
-            )?;
+            let quality_lower_bound = match has_suffix {
+                true => 0,
+                false => fast_lower_bound(
+                    state,
+                    self.settings,
+                    self.interrupt_signal.clone(),
+                    &mut self.finish_solver,
+                    &mut self.quality_ub_solver,
+                )?,
+            };
             let minimum_score = SearchScore {
                 quality_upper_bound: quality_lower_bound,
                 ..SearchScore::MIN
@@ -123,106 +730,317 @@ impl<'a> MacroSolver<'a> {
             SearchQueue::new(state, minimum_score)
         };
 
-        let mut solution: Option<Solution> = None;
+        let solution: Option<Solution> = match has_suffix {
+            true => None,
+            false => self.warm_start_solution(state),
+        };
+        if let Some(solution) = &solution {
+            search_queue.update_min_score(solution.score.0);
+        }
+        self.search(SolverCheckpoint {
+            simulator_settings: self.settings.simulator_settings,
+            initial_state: state,
+            search_queue,
+            solution,
+            popped: 0,
+        })
+    }
+
+    fn search(&mut self, checkpoint: SolverCheckpoint) -> Result<Solution, SolverException> {
+        let SolverCheckpoint {
+            initial_state,
+            mut search_queue,
+            mut solution,
+            mut popped,
+            ..
+        } = checkpoint;
+        // The Quality of intermediate states is not a lower bound on the Quality of a solution
+        // if the solution must end with the locked suffix or the Quality finisher.
+        let has_suffix = !self.locked_suffix.is_empty() || self.quality_finisher.is_some();
+        let mut ordered_actions = Vec::new();
 
-        let mut popped = 0;
-        while let Some((state, score, backtrack_id)) = search_queue.pop() {
+        loop {
             if self.interrupt_signal.is_set() {
+                self.checkpoint = Some(SolverCheckpoint {
+                    simulator_settings: self.settings.simulator_settings,
+                    initial_state,
+                    search_queue,
+                    solution,
+                    popped,
+                });
                 return Err(SolverException::Interrupted);
             }
+            let Some((state, score, backtrack_id)) = search_queue.pop() else {
+                break;
+            };
 
             popped += 1;
             if popped % (1 << 12) == 0 {
                 (self.progress_callback)(popped);
             }
 
-            let search_actions = match state.effects.allow_quality_actions() {
+            let mut search_actions = match state.effects.allow_quality_actions() {
                 false => PROGRESS_ONLY_SEARCH_ACTIONS,
                 true => FULL_SEARCH_ACTIONS,
             };
+            if let Some(action_ordering) = &self.action_ordering {
+                ordered_actions.clear();
+                ordered_actions.extend_from_slice(search_actions);
+                ordered_actions.sort_by_cached_key(|action: &ActionCombo| {
+                    action_ordering.sort_key(&state, action.actions())
+                });
+                search_actions = &ordered_actions;
+            }
 
-            for action in search_actions {
-                if let Ok(state) = use_action_combo(&self.settings, state, *action) {
-                    if !state.is_final(&self.settings.simulator_settings) {
-                        if !self.finish_solver.can_finish(&state) {
-                            // skip this state if it is impossible to max out Progress
-                            continue;
-                        }
+            let previous_action = search_queue.last_action(backtrack_id);
+            let mut expand_node = || -> Result<(), SolverException> {
+                for action in search_actions {
+                    if let (Some(ActionCombo::Single(previous_action)), ActionCombo::Single(action)) =
+                        (previous_action, action)
+                        && action.redundant_after(previous_action, state.effects)
+                    {
+                        continue;
+                    }
+                    if let Ok(state) = use_action_combo(&self.settings, state, *action) {
+                        let penalty = score.penalty + self.action_penalty(*action);
+                        if !state.is_final(&self.settings.simulator_settings) {
+                            if !self.finish_solver.can_finish(&state) {
+                                // skip this state if it is impossible to max out Progress
+                                continue;
+                            }
 
-                        search_queue.update_min_score(SearchScore {
-                            quality_upper_bound: std::cmp::min(
-                                state.quality,
-                                self.settings.max_quality(),
-                            ),
-                            ..SearchScore::MIN
-                        });
+                            if !has_suffix {
+                                search_queue.update_min_score(SearchScore {
+                                    quality_upper_bound: std::cmp::min(
+                                        state.quality,
+                                        self.settings.max_quality(),
+                                    ),
+                                    ..SearchScore::MIN
+                                });
+                            }
 
-                        let quality_upper_bound = if state.quality >= self.settings.max_quality() {
-                            self.settings.max_quality()
-                        } else {
-                            std::cmp::min(
-                                score.quality_upper_bound,
-                                self.quality_ub_solver.quality_upper_bound(state)?,
-                            )
-                        };
+                            let quality_upper_bound =
+                                if state.quality >= self.settings.max_quality() {
+                                    self.settings.max_quality()
+                                } else {
+                                    std::cmp::min(
+                                        score.quality_upper_bound,
+                                        self.quality_ub_solver.quality_upper_bound(state)?,
+                                    )
+                                };
+
+                            let current_steps = score.current_steps + action.steps();
+                            let current_duration = score.current_duration + action.duration();
 
-                        let step_lb_hint = score
-                            .steps_lower_bound
-                            .saturating_sub(score.current_steps + action.steps());
-                        let steps_lower_bound =
-                            match quality_upper_bound >= self.settings.max_quality() {
-                                true => self
-                                    .step_lb_solver
-                                    .step_lower_bound(state, step_lb_hint)?
-                                    .saturating_add(score.current_steps + action.steps()),
-                                false => score.current_steps + action.steps(),
+                            // When minimizing duration, the step lower bound solver bounds the remaining duration instead
+                            // and the step count is left out of the score.
+                            let (current, lower_bound) = match self.minimize_duration {
+                                true => (current_duration, score.duration_lower_bound),
+                                false => (current_steps, score.steps_lower_bound),
                             };
+                            let step_lb_hint = lower_bound.saturating_sub(current);
+                            let lower_bound =
+                                match quality_upper_bound >= self.settings.max_quality() {
+                                    true => self
+                                        .step_lb_solver
+                                        .step_lower_bound(state, step_lb_hint)?
+                                        .saturating_add(current),
+                                    false => current,
+                                };
+                            let (steps_lower_bound, duration_lower_bound) =
+                                match self.minimize_duration {
+                                    true => (0, std::cmp::max(lower_bound, current_duration + 3)),
+                                    false => (lower_bound, current_duration + 3),
+                                };
 
-                        search_queue.push(
-                            state,
-                            SearchScore {
-                                quality_upper_bound,
-                                steps_lower_bound,
-                                duration_lower_bound: score.current_duration
-                                    + action.duration()
-                                    + 3,
-                                current_steps: score.current_steps + action.steps(),
-                                current_duration: score.current_duration + action.duration(),
-                            },
-                            *action,
-                            backtrack_id,
-                        );
-                    } else if state.progress >= self.settings.max_progress() {
-                        let solution_score = SearchScore {
-                            quality_upper_bound: std::cmp::min(
-                                state.quality,
-                                self.settings.max_quality(),
-                            ),
-                            steps_lower_bound: score.current_steps + action.steps(),
-                            duration_lower_bound: score.current_duration + action.duration(),
-                            current_steps: score.current_steps + action.steps(),
-                            current_duration: score.current_duration + action.duration(),
+                            search_queue.push(
+                                state,
+                                SearchScore {
+                                    quality_upper_bound,
+                                    penalty,
+                                    steps_lower_bound,
+                                    duration_lower_bound,
+                                    current_steps,
+                                    current_duration,
+                                },
+                                *action,
+                                backtrack_id,
+                            );
+                        }
+                        let finisher_suffix;
+                        let suffix: &[Action] = if !has_suffix {
+                            &[]
+                        } else if state.is_final(&self.settings.simulator_settings) {
+                            continue;
+                        } else if let Some(finisher) = self.quality_finisher {
+                            let Some(actions) = quality_finisher_suffix(
+                                &self.settings,
+                                &mut self.finish_solver,
+                                state,
+                                finisher,
+                            ) else {
+                                continue;
+                            };
+                            finisher_suffix = actions;
+                            &finisher_suffix
+                        } else {
+                            &self.locked_suffix
+                        };
+                        let final_state = if suffix.is_empty() {
+                            Some(state)
+                        } else {
+                            state
+                                .use_actions_with_condition_schedule(
+                                    suffix,
+                                    &[],
+                                    &self.settings.simulator_settings,
+                                )
+                                .ok()
                         };
-                        search_queue.update_min_score(solution_score);
-                        if solution.is_none()
-                            || solution.as_ref().unwrap().score < (solution_score, state.quality)
+                        let Some(final_state) = final_state else {
+                            continue;
+                        };
+                        if final_state.progress >= self.settings.max_progress()
+                            || self.settings.simulator_settings.progress_optional
                         {
-                            solution = Some(Solution {
-                                score: (solution_score, state.quality),
-                                solver_actions: search_queue
-                                    .backtrack(backtrack_id)
-                                    .chain(std::iter::once(*action))
-                                    .collect(),
-                            });
-                            (self.solution_callback)(&solution.as_ref().unwrap().actions());
+                            let state = final_state;
+                            let suffix_penalty = suffix
+                                .iter()
+                                .filter(|action| self.penalized_actions.has(**action))
+                                .count() as u8;
+                            let suffix_duration: u8 =
+                                suffix.iter().map(|action| action.time_cost()).sum();
+                            let steps = score.current_steps + action.steps() + suffix.len() as u8;
+                            let duration =
+                                score.current_duration + action.duration() + suffix_duration;
+                            let solution_score = SearchScore {
+                                quality_upper_bound: std::cmp::min(
+                                    state.quality,
+                                    self.settings.max_quality(),
+                                ),
+                                penalty: (penalty + suffix_penalty)
+                                    .saturating_add(self.overshoot_penalty(state.quality)),
+                                steps_lower_bound: match self.minimize_duration {
+                                    true => 0,
+                                    false => steps,
+                                },
+                                duration_lower_bound: duration,
+                                current_steps: steps,
+                                current_duration: duration,
+                            };
+                            search_queue.update_min_score(solution_score);
+                            let score = (solution_score, state.quality);
+                            if solution
+                                .as_ref()
+                                .is_none_or(|solution| self.is_better_score(score, solution.score))
+                            {
+                                solution = Some(Solution {
+                                    score,
+                                    solver_actions: search_queue
+                                        .backtrack(backtrack_id)
+                                        .chain(std::iter::once(*action))
+                                        .chain(suffix.iter().copied().map(ActionCombo::Single))
+                                        .collect(),
+                                });
+                                (self.solution_callback)(&solution.as_ref().unwrap().actions());
+                            }
                         }
                     }
                 }
+                Ok(())
+            };
+            match expand_node() {
+                Ok(()) => (),
+                Err(SolverException::Interrupted) => {
+                    // Expand the node again when resuming. Children that were already pushed
+                    // are dominated by their duplicates in the Pareto front and are skipped.
+                    search_queue.restore(state, backtrack_id);
+                    popped -= 1;
+                }
+                Err(err) => return Err(err),
             }
         }
 
         self.search_queue_stats = search_queue.runtime_stats();
-        solution.ok_or(SolverException::NoSolution)
+        let solution = solution.ok_or(SolverException::NoSolution)?;
+        debug_verify_solution(&self.settings, initial_state, &solution);
+        Ok(solution)
+    }
+
+    /// Replays the warm start hint from `state` and returns it as a solution if it completes the craft.
+    fn warm_start_solution(&self, state: SimulationState) -> Option<Solution> {
+        if self.warm_start_hint.is_empty() {
+            return None;
+        }
+        let final_state = state
+            .use_actions_with_condition_schedule(
+                &self.warm_start_hint,
+                &[],
+                &self.settings.simulator_settings,
+            )
+            .ok()?;
+        if final_state.progress < self.settings.max_progress()
+            && !self.settings.simulator_settings.progress_optional
+        {
+            return None;
+        }
+        let steps = self.warm_start_hint.len() as u8;
+        let duration: u8 = self.warm_start_hint.iter().map(|a| a.time_cost()).sum();
+        let score = SearchScore {
+            quality_upper_bound: std::cmp::min(final_state.quality, self.settings.max_quality()),
+            penalty: (self
+                .warm_start_hint
+                .iter()
+                .filter(|action| self.penalized_actions.has(**action))
+                .count() as u8)
+                .saturating_add(self.overshoot_penalty(final_state.quality)),
+            steps_lower_bound: match self.minimize_duration {
+                true => 0,
+                false => steps,
+            },
+            duration_lower_bound: duration,
+            current_steps: steps,
+            current_duration: duration,
+        };
+        Some(Solution {
+            score: (score, final_state.quality),
+            solver_actions: self
+                .warm_start_hint
+                .iter()
+                .copied()
+                .map(ActionCombo::Single)
+                .collect(),
+        })
+    }
+
+    /// Penalty of a solution with the given final Quality when minimizing the Quality overshoot.
+    fn overshoot_penalty(&self, quality: u32) -> u8 {
+        if !self.minimize_quality_overshoot {
+            return 0;
+        }
+        let max_quality = self.settings.max_quality();
+        if max_quality == 0 {
+            // no Quality is needed, so any Quality is as good as none
+            return 0;
+        }
+        let overshoot = quality.saturating_sub(max_quality);
+        std::cmp::min(overshoot.saturating_mul(100).div_ceil(max_quality), 255) as u8
+    }
+
+    /// Compares solution scores, breaking ties in favor of more Quality unless minimizing the Quality overshoot.
+    fn is_better_score(&self, lhs: (SearchScore, u32), rhs: (SearchScore, u32)) -> bool {
+        match self.minimize_quality_overshoot {
+            true => (lhs.0, std::cmp::Reverse(lhs.1)) > (rhs.0, std::cmp::Reverse(rhs.1)),
+            false => lhs > rhs,
+        }
+    }
+
+    fn action_penalty(&self, action: ActionCombo) -> u8 {
+        action
+            .actions()
+            .iter()
+            .filter(|action| self.penalized_actions.has(**action))
+            .count() as u8
     }
 
     pub fn runtime_stats(&self) -> MacroSolverStats {
@@ -234,3 +1052,107 @@ impl<'a> MacroSolver<'a> {
         }
     }
 }
+
+/// Uses `finisher` and then completes Progress with as few steps as possible without increasing Quality.
+/// Returns the used actions, or `None` if Progress cannot be completed after the finisher.
+fn quality_finisher_suffix(
+    settings: &SolverSettings,
+    finish_solver: &mut FinishSolver,
+    state: SimulationState,
+    finisher: Action,
+) -> Option<Vec<Action>> {
+    let state = state
+        .use_action(finisher, Condition::Normal, &settings.simulator_settings)
+        .ok()?;
+    let mut suffix = vec![finisher];
+    if settings.simulator_settings.progress_optional {
+        return Some(suffix);
+    }
+    if state.is_final(&settings.simulator_settings) {
+        return (state.progress >= settings.max_progress()).then_some(suffix);
+    }
+    suffix.extend(finish_solver.finish_sequence(&state)?);
+    Some(suffix)
+}
+
+/// Replays the actions of the solution through the simulator and asserts that the replayed Quality
+/// matches the Quality reported by the search. Only checked in debug builds.
+fn debug_verify_solution(settings: &SolverSettings, state: SimulationState, solution: &Solution) {
+    if cfg!(debug_assertions) {
+        let replayed_state = state
+            .use_actions_with_condition_schedule(
+                &solution.actions(),
+                &[],
+                &settings.simulator_settings,
+            )
+            .expect("Solution cannot be replayed");
+        assert_eq!(
+            replayed_state.quality, solution.score.1,
+            "Replayed Quality does not match the Quality reported by the solver"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_solution(settings: &SolverSettings) -> (SimulationState, Solution) {
+        let state = SimulationState::new(&settings.simulator_settings);
+        let solver_actions = vec![
+            ActionCombo::Single(Action::Reflect),
+            ActionCombo::Single(Action::BasicTouch),
+            ActionCombo::Single(Action::CarefulSynthesis),
+        ];
+        let actions: Vec<Action> = solver_actions
+            .iter()
+            .flat_map(|action| action.actions().iter().copied())
+            .collect();
+        let final_state =
+            SimulationState::from_macro(&settings.simulator_settings, &actions).unwrap();
+        let solution = Solution {
+            score: (SearchScore::MIN, final_state.quality),
+            solver_actions,
+        };
+        (state, solution)
+    }
+
+    fn test_settings() -> SolverSettings {
+        SolverSettings {
+            simulator_settings: Settings {
+                max_cp: 500,
+                max_durability: 60,
+                max_progress: 2000,
+                max_quality: 40000,
+                base_progress: 100,
+                base_quality: 100,
+                job_level: 100,
+                allowed_actions: ActionMask::all(),
+                adversarial: false,
+                backload_progress: false,
+                quality_increase_cap: None,
+                progress_target: None,
+                progress_optional: false,
+                rounding_mode: RoundingMode::Floor,
+                cp_cost_overrides: CpCostOverrides::none(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_verify_solution() {
+        let settings = test_settings();
+        let (state, solution) = test_solution(&settings);
+        debug_verify_solution(&settings, state, &solution);
+    }
+
+    #[test]
+    #[cfg_attr(debug_assertions, should_panic(expected = "Replayed Quality"))]
+    fn test_verify_tampered_solution() {
+        let settings = test_settings();
+        let (state, mut solution) = test_solution(&settings);
+        // Replace Basic Touch with an action that doesn't increase Quality
+        solution.solver_actions[1] = ActionCombo::Single(Action::BasicSynthesis);
+        debug_verify_solution(&settings, state, &solution);
+    }
+}
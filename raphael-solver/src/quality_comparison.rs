@@ -0,0 +1,52 @@
+use raphael_sim::SimulationState;
+
+use crate::{AtomicFlag, MacroSolver, SolverException, SolverSettings};
+
+/// Solves the settings and returns the reached Quality, capped at the Quality target.
+fn solve_quality(settings: SolverSettings) -> Result<u32, SolverException> {
+    let mut solver = MacroSolver::new(
+        settings,
+        Box::new(|_| {}),
+        Box::new(|_| {}),
+        AtomicFlag::new(),
+    );
+    let actions = solver.solve()?;
+    let state = SimulationState::from_macro(&settings.simulator_settings, &actions)
+        .map_err(|(_, err)| SolverException::InternalError(err.to_owned()))?;
+    Ok(std::cmp::min(state.quality, settings.max_quality()))
+}
+
+/// Solves the settings once with and once without the `adversarial` setting
+/// and returns the Quality guaranteed regardless of condition procs together with the best-case Quality under Normal conditions.
+/// Both values are capped at the Quality target. The `adversarial` setting of `settings` is ignored.
+pub fn guaranteed_and_best_case_quality(
+    settings: SolverSettings,
+) -> Result<(u32, u32), SolverException> {
+    let with_adversarial = |adversarial: bool| {
+        let mut settings = settings;
+        settings.simulator_settings.adversarial = adversarial;
+        settings
+    };
+    Ok((
+        solve_quality(with_adversarial(true))?,
+        solve_quality(with_adversarial(false))?,
+    ))
+}
+
+/// Solves the settings once with and once without the `backload_progress` setting
+/// and returns the best Quality with backloaded Progress together with the best unconstrained Quality.
+/// The difference between the two is the Quality lost to backloading Progress.
+/// Both values are capped at the Quality target. The `backload_progress` setting of `settings` is ignored.
+pub fn backloaded_and_unconstrained_quality(
+    settings: SolverSettings,
+) -> Result<(u32, u32), SolverException> {
+    let with_backload_progress = |backload_progress: bool| {
+        let mut settings = settings;
+        settings.simulator_settings.backload_progress = backload_progress;
+        settings
+    };
+    Ok((
+        solve_quality(with_backload_progress(true))?,
+        solve_quality(with_backload_progress(false))?,
+    ))
+}
@@ -0,0 +1,44 @@
+use std::collections::BTreeMap;
+
+use raphael_sim::{Condition, CraftOutcome, SimulationState};
+use rustc_hash::FxHashSet as HashSet;
+
+use crate::SolverSettings;
+
+/// Enumerates all states that can be reached under Normal conditions with at most `max_steps` actions
+/// and returns the number of distinct completed states for each final Quality, capped at the Quality target.
+///
+/// The number of reachable states grows exponentially with `max_steps`, so this is only tractable for small step bounds.
+/// States that are reached by different rotations are counted once.
+pub fn terminal_quality_histogram(settings: SolverSettings, max_steps: u8) -> BTreeMap<u32, usize> {
+    let simulator_settings = &settings.simulator_settings;
+    let initial_state = SimulationState::new(simulator_settings);
+    let mut visited: HashSet<SimulationState> = HashSet::default();
+    visited.insert(initial_state);
+    let mut layer = vec![initial_state];
+    let mut histogram = BTreeMap::new();
+    for _ in 0..max_steps {
+        let mut next_layer = Vec::new();
+        for state in &layer {
+            for action in state.legal_actions(Condition::Normal, simulator_settings) {
+                let Ok(state) = state.use_action(action, Condition::Normal, simulator_settings)
+                else {
+                    continue;
+                };
+                if !visited.insert(state) {
+                    continue;
+                }
+                match state.outcome(simulator_settings) {
+                    CraftOutcome::Completed => {
+                        let quality = std::cmp::min(state.quality, settings.max_quality());
+                        *histogram.entry(quality).or_default() += 1;
+                    }
+                    CraftOutcome::InProgress => next_layer.push(state),
+                    CraftOutcome::Failed { .. } => (),
+                }
+            }
+        }
+        layer = next_layer;
+    }
+    histogram
+}
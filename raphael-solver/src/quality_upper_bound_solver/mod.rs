@@ -3,5 +3,60 @@ mod state;
 
 pub use solver::{QualityUbSolver, QualityUbSolverStats};
 
+use raphael_sim::{Combo, SimulationState};
+
+use crate::{
+    AtomicFlag, SolverException, SolverSettings,
+    actions::{FULL_SEARCH_ACTIONS, use_action_combo},
+};
+
+/// Returns an upper bound on the Quality that any rotation can reach from the start of the craft.
+/// The bound is not necessarily reachable.
+pub fn max_possible_quality(settings: SolverSettings) -> Result<u32, SolverException> {
+    settings
+        .simulator_settings
+        .validate()
+        .map_err(|err| SolverException::InternalError(err.to_owned()))?;
+    let mut solver = QualityUbSolver::new(settings, AtomicFlag::new());
+    let initial_state = SimulationState::new(&settings.simulator_settings);
+    quality_upper_bound_with_combo(&mut solver, &settings, initial_state)
+}
+
+/// Estimates the memory (in bytes) that the Quality upper bound precompute of a solve with these settings allocates.
+/// Can be used to decide whether a solve fits into the memory of a device before starting it.
+pub fn estimated_precompute_memory_bytes(settings: SolverSettings) -> usize {
+    QualityUbSolver::estimated_memory_bytes(settings)
+}
+
+/// Same as `QualityUbSolver::quality_upper_bound`, but also accepts states with an active combo.
+pub(crate) fn quality_upper_bound_with_combo(
+    solver: &mut QualityUbSolver,
+    settings: &SolverSettings,
+    state: SimulationState,
+) -> Result<u32, SolverException> {
+    if state.effects.combo() == Combo::None {
+        return solver.quality_upper_bound(state);
+    }
+    let mut max_quality = 0;
+    if settings.simulator_settings.progress_optional {
+        max_quality = std::cmp::min(state.quality, settings.max_quality());
+    }
+    // The quality upper bound solver does not handle combos, so the next action is expanded here.
+    for action in FULL_SEARCH_ACTIONS {
+        if let Ok(state) = use_action_combo(settings, state, *action) {
+            let quality = if state.is_final(&settings.simulator_settings) {
+                if state.progress < settings.max_progress() {
+                    continue;
+                }
+                std::cmp::min(state.quality, settings.max_quality())
+            } else {
+                solver.quality_upper_bound(state)?
+            };
+            max_quality = std::cmp::max(max_quality, quality);
+        }
+    }
+    Ok(max_quality)
+}
+
 #[cfg(test)]
 mod tests;
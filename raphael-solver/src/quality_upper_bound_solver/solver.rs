@@ -11,8 +11,10 @@ use super::state::ReducedState;
 type ParetoValue = utils::ParetoValue<u32, u32>;
 type ParetoFrontBuilder = utils::ParetoFrontBuilder<u32, u32>;
 type SolvedStates = rustc_hash::FxHashMap<ReducedState, Box<[ParetoValue]>>;
+type SaturatedTemplates = rustc_hash::FxHashMap<Template, u16>;
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct QualityUbSolverStats {
     pub states: usize,
     pub pareto_values: usize,
@@ -22,6 +24,8 @@ pub struct QualityUbSolver {
     settings: SolverSettings,
     interrupt_signal: utils::AtomicFlag,
     solved_states: SolvedStates,
+    // lowest precomputed CP at which the Pareto front of a template maxes out both Progress and Quality
+    saturated_templates: SaturatedTemplates,
     pareto_front_builder: ParetoFrontBuilder,
     durability_cost: u16,
 }
@@ -29,11 +33,15 @@ pub struct QualityUbSolver {
 impl QualityUbSolver {
     pub fn new(mut settings: SolverSettings, interrupt_signal: utils::AtomicFlag) -> Self {
         let durability_cost = durability_cost(&settings.simulator_settings);
-        settings.simulator_settings.max_cp += durability_cost * (settings.max_durability() / 5);
+        settings.simulator_settings.max_cp = settings
+            .simulator_settings
+            .max_cp
+            .saturating_add(durability_cost.saturating_mul(settings.max_durability() / 5));
         Self {
             settings,
             interrupt_signal,
             solved_states: SolvedStates::default(),
+            saturated_templates: SaturatedTemplates::default(),
             pareto_front_builder: ParetoFrontBuilder::new(
                 settings.max_progress(),
                 settings.max_quality(),
@@ -94,6 +102,29 @@ impl QualityUbSolver {
         templates.into_iter().collect()
     }
 
+    /// Estimates the memory (in bytes) that `precompute` allocates for the given settings, without running the precompute.
+    ///
+    /// The number of precomputed states is exact unless the precompute stops early because the Quality target is reached,
+    /// in which case it is an upper bound. The size of the Pareto front of each state is not known in advance,
+    /// so an average of `ESTIMATED_PARETO_VALUES_PER_STATE` values per state is assumed.
+    pub fn estimated_memory_bytes(settings: SolverSettings) -> usize {
+        let solver = Self::new(settings, utils::AtomicFlag::new());
+        let num_states: usize = solver
+            .generate_precompute_templates()
+            .iter()
+            .map(|(template, required_cp)| {
+                let precompute_cp_ceiling = if template.effects.heart_and_soul_available() {
+                    solver.settings.max_cp().saturating_sub(20)
+                } else {
+                    solver.settings.max_cp()
+                };
+                let max_cp = precompute_cp_ceiling.saturating_sub(*required_cp);
+                usize::from((max_cp + 1).saturating_sub(solver.durability_cost))
+            })
+            .sum();
+        num_states * (STATE_BYTES + ESTIMATED_PARETO_VALUES_PER_STATE * PARETO_VALUE_BYTES)
+    }
+
     pub fn precompute(&mut self) {
         if !self.solved_states.is_empty() || rayon::current_num_threads() <= 1 {
             return;
@@ -130,7 +161,10 @@ impl QualityUbSolver {
                 let missing_cp = precompute_cp_ceiling - cp;
                 let solved_states = templates
                     .par_iter()
-                    .filter(|(_, required_cp)| missing_cp >= *required_cp)
+                    .filter(|(template, required_cp)| {
+                        missing_cp >= *required_cp
+                            && !self.saturated_templates.contains_key(template)
+                    })
                     .map_init(
                         || {
                             ParetoFrontBuilder::new(
@@ -151,18 +185,36 @@ impl QualityUbSolver {
                         },
                     )
                     .collect_vec_list();
-                self.solved_states
-                    .extend(solved_states.into_iter().flatten());
+                for (state, pareto_front) in solved_states.into_iter().flatten() {
+                    if self.is_saturated(&pareto_front) {
+                        // More CP cannot result in a better Pareto front, so states of this template with more CP are not computed.
+                        // This stops the precompute early for low Quality targets.
+                        self.saturated_templates.insert(Template::from(state), cp);
+                    }
+                    self.solved_states.insert(state, pareto_front);
+                }
             }
         }
 
         log::debug!(
-            "QualityUbSolver - templates: {}, precomputed_states: {}",
+            "QualityUbSolver - templates: {}, precomputed_states: {}, saturated_templates: {}",
             all_templates.len(),
-            self.solved_states.len()
+            self.solved_states.len(),
+            self.saturated_templates.len()
         );
     }
 
+    /// Whether the Pareto front maxes out both Progress and Quality.
+    fn is_saturated(&self, pareto_front: &[ParetoValue]) -> bool {
+        match pareto_front {
+            [value] => {
+                value.first >= self.settings.max_progress()
+                    && value.second >= self.settings.max_quality()
+            }
+            _ => false,
+        }
+    }
+
     fn solve_precompute_state(
         &self,
         pareto_front_builder: &mut ParetoFrontBuilder,
@@ -170,12 +222,21 @@ impl QualityUbSolver {
     ) -> Box<[ParetoValue]> {
         pareto_front_builder.clear();
         pareto_front_builder.push_empty();
+        if self.settings.simulator_settings.progress_optional {
+            // the craft may end in this state
+            pareto_front_builder.push_slice(&[ParetoValue::new(0, 0)]);
+            pareto_front_builder.merge();
+        }
         for &action in FULL_SEARCH_ACTIONS {
             if let Some((new_state, progress, quality)) =
                 state.use_action(action, &self.settings, self.durability_cost)
             {
                 if !new_state.is_final(self.durability_cost) {
-                    if let Some(pareto_front) = self.solved_states.get(&new_state) {
+                    if let Some(pareto_front) = solved_pareto_front(
+                        &self.solved_states,
+                        &self.saturated_templates,
+                        new_state,
+                    ) {
                         pareto_front_builder.push_slice(pareto_front);
                     } else {
                         unreachable!(
@@ -191,7 +252,7 @@ impl QualityUbSolver {
                             value.second += quality;
                         });
                     pareto_front_builder.merge();
-                } else if progress != 0 {
+                } else if progress != 0 || self.settings.simulator_settings.progress_optional {
                     pareto_front_builder.push_slice(&[ParetoValue::new(progress, quality)]);
                     pareto_front_builder.merge();
                 }
@@ -200,6 +261,25 @@ impl QualityUbSolver {
         Box::from(pareto_front_builder.peek().unwrap())
     }
 
+    /// Removes all actions that are not in `allowed_actions` from the actions considered by the solver.
+    ///
+    /// Already computed bounds are kept instead of being recomputed. Removing actions only removes transitions,
+    /// so these bounds remain valid upper bounds, but they may be looser than bounds computed from scratch.
+    pub fn restrict_actions(&mut self, allowed_actions: ActionMask) {
+        self.settings.simulator_settings.allowed_actions = self
+            .settings
+            .simulator_settings
+            .allowed_actions
+            .intersection(allowed_actions);
+    }
+
+    /// Changes the Quality at which the upper bounds are capped, e.g. to solve for a different initial Quality.
+    ///
+    /// Already computed states are reused, so `max_quality` must not exceed the max Quality the solver was created with.
+    pub fn set_max_quality(&mut self, max_quality: u16) {
+        self.settings.simulator_settings.max_quality = max_quality;
+    }
+
     /// Returns an upper-bound on the maximum Quality achievable from this state while also maxing out Progress.
     /// There is no guarantee on the tightness of the upper-bound.
     pub fn quality_upper_bound(&mut self, state: SimulationState) -> Result<u32, SolverException> {
@@ -212,9 +292,13 @@ impl QualityUbSolver {
 
         let reduced_state =
             ReducedState::from_simulation_state(state, &self.settings, self.durability_cost);
-        let required_progress = self.settings.max_progress() - state.progress;
+        let required_progress = self.settings.required_progress(state.progress);
 
-        if let Some(pareto_front) = self.solved_states.get(&reduced_state) {
+        if let Some(pareto_front) = solved_pareto_front(
+            &self.solved_states,
+            &self.saturated_templates,
+            reduced_state,
+        ) {
             let index = pareto_front.partition_point(|value| value.first < required_progress);
             let quality = pareto_front
                 .get(index)
@@ -241,6 +325,12 @@ impl QualityUbSolver {
             return Err(SolverException::Interrupted);
         }
         self.pareto_front_builder.push_empty();
+        if self.settings.simulator_settings.progress_optional {
+            // the craft may end in this state
+            self.pareto_front_builder
+                .push_slice(&[ParetoValue::new(0, 0)]);
+            self.pareto_front_builder.merge();
+        }
         let search_actions = match state.effects.allow_quality_actions() {
             false => PROGRESS_ONLY_SEARCH_ACTIONS,
             true => FULL_SEARCH_ACTIONS,
@@ -269,7 +359,9 @@ impl QualityUbSolver {
             state.use_action(action, &self.settings, self.durability_cost)
         {
             if !new_state.is_final(self.durability_cost) {
-                if let Some(pareto_front) = self.solved_states.get(&new_state) {
+                if let Some(pareto_front) =
+                    solved_pareto_front(&self.solved_states, &self.saturated_templates, new_state)
+                {
                     self.pareto_front_builder.push_slice(pareto_front);
                 } else {
                     self.solve_state(new_state)?;
@@ -283,7 +375,7 @@ impl QualityUbSolver {
                         value.second += quality;
                     });
                 self.pareto_front_builder.merge();
-            } else if progress != 0 {
+            } else if progress != 0 || self.settings.simulator_settings.progress_optional {
                 // last action must be a progress increase
                 self.pareto_front_builder
                     .push_slice(&[ParetoValue::new(progress, quality)]);
@@ -312,19 +404,58 @@ impl Drop for QualityUbSolver {
     }
 }
 
+/// Average number of values in the Pareto front of a precomputed state, used by `QualityUbSolver::estimated_memory_bytes`.
+const ESTIMATED_PARETO_VALUES_PER_STATE: usize = 12;
+/// Size of a `SolvedStates` entry, including the control byte of the hash map.
+const STATE_BYTES: usize = std::mem::size_of::<(ReducedState, Box<[ParetoValue]>)>() + 1;
+const PARETO_VALUE_BYTES: usize = std::mem::size_of::<ParetoValue>();
+
+/// Returns the Pareto front of the state if it is already solved.
+/// States of a saturated template that have more CP than the saturated state share its Pareto front.
+fn solved_pareto_front<'a>(
+    solved_states: &'a SolvedStates,
+    saturated_templates: &SaturatedTemplates,
+    state: ReducedState,
+) -> Option<&'a [ParetoValue]> {
+    if let Some(pareto_front) = solved_states.get(&state) {
+        return Some(pareto_front);
+    }
+    let saturated_cp = *saturated_templates.get(&Template::from(state))?;
+    if state.cp < saturated_cp {
+        return None;
+    }
+    let saturated_state = ReducedState {
+        cp: saturated_cp,
+        ..state
+    };
+    solved_states
+        .get(&saturated_state)
+        .map(|pareto_front| &**pareto_front)
+}
+
 /// Calculates the CP cost to "magically" restore 5 durability
 fn durability_cost(settings: &Settings) -> u16 {
+    let cp_cost = |action: Action, default_cp_cost: u16| {
+        settings
+            .cp_cost_overrides
+            .get(action)
+            .unwrap_or(default_cp_cost)
+    };
+    // Number of times 5 durability can be restored by a mend right after using 5 durability
+    let restorable_fives = (settings.max_durability / 5).saturating_sub(1);
     let mut cost = 100;
-    if settings.is_action_allowed::<MasterMend>() {
-        let cost_per_five = MasterMend::CP_COST / std::cmp::min(6, settings.max_durability / 5 - 1);
+    if settings.is_action_allowed::<MasterMend>() && restorable_fives != 0 {
+        let cost_per_five =
+            cp_cost(Action::MasterMend, MasterMend::CP_COST) / std::cmp::min(6, restorable_fives);
         cost = std::cmp::min(cost, cost_per_five);
     }
     if settings.is_action_allowed::<Manipulation>() {
-        let cost_per_five = Manipulation::CP_COST / 8;
+        let cost_per_five = cp_cost(Action::Manipulation, Manipulation::CP_COST) / 8;
         cost = std::cmp::min(cost, cost_per_five);
     }
-    if settings.is_action_allowed::<ImmaculateMend>() {
-        let cost_per_five = ImmaculateMend::CP_COST / (settings.max_durability / 5 - 1);
+    if settings.is_action_allowed::<ImmaculateMend>() && restorable_fives != 0 {
+        let cost_per_five =
+            cp_cost(Action::ImmaculateMend, ImmaculateMend::CP_COST) / restorable_fives;
         cost = std::cmp::min(cost, cost_per_five);
     }
     cost
@@ -336,6 +467,15 @@ struct Template {
     compressed_unreliable_quality: u8,
 }
 
+impl From<ReducedState> for Template {
+    fn from(state: ReducedState) -> Self {
+        Self {
+            effects: state.effects,
+            compressed_unreliable_quality: state.compressed_unreliable_quality,
+        }
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Eq)]
 struct Node {
     template: Template,
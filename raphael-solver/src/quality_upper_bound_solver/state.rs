@@ -46,7 +46,7 @@ impl ReducedState {
         }
         let compressed_unreliable_quality = state
             .unreliable_quality
-            .div_ceil(2 * settings.base_quality())
+            .div_ceil(std::cmp::max(1, 2 * settings.base_quality()))
             as u8;
         let effects = {
             let great_strides_active = state.effects.great_strides() != 0;
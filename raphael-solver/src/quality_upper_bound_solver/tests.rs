@@ -1,10 +1,11 @@
 use expect_test::expect;
-use rand::Rng;
+use rand::{Rng, SeedableRng, rngs::StdRng};
 use raphael_sim::*;
 
 use crate::{
-    SolverSettings,
+    AtomicFlag, SolverSettings,
     actions::{FULL_SEARCH_ACTIONS, use_action_combo},
+    utils::ParetoValue,
 };
 
 use super::QualityUbSolver;
@@ -17,26 +18,45 @@ fn solve(simulator_settings: Settings, actions: &[Action]) -> u32 {
     solver.quality_upper_bound(state).unwrap()
 }
 
-#[test]
-fn test_01() {
-    let settings = Settings {
-        max_cp: 553,
-        max_durability: 70,
-        max_progress: 2400,
-        max_quality: 20000,
-        base_progress: 100,
-        base_quality: 100,
-        job_level: 90,
-        allowed_actions: ActionMask::all()
-            .remove(Action::TrainedEye)
-            .remove(Action::HeartAndSoul)
-            .remove(Action::QuickInnovation),
-        adversarial: false,
-        backload_progress: false,
-    };
-    let result = solve(
-        settings,
-        &[
+struct RegressionCase {
+    settings: Settings,
+    actions: &'static [Action],
+    expected_quality: u32,
+}
+
+const BASE_SETTINGS: Settings = Settings {
+    max_cp: 0,
+    max_durability: 0,
+    max_progress: 0,
+    max_quality: 0,
+    base_progress: 100,
+    base_quality: 100,
+    job_level: 90,
+    allowed_actions: ActionMask::all()
+        .remove(Action::TrainedEye)
+        .remove(Action::HeartAndSoul)
+        .remove(Action::QuickInnovation),
+    adversarial: false,
+    backload_progress: false,
+    quality_increase_cap: None,
+    progress_target: None,
+    progress_optional: false,
+    rounding_mode: RoundingMode::Floor,
+    cp_cost_overrides: CpCostOverrides::none(),
+};
+
+/// Expected quality upper bounds of the initial state after using `actions`.
+/// Every entry is checked by `test_regression_cases`.
+const REGRESSION_CASES: &[RegressionCase] = &[
+    RegressionCase {
+        settings: Settings {
+            max_cp: 553,
+            max_durability: 70,
+            max_progress: 2400,
+            max_quality: 20000,
+            ..BASE_SETTINGS
+        },
+        actions: &[
             Action::MuscleMemory,
             Action::PrudentTouch,
             Action::Manipulation,
@@ -47,30 +67,18 @@ fn test_01() {
             Action::Groundwork,
             Action::PreparatoryTouch,
         ],
-    );
-    assert_eq!(result, 3352);
-}
-
-#[test]
-fn test_adversarial_01() {
-    let settings = Settings {
-        max_cp: 553,
-        max_durability: 70,
-        max_progress: 2400,
-        max_quality: 20000,
-        base_progress: 100,
-        base_quality: 100,
-        job_level: 90,
-        allowed_actions: ActionMask::all()
-            .remove(Action::TrainedEye)
-            .remove(Action::HeartAndSoul)
-            .remove(Action::QuickInnovation),
-        adversarial: true,
-        backload_progress: false,
-    };
-    let result = solve(
-        settings,
-        &[
+        expected_quality: 3352,
+    },
+    RegressionCase {
+        settings: Settings {
+            max_cp: 553,
+            max_durability: 70,
+            max_progress: 2400,
+            max_quality: 20000,
+            adversarial: true,
+            ..BASE_SETTINGS
+        },
+        actions: &[
             Action::MuscleMemory,
             Action::PrudentTouch,
             Action::Manipulation,
@@ -81,30 +89,17 @@ fn test_adversarial_01() {
             Action::Groundwork,
             Action::PreparatoryTouch,
         ],
-    );
-    assert_eq!(result, 2955);
-}
-
-#[test]
-fn test_02() {
-    let settings = Settings {
-        max_cp: 700,
-        max_durability: 70,
-        max_progress: 2500,
-        max_quality: 5000,
-        base_progress: 100,
-        base_quality: 100,
-        job_level: 90,
-        allowed_actions: ActionMask::all()
-            .remove(Action::TrainedEye)
-            .remove(Action::HeartAndSoul)
-            .remove(Action::QuickInnovation),
-        adversarial: false,
-        backload_progress: false,
-    };
-    let result = solve(
-        settings,
-        &[
+        expected_quality: 2955,
+    },
+    RegressionCase {
+        settings: Settings {
+            max_cp: 700,
+            max_durability: 70,
+            max_progress: 2500,
+            max_quality: 5000,
+            ..BASE_SETTINGS
+        },
+        actions: &[
             Action::MuscleMemory,
             Action::Manipulation,
             Action::Veneration,
@@ -112,30 +107,18 @@ fn test_02() {
             Action::Groundwork,
             Action::Groundwork,
         ],
-    );
-    assert_eq!(result, 4693);
-}
-
-#[test]
-fn test_adversarial_02() {
-    let settings = Settings {
-        max_cp: 700,
-        max_durability: 70,
-        max_progress: 2500,
-        max_quality: 5000,
-        base_progress: 100,
-        base_quality: 100,
-        job_level: 90,
-        allowed_actions: ActionMask::all()
-            .remove(Action::TrainedEye)
-            .remove(Action::HeartAndSoul)
-            .remove(Action::QuickInnovation),
-        adversarial: true,
-        backload_progress: false,
-    };
-    let result = solve(
-        settings,
-        &[
+        expected_quality: 4693,
+    },
+    RegressionCase {
+        settings: Settings {
+            max_cp: 700,
+            max_durability: 70,
+            max_progress: 2500,
+            max_quality: 5000,
+            adversarial: true,
+            ..BASE_SETTINGS
+        },
+        actions: &[
             Action::MuscleMemory,
             Action::Manipulation,
             Action::Veneration,
@@ -143,30 +126,17 @@ fn test_adversarial_02() {
             Action::Groundwork,
             Action::Groundwork,
         ],
-    );
-    assert_eq!(result, 3975);
-}
-
-#[test]
-fn test_03() {
-    let settings = Settings {
-        max_cp: 617,
-        max_durability: 60,
-        max_progress: 2120,
-        max_quality: 5000,
-        base_progress: 100,
-        base_quality: 100,
-        job_level: 90,
-        allowed_actions: ActionMask::all()
-            .remove(Action::TrainedEye)
-            .remove(Action::HeartAndSoul)
-            .remove(Action::QuickInnovation),
-        adversarial: false,
-        backload_progress: false,
-    };
-    let result = solve(
-        settings,
-        &[
+        expected_quality: 3975,
+    },
+    RegressionCase {
+        settings: Settings {
+            max_cp: 617,
+            max_durability: 60,
+            max_progress: 2120,
+            max_quality: 5000,
+            ..BASE_SETTINGS
+        },
+        actions: &[
             Action::MuscleMemory,
             Action::Manipulation,
             Action::Veneration,
@@ -179,30 +149,18 @@ fn test_03() {
             Action::BasicTouch,
             Action::StandardTouch,
         ],
-    );
-    assert_eq!(result, 4004);
-}
-
-#[test]
-fn test_adversarial_03() {
-    let settings = Settings {
-        max_cp: 617,
-        max_durability: 60,
-        max_progress: 2120,
-        max_quality: 5000,
-        base_progress: 100,
-        base_quality: 100,
-        job_level: 90,
-        allowed_actions: ActionMask::all()
-            .remove(Action::TrainedEye)
-            .remove(Action::HeartAndSoul)
-            .remove(Action::QuickInnovation),
-        adversarial: true,
-        backload_progress: false,
-    };
-    let result = solve(
-        settings,
-        &[
+        expected_quality: 4004,
+    },
+    RegressionCase {
+        settings: Settings {
+            max_cp: 617,
+            max_durability: 60,
+            max_progress: 2120,
+            max_quality: 5000,
+            adversarial: true,
+            ..BASE_SETTINGS
+        },
+        actions: &[
             Action::MuscleMemory,
             Action::Manipulation,
             Action::Veneration,
@@ -215,180 +173,311 @@ fn test_adversarial_03() {
             Action::BasicTouch,
             Action::StandardTouch,
         ],
+        expected_quality: 3376,
+    },
+    RegressionCase {
+        settings: Settings {
+            max_cp: 411,
+            max_durability: 60,
+            max_progress: 1990,
+            max_quality: 5000,
+            ..BASE_SETTINGS
+        },
+        actions: &[Action::MuscleMemory],
+        expected_quality: 2075,
+    },
+    RegressionCase {
+        settings: Settings {
+            max_cp: 411,
+            max_durability: 60,
+            max_progress: 1990,
+            max_quality: 5000,
+            adversarial: true,
+            ..BASE_SETTINGS
+        },
+        actions: &[Action::MuscleMemory],
+        expected_quality: 1888,
+    },
+    RegressionCase {
+        settings: Settings {
+            max_cp: 450,
+            max_durability: 60,
+            max_progress: 1970,
+            max_quality: 2000,
+            ..BASE_SETTINGS
+        },
+        actions: &[Action::MuscleMemory],
+        expected_quality: 2000,
+    },
+    RegressionCase {
+        settings: Settings {
+            max_cp: 450,
+            max_durability: 60,
+            max_progress: 1970,
+            max_quality: 2000,
+            adversarial: true,
+            ..BASE_SETTINGS
+        },
+        actions: &[Action::MuscleMemory],
+        expected_quality: 2000,
+    },
+    RegressionCase {
+        settings: Settings {
+            max_cp: 673,
+            max_durability: 60,
+            max_progress: 2345,
+            max_quality: 8000,
+            ..BASE_SETTINGS
+        },
+        actions: &[Action::MuscleMemory],
+        expected_quality: 4438,
+    },
+    RegressionCase {
+        settings: Settings {
+            max_cp: 673,
+            max_durability: 60,
+            max_progress: 2345,
+            max_quality: 8000,
+            adversarial: true,
+            ..BASE_SETTINGS
+        },
+        actions: &[Action::MuscleMemory],
+        expected_quality: 3745,
+    },
+    RegressionCase {
+        settings: Settings {
+            max_cp: 673,
+            max_durability: 60,
+            max_progress: 2345,
+            max_quality: 8000,
+            ..BASE_SETTINGS
+        },
+        actions: &[Action::Reflect],
+        expected_quality: 4449,
+    },
+    RegressionCase {
+        settings: Settings {
+            max_cp: 673,
+            max_durability: 60,
+            max_progress: 2345,
+            max_quality: 8000,
+            adversarial: true,
+            ..BASE_SETTINGS
+        },
+        actions: &[Action::Reflect],
+        expected_quality: 3769,
+    },
+    RegressionCase {
+        settings: Settings {
+            max_cp: 32,
+            max_durability: 10,
+            max_progress: 10000,
+            max_quality: 20000,
+            base_progress: 10000,
+            base_quality: 10000,
+            ..BASE_SETTINGS
+        },
+        actions: &[Action::PrudentTouch],
+        expected_quality: 10000,
+    },
+    RegressionCase {
+        settings: Settings {
+            max_cp: 32,
+            max_durability: 10,
+            max_progress: 10000,
+            max_quality: 20000,
+            base_progress: 10000,
+            base_quality: 10000,
+            adversarial: true,
+            ..BASE_SETTINGS
+        },
+        actions: &[Action::PrudentTouch],
+        expected_quality: 10000,
+    },
+    RegressionCase {
+        settings: Settings {
+            max_cp: 700,
+            max_durability: 70,
+            max_progress: 2500,
+            max_quality: 40000,
+            allowed_actions: BASE_SETTINGS.allowed_actions.remove(Action::Manipulation),
+            ..BASE_SETTINGS
+        },
+        actions: &[],
+        expected_quality: 4079,
+    },
+    RegressionCase {
+        settings: Settings {
+            max_cp: 700,
+            max_durability: 70,
+            max_progress: 2500,
+            max_quality: 40000,
+            allowed_actions: BASE_SETTINGS.allowed_actions.remove(Action::Manipulation),
+            adversarial: true,
+            ..BASE_SETTINGS
+        },
+        actions: &[],
+        expected_quality: 3469,
+    },
+    RegressionCase {
+        settings: Settings {
+            max_cp: 400,
+            max_durability: 80,
+            max_progress: 1200,
+            max_quality: 24000,
+            job_level: 100,
+            allowed_actions: BASE_SETTINGS.allowed_actions.remove(Action::Manipulation),
+            ..BASE_SETTINGS
+        },
+        actions: &[],
+        expected_quality: 3929,
+    },
+    RegressionCase {
+        settings: Settings {
+            max_cp: 400,
+            max_durability: 80,
+            max_progress: 1200,
+            max_quality: 24000,
+            job_level: 100,
+            allowed_actions: BASE_SETTINGS.allowed_actions.remove(Action::Manipulation),
+            adversarial: true,
+            ..BASE_SETTINGS
+        },
+        actions: &[],
+        expected_quality: 3440,
+    },
+    RegressionCase {
+        settings: Settings {
+            max_cp: 320,
+            max_durability: 80,
+            max_progress: 1600,
+            max_quality: 24000,
+            job_level: 100,
+            allowed_actions: BASE_SETTINGS.allowed_actions.remove(Action::Manipulation),
+            ..BASE_SETTINGS
+        },
+        actions: &[],
+        expected_quality: 2481,
+    },
+    RegressionCase {
+        settings: Settings {
+            max_cp: 320,
+            max_durability: 80,
+            max_progress: 1600,
+            max_quality: 24000,
+            job_level: 100,
+            allowed_actions: BASE_SETTINGS.allowed_actions.remove(Action::Manipulation),
+            adversarial: true,
+            ..BASE_SETTINGS
+        },
+        actions: &[],
+        expected_quality: 2286,
+    },
+];
+
+fn assert_solve_matches(settings: Settings, actions: &[Action], expected_quality: u32) {
+    let result = solve(settings, actions);
+    assert_eq!(
+        result, expected_quality,
+        "settings: {settings:?}, actions: {actions:?}"
     );
-    assert_eq!(result, 3376);
 }
 
 #[test]
-fn test_04() {
-    let settings = Settings {
-        max_cp: 411,
-        max_durability: 60,
-        max_progress: 1990,
-        max_quality: 5000,
-        base_progress: 100,
-        base_quality: 100,
-        job_level: 90,
-        allowed_actions: ActionMask::all()
-            .remove(Action::TrainedEye)
-            .remove(Action::HeartAndSoul)
-            .remove(Action::QuickInnovation),
-        adversarial: false,
-        backload_progress: false,
-    };
-    let result = solve(settings, &[Action::MuscleMemory]);
-    assert_eq!(result, 2075);
+fn test_regression_cases() {
+    for case in REGRESSION_CASES {
+        assert_solve_matches(case.settings, case.actions, case.expected_quality);
+    }
 }
 
 #[test]
-fn test_adversarial_04() {
-    let settings = Settings {
-        max_cp: 411,
-        max_durability: 60,
-        max_progress: 1990,
-        max_quality: 5000,
-        base_progress: 100,
-        base_quality: 100,
-        job_level: 90,
-        allowed_actions: ActionMask::all()
-            .remove(Action::TrainedEye)
-            .remove(Action::HeartAndSoul)
-            .remove(Action::QuickInnovation),
-        adversarial: true,
-        backload_progress: false,
+fn test_max_possible_quality() {
+    // first regression case (test_01)
+    let case = &REGRESSION_CASES[0];
+    assert_eq!(case.expected_quality, 3352);
+    let solver_settings = SolverSettings {
+        simulator_settings: case.settings,
     };
-    let result = solve(settings, &[Action::MuscleMemory]);
-    assert_eq!(result, 1888);
+    let result = super::max_possible_quality(solver_settings).unwrap();
+    // the bound from the start of the craft cannot be lower than the bound after any sequence of actions
+    assert!(result >= case.expected_quality);
+    assert_eq!(result, 3445);
 }
 
 #[test]
-fn test_05() {
+fn test_restrict_actions() {
     let settings = Settings {
-        max_cp: 450,
+        max_cp: 411,
         max_durability: 60,
-        max_progress: 1970,
-        max_quality: 2000,
-        base_progress: 100,
-        base_quality: 100,
-        job_level: 90,
-        allowed_actions: ActionMask::all()
-            .remove(Action::TrainedEye)
-            .remove(Action::HeartAndSoul)
-            .remove(Action::QuickInnovation),
-        adversarial: false,
-        backload_progress: false,
+        max_progress: 1990,
+        max_quality: 5000,
+        ..BASE_SETTINGS
     };
-    let result = solve(settings, &[Action::MuscleMemory]);
-    assert_eq!(result, 2000);
-}
+    let allowed_actions = settings
+        .allowed_actions
+        .remove(Action::PreparatoryTouch)
+        .remove(Action::GreatStrides);
+    let mut state = SimulationState::from_macro(&settings, &[Action::MuscleMemory]).unwrap();
+    state.effects.set_combo(Combo::None);
 
-#[test]
-fn test_adversarial_05() {
-    let settings = Settings {
-        max_cp: 450,
-        max_durability: 60,
-        max_progress: 1970,
-        max_quality: 2000,
-        base_progress: 100,
-        base_quality: 100,
-        job_level: 90,
-        allowed_actions: ActionMask::all()
-            .remove(Action::TrainedEye)
-            .remove(Action::HeartAndSoul)
-            .remove(Action::QuickInnovation),
-        adversarial: true,
-        backload_progress: false,
-    };
-    let result = solve(settings, &[Action::MuscleMemory]);
-    assert_eq!(result, 2000);
-}
+    let mut solver = QualityUbSolver::new(
+        SolverSettings {
+            simulator_settings: settings,
+        },
+        AtomicFlag::new(),
+    );
+    let unrestricted_bound = solver.quality_upper_bound(state).unwrap();
+    assert_eq!(unrestricted_bound, 2075); // same as the corresponding regression case
+    solver.restrict_actions(allowed_actions);
+    let restricted_bound = solver.quality_upper_bound(state).unwrap();
 
-#[test]
-fn test_06() {
-    let settings = Settings {
-        max_cp: 673,
-        max_durability: 60,
-        max_progress: 2345,
-        max_quality: 8000,
-        base_progress: 100,
-        base_quality: 100,
-        job_level: 90,
-        allowed_actions: ActionMask::all()
-            .remove(Action::TrainedEye)
-            .remove(Action::HeartAndSoul)
-            .remove(Action::QuickInnovation),
-        adversarial: false,
-        backload_progress: false,
+    let restricted_settings = Settings {
+        allowed_actions,
+        ..settings
     };
-    let result = solve(settings, &[Action::MuscleMemory]);
-    assert_eq!(result, 4438);
-}
+    let mut fresh_solver = QualityUbSolver::new(
+        SolverSettings {
+            simulator_settings: restricted_settings,
+        },
+        AtomicFlag::new(),
+    );
+    let fresh_bound = fresh_solver.quality_upper_bound(state).unwrap();
 
-#[test]
-fn test_adversarial_06() {
-    let settings = Settings {
-        max_cp: 673,
-        max_durability: 60,
-        max_progress: 2345,
-        max_quality: 8000,
-        base_progress: 100,
-        base_quality: 100,
-        job_level: 90,
-        allowed_actions: ActionMask::all()
-            .remove(Action::TrainedEye)
-            .remove(Action::HeartAndSoul)
-            .remove(Action::QuickInnovation),
-        adversarial: true,
-        backload_progress: false,
-    };
-    let result = solve(settings, &[Action::MuscleMemory]);
-    assert_eq!(result, 3745);
+    assert!(restricted_bound <= unrestricted_bound);
+    assert!(restricted_bound >= fresh_bound);
+    assert!(fresh_bound < unrestricted_bound);
 }
 
 #[test]
-fn test_07() {
+fn test_manipulation_refund() {
+    // https://github.com/KonaeAkira/raphael-rs/pull/128#discussion_r2062585163
     let settings = Settings {
-        max_cp: 673,
-        max_durability: 60,
-        max_progress: 2345,
-        max_quality: 8000,
+        max_cp: 500,
+        max_durability: 80,
+        max_progress: 700,
+        max_quality: 20000,
         base_progress: 100,
         base_quality: 100,
-        job_level: 90,
-        allowed_actions: ActionMask::all()
-            .remove(Action::TrainedEye)
-            .remove(Action::HeartAndSoul)
-            .remove(Action::QuickInnovation),
-        adversarial: false,
-        backload_progress: false,
-    };
-    let result = solve(settings, &[Action::Reflect]);
-    assert_eq!(result, 4449);
-}
-
-#[test]
-fn test_08() {
-    let settings = Settings {
-        max_cp: 32,
-        max_durability: 10,
-        max_progress: 10000,
-        max_quality: 20000,
-        base_progress: 10000,
-        base_quality: 10000,
-        job_level: 90,
+        job_level: 100,
         allowed_actions: ActionMask::all()
             .remove(Action::TrainedEye)
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
-    let result = solve(settings, &[Action::PrudentTouch]);
-    assert_eq!(result, 10000);
+    let result = solve(settings, &[Action::Manipulation]);
+    assert_eq!(result, 4975);
 }
 
 #[test]
-fn test_09() {
+fn test_manipulation_not_allowed() {
+    // The bound must not assume any durability restoration from Manipulation when Manipulation is not allowed
     let settings = Settings {
         max_cp: 700,
         max_durability: 70,
@@ -398,81 +487,73 @@ fn test_09() {
         base_quality: 100,
         job_level: 90,
         allowed_actions: ActionMask::all()
-            .remove(Action::Manipulation)
-            .remove(Action::TrainedEye)
-            .remove(Action::HeartAndSoul)
-            .remove(Action::QuickInnovation),
-        adversarial: false,
-        backload_progress: false,
-    };
-    let result = solve(settings, &[]);
-    assert_eq!(result, 4079);
-}
-
-#[test]
-fn test_10() {
-    let settings = Settings {
-        max_cp: 400,
-        max_durability: 80,
-        max_progress: 1200,
-        max_quality: 24000,
-        base_progress: 100,
-        base_quality: 100,
-        job_level: 100,
-        allowed_actions: ActionMask::all()
-            .remove(Action::Manipulation)
             .remove(Action::TrainedEye)
             .remove(Action::HeartAndSoul)
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
-    let result = solve(settings, &[]);
-    assert_eq!(result, 3929);
-}
-
-#[test]
-fn test_11() {
-    let settings = Settings {
-        max_cp: 320,
-        max_durability: 80,
-        max_progress: 1600,
-        max_quality: 24000,
-        base_progress: 100,
-        base_quality: 100,
-        job_level: 100,
-        allowed_actions: ActionMask::all()
-            .remove(Action::Manipulation)
-            .remove(Action::TrainedEye)
-            .remove(Action::HeartAndSoul)
-            .remove(Action::QuickInnovation),
-        adversarial: false,
-        backload_progress: false,
+    let bound_with_manipulation = solve(settings, &[]);
+    let settings_without_manipulation = Settings {
+        allowed_actions: settings.allowed_actions.remove(Action::Manipulation),
+        ..settings
     };
-    let result = solve(settings, &[]);
-    assert_eq!(result, 2481);
+    let bound_without_manipulation = solve(settings_without_manipulation, &[]);
+    assert_eq!(bound_without_manipulation, 4079); // same as the corresponding regression case
+    assert!(bound_without_manipulation < bound_with_manipulation);
 }
 
 #[test]
-fn test_manipulation_refund() {
-    // https://github.com/KonaeAkira/raphael-rs/pull/128#discussion_r2062585163
-    let settings = Settings {
-        max_cp: 500,
-        max_durability: 80,
-        max_progress: 700,
-        max_quality: 20000,
-        base_progress: 100,
-        base_quality: 100,
-        job_level: 100,
-        allowed_actions: ActionMask::all()
-            .remove(Action::TrainedEye)
-            .remove(Action::HeartAndSoul)
-            .remove(Action::QuickInnovation),
-        adversarial: false,
-        backload_progress: false,
-    };
-    let result = solve(settings, &[Action::Manipulation]);
-    assert_eq!(result, 4975);
+fn test_master_mend_refund() {
+    // Master Mend is the only way to restore durability. With a max durability of 25 it can restore at most 20 durability
+    // instead of its nominal 30, so the CP refund per 5 durability must not be based on the full 30.
+    for max_durability in [20, 25, 35] {
+        let simulator_settings = Settings {
+            max_cp: 350,
+            max_durability,
+            max_progress: 1200,
+            max_quality: 20000,
+            base_progress: 100,
+            base_quality: 100,
+            job_level: 90,
+            allowed_actions: ActionMask::all()
+                .remove(Action::TrainedEye)
+                .remove(Action::HeartAndSoul)
+                .remove(Action::QuickInnovation)
+                .remove(Action::Manipulation),
+            adversarial: false,
+            backload_progress: false,
+            quality_increase_cap: None,
+            progress_target: None,
+            progress_optional: false,
+            rounding_mode: RoundingMode::Floor,
+            cp_cost_overrides: CpCostOverrides::none(),
+        };
+        let solver_settings = SolverSettings { simulator_settings };
+        let upper_bound = super::max_possible_quality(solver_settings).unwrap();
+        let actions = crate::MacroSolver::new(
+            solver_settings,
+            Box::new(|_| {}),
+            Box::new(|_| {}),
+            AtomicFlag::new(),
+        )
+        .solve()
+        .unwrap();
+        let quality = SimulationState::from_macro(&simulator_settings, &actions)
+            .unwrap()
+            .quality;
+        assert!(
+            upper_bound >= quality,
+            "max_durability {max_durability}: bound {upper_bound} < quality {quality}"
+        );
+        // the case is only meaningful if the optimal rotation actually relies on Master Mend
+        assert!(actions.contains(&Action::MasterMend));
+    }
 }
 
 #[test]
@@ -493,6 +574,11 @@ fn test_issue_113() {
             .remove(Action::QuickInnovation),
         adversarial: true,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let solver_settings = SolverSettings { simulator_settings };
     let mut solver = QualityUbSolver::new(solver_settings, Default::default());
@@ -522,6 +608,11 @@ fn test_issue_118() {
             .remove(Action::QuickInnovation),
         adversarial: true,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let solver_settings = SolverSettings { simulator_settings };
     let mut solver = QualityUbSolver::new(solver_settings, Default::default());
@@ -535,48 +626,140 @@ fn test_issue_118() {
     expected_runtime_stats.assert_debug_eq(&solver.runtime_stats());
 }
 
-fn random_effects(settings: &Settings) -> Effects {
+#[test]
+fn test_estimated_memory_bytes() {
+    // Same settings as `test_issue_118`
+    let simulator_settings = Settings {
+        max_cp: 614,
+        max_durability: 20,
+        max_progress: 2310,
+        max_quality: 8400,
+        base_progress: 205,
+        base_quality: 240,
+        job_level: 100,
+        allowed_actions: ActionMask::all()
+            .remove(Action::TrainedEye)
+            .remove(Action::HeartAndSoul)
+            .remove(Action::QuickInnovation),
+        adversarial: true,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    let solver_settings = SolverSettings { simulator_settings };
+    let estimated_bytes = QualityUbSolver::estimated_memory_bytes(solver_settings);
+    let mut solver = QualityUbSolver::new(solver_settings, AtomicFlag::new());
+    // The precompute is skipped on a single thread
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(2)
+        .build()
+        .unwrap()
+        .install(|| solver.precompute());
+    let runtime_stats = solver.runtime_stats();
+    let observed_bytes = runtime_stats.states
+        * (std::mem::size_of::<(super::state::ReducedState, Box<[ParetoValue<u32, u32>]>)>() + 1)
+        + runtime_stats.pareto_values * std::mem::size_of::<ParetoValue<u32, u32>>();
+    assert!(estimated_bytes <= 2 * observed_bytes);
+    assert!(observed_bytes <= 2 * estimated_bytes);
+}
+
+#[test]
+fn test_precompute_stops_at_quality_target() {
+    let simulator_settings = Settings {
+        max_cp: 400,
+        max_durability: 60,
+        max_progress: 2000,
+        max_quality: 20000,
+        ..BASE_SETTINGS
+    };
+    let target_settings = Settings {
+        max_quality: 1000,
+        ..simulator_settings
+    };
+    let precompute = |simulator_settings: Settings| {
+        let solver_settings = SolverSettings { simulator_settings };
+        let mut solver = QualityUbSolver::new(solver_settings, AtomicFlag::new());
+        // The precompute is skipped on a single thread
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(2)
+            .build()
+            .unwrap()
+            .install(|| solver.precompute());
+        solver
+    };
+    let mut full_solver = precompute(simulator_settings);
+    let mut target_solver = precompute(target_settings);
+    assert!(target_solver.runtime_stats().states < full_solver.runtime_stats().states);
+    // The bounds capped at the Quality target are the same as the full bounds capped at the Quality target
+    let mut rng = StdRng::seed_from_u64(42);
+    for _ in 0..1000 {
+        let state = random_state(&mut rng, &simulator_settings);
+        let full_bound = full_solver.quality_upper_bound(state).unwrap();
+        let target_bound = target_solver.quality_upper_bound(state).unwrap();
+        assert_eq!(
+            target_bound,
+            std::cmp::min(full_bound, u32::from(target_settings.max_quality))
+        );
+    }
+}
+
+fn random_effects(rng: &mut impl Rng, settings: &Settings) -> Effects {
     Effects::new()
-        .with_inner_quiet(rand::thread_rng().gen_range(0..=10))
-        .with_great_strides(rand::thread_rng().gen_range(0..=3))
-        .with_innovation(rand::thread_rng().gen_range(0..=4))
-        .with_veneration(rand::thread_rng().gen_range(0..=4))
-        .with_waste_not(rand::thread_rng().gen_range(0..=8))
-        .with_manipulation(rand::thread_rng().gen_range(0..=8))
-        .with_quick_innovation_available(rand::random())
+        .with_inner_quiet(rng.gen_range(0..=10))
+        .with_great_strides(rng.gen_range(0..=3))
+        .with_innovation(rng.gen_range(0..=4))
+        .with_veneration(rng.gen_range(0..=4))
+        .with_waste_not(rng.gen_range(0..=8))
+        .with_manipulation(rng.gen_range(0..=8))
+        .with_quick_innovation_available(rng.gen_bool(0.5))
         .with_adversarial_guard(if settings.adversarial {
-            rand::random()
+            rng.gen_bool(0.5)
         } else {
             false
         })
         .with_allow_quality_actions(if settings.backload_progress {
-            rand::random()
+            rng.gen_bool(0.5)
         } else {
             true
         })
 }
 
-fn random_state(settings: &Settings) -> SimulationState {
+fn random_state(rng: &mut impl Rng, settings: &Settings) -> SimulationState {
     SimulationState {
-        cp: rand::thread_rng().gen_range(0..=settings.max_cp),
-        durability: rand::thread_rng().gen_range(1..=(settings.max_durability / 5)) * 5,
-        progress: rand::thread_rng().gen_range(0..u32::from(settings.max_progress)),
+        cp: rng.gen_range(0..=settings.max_cp),
+        durability: rng.gen_range(1..=(settings.max_durability / 5)) * 5,
+        progress: rng.gen_range(0..u32::from(settings.max_progress)),
         quality: 0,
         unreliable_quality: 0,
-        effects: random_effects(settings),
+        effects: random_effects(rng, settings),
     }
     .try_into()
     .unwrap()
 }
 
+/// Seed of the fuzz tests. Set `RAPHAEL_FUZZ_SEED` to replay a failed run with the seed it printed.
+fn fuzz_seed() -> u64 {
+    std::env::var("RAPHAEL_FUZZ_SEED").map_or_else(|_| rand::random(), |seed| seed.parse().unwrap())
+}
+
+fn monotonic_fuzz_check(simulator_settings: Settings) {
+    monotonic_fuzz_check_with_seed(simulator_settings, fuzz_seed());
+}
+
 /// Test that the upper-bound solver is monotonic,
 /// i.e. the quality UB of a state is never less than the quality UB of any of its children.
-fn monotonic_fuzz_check(simulator_settings: Settings) {
+fn monotonic_fuzz_check_with_seed(simulator_settings: Settings, seed: u64) {
+    // Only shown if the test fails
+    eprintln!("RAPHAEL_FUZZ_SEED={seed}");
+    let mut rng = StdRng::seed_from_u64(seed);
     let solver_settings = SolverSettings { simulator_settings };
     let mut solver = QualityUbSolver::new(solver_settings, Default::default());
     solver.precompute();
     for _ in 0..100000 {
-        let state = random_state(&simulator_settings);
+        let state = random_state(&mut rng, &simulator_settings);
         let state_upper_bound = solver.quality_upper_bound(state).unwrap();
         for action in FULL_SEARCH_ACTIONS {
             let child_upper_bound = match use_action_combo(&solver_settings, state, *action) {
@@ -597,6 +780,35 @@ fn monotonic_fuzz_check(simulator_settings: Settings) {
     }
 }
 
+#[test]
+fn test_fuzz_seed_is_reproducible() {
+    let settings = Settings {
+        max_cp: 360,
+        max_durability: 70,
+        max_progress: 1000,
+        max_quality: 20000,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 100,
+        allowed_actions: ActionMask::all(),
+        adversarial: true,
+        backload_progress: true,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    let random_states = |seed: u64| -> Vec<SimulationState> {
+        let mut rng = StdRng::seed_from_u64(seed);
+        (0..100)
+            .map(|_| random_state(&mut rng, &settings))
+            .collect()
+    };
+    assert_eq!(random_states(42), random_states(42));
+    assert_ne!(random_states(42), random_states(43));
+}
+
 #[test]
 fn test_monotonic_normal_sim() {
     let settings = Settings {
@@ -610,6 +822,11 @@ fn test_monotonic_normal_sim() {
         allowed_actions: ActionMask::all(),
         adversarial: false,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     monotonic_fuzz_check(settings);
 }
@@ -627,6 +844,11 @@ fn test_monotonic_backload_progress_sim() {
         allowed_actions: ActionMask::all(),
         adversarial: false,
         backload_progress: true,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     monotonic_fuzz_check(settings);
 }
@@ -645,6 +867,11 @@ fn test_monotonic_adversarial_sim() {
         allowed_actions: ActionMask::all(),
         adversarial: true,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     monotonic_fuzz_check(settings);
 }
@@ -0,0 +1,44 @@
+use raphael_sim::{Action, SimulationState};
+
+use crate::SolverSettings;
+
+/// The parts of the final state that make up the outcome of a rotation.
+fn outcome(settings: &SolverSettings, actions: &[Action]) -> Option<(u32, u32, u32, u16, u16)> {
+    let state = SimulationState::from_macro(&settings.simulator_settings, actions).ok()?;
+    Some((
+        state.progress,
+        state.quality,
+        state.unreliable_quality,
+        state.cp,
+        state.durability,
+    ))
+}
+
+/// Moves each buff action as late as possible, so that buffs end up right before the actions they boost.
+/// A buff is only moved if the final Progress, Quality, CP and durability stay exactly the same.
+/// Rotations that cannot be used from the start of the craft are returned unchanged.
+pub fn canonicalize_rotation(settings: &SolverSettings, mut actions: Vec<Action>) -> Vec<Action> {
+    let Some(target_outcome) = outcome(settings, &actions) else {
+        return actions;
+    };
+    for index in (0..actions.len()).rev() {
+        if !actions[index].is_buff() {
+            continue;
+        }
+        let mut position = index;
+        while position + 1 < actions.len() {
+            actions.swap(position, position + 1);
+            if outcome(settings, &actions) != Some(target_outcome) {
+                actions.swap(position, position + 1);
+                break;
+            }
+            position += 1;
+        }
+        // Moving past other buffs without reaching a new action only shuffles the buffs
+        while position > index && actions[position - 1].is_buff() {
+            actions.swap(position - 1, position);
+            position -= 1;
+        }
+    }
+    actions
+}
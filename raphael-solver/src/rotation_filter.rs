@@ -0,0 +1,52 @@
+use raphael_sim::{Action, SimulationState};
+
+use crate::SolverSettings;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct RotationScore {
+    capped_quality: u32,
+    steps: usize,
+    cp_cost: u16,
+}
+
+impl RotationScore {
+    fn dominates(&self, other: &Self) -> bool {
+        self.capped_quality >= other.capped_quality
+            && self.steps <= other.steps
+            && self.cp_cost <= other.cp_cost
+            && self != other
+    }
+}
+
+/// Keeps only the rotations that are not dominated by another rotation,
+/// i.e. for which no other rotation reaches at least as much Quality with at most as many steps and at most as much CP.
+/// Quality is capped at the Quality target. Of several rotations with the same score, only the first is kept.
+/// Rotations that cannot be used from the start of the craft are removed.
+pub fn pareto_optimal_rotations(
+    settings: &SolverSettings,
+    rotations: Vec<Vec<Action>>,
+) -> Vec<Vec<Action>> {
+    let scored_rotations: Vec<(RotationScore, Vec<Action>)> = rotations
+        .into_iter()
+        .filter_map(|actions| {
+            let state = SimulationState::from_macro(&settings.simulator_settings, &actions).ok()?;
+            let score = RotationScore {
+                capped_quality: std::cmp::min(state.quality, settings.max_quality()),
+                steps: actions.len(),
+                cp_cost: settings.max_cp() - state.cp,
+            };
+            Some((score, actions))
+        })
+        .collect();
+    let mut result: Vec<(RotationScore, Vec<Action>)> = Vec::new();
+    for (score, actions) in &scored_rotations {
+        let dominated = scored_rotations
+            .iter()
+            .any(|(other_score, _)| other_score.dominates(score));
+        let duplicate = result.iter().any(|(other_score, _)| other_score == score);
+        if !dominated && !duplicate {
+            result.push((*score, actions.clone()));
+        }
+    }
+    result.into_iter().map(|(_, actions)| actions).collect()
+}
@@ -0,0 +1,94 @@
+use raphael_sim::{Action, SimulationState};
+
+use crate::{AtomicFlag, MacroSolver, SolverException, SolverSettings};
+
+/// Solves with `target_quality` as the Quality target and returns the rotation if it reaches the target.
+fn solve_for_target(
+    mut settings: SolverSettings,
+    target_quality: u16,
+) -> Result<Option<Vec<Action>>, SolverException> {
+    settings.simulator_settings.max_quality = target_quality;
+    let mut solver = MacroSolver::new(
+        settings,
+        Box::new(|_| {}),
+        Box::new(|_| {}),
+        AtomicFlag::new(),
+    );
+    let actions = match solver.solve() {
+        Ok(actions) => actions,
+        Err(SolverException::NoSolution) => return Ok(None),
+        Err(exception) => return Err(exception),
+    };
+    let state = SimulationState::from_macro(&settings.simulator_settings, &actions)
+        .map_err(|(_, err)| SolverException::InternalError(err.to_owned()))?;
+    match state.quality >= u32::from(target_quality) {
+        true => Ok(Some(actions)),
+        false => Ok(None),
+    }
+}
+
+/// Finds the lowest Control with which `target_quality` can be reached, searching Control values up to `max_control`.
+///
+/// `settings_for` maps a Control value to the corresponding solver settings, with all other stats held fixed.
+/// Reachable Quality is assumed to be non-decreasing in Control, which allows a binary search over repeated solves.
+/// Returns `None` if the target cannot be reached even with `max_control`.
+pub fn min_control_for_target(
+    settings_for: impl Fn(u16) -> SolverSettings,
+    target_quality: u16,
+    max_control: u16,
+) -> Result<Option<u16>, SolverException> {
+    let reaches_target = |control: u16| -> Result<bool, SolverException> {
+        Ok(solve_for_target(settings_for(control), target_quality)?.is_some())
+    };
+    if !reaches_target(max_control)? {
+        return Ok(None);
+    }
+    let (mut lo, mut hi) = (0, max_control);
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if reaches_target(mid)? {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+    Ok(Some(lo))
+}
+
+/// Finds the rotation with the lowest CP budget that reaches each of the three collectability tiers.
+/// `tiers` are the Quality targets of the tiers in ascending order. `None` means that the tier cannot be reached with the CP of `settings`.
+///
+/// The CP budget is found with a binary search over repeated solves. The budget of a tier is a lower bound
+/// for the budget of the next tier, so the search range shrinks with each tier.
+pub fn solve_collectable_tiers(
+    settings: SolverSettings,
+    tiers: [u16; 3],
+) -> Result<[Option<Vec<Action>>; 3], SolverException> {
+    let settings_for = |max_cp: u16| {
+        let mut settings = settings;
+        settings.simulator_settings.max_cp = max_cp;
+        settings
+    };
+    let mut results = [None, None, None];
+    let mut min_cp = 0;
+    for (result, target_quality) in results.iter_mut().zip(tiers) {
+        let Some(mut actions) = solve_for_target(settings, target_quality)? else {
+            // Higher tiers cannot be reached either
+            break;
+        };
+        let (mut lo, mut hi) = (min_cp, settings.max_cp());
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            match solve_for_target(settings_for(mid), target_quality)? {
+                Some(mid_actions) => {
+                    actions = mid_actions;
+                    hi = mid;
+                }
+                None => lo = mid + 1,
+            }
+        }
+        min_cp = lo;
+        *result = Some(actions);
+    }
+    Ok(results)
+}
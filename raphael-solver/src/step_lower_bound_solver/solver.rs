@@ -14,6 +14,7 @@ type ParetoFrontBuilder = utils::ParetoFrontBuilder<u32, u32>;
 type SolvedStates = rustc_hash::FxHashMap<ReducedState, Box<[ParetoValue]>>;
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StepLbSolverStats {
     pub states: usize,
     pub pareto_values: usize,
@@ -24,10 +25,25 @@ pub struct StepLbSolver {
     interrupt_signal: utils::AtomicFlag,
     solved_states: SolvedStates,
     pareto_front_builder: ParetoFrontBuilder,
+    // bound the total duration of the remaining actions instead of the number of steps
+    measure_duration: bool,
 }
 
 impl StepLbSolver {
-    pub fn new(mut settings: SolverSettings, interrupt_signal: utils::AtomicFlag) -> Self {
+    pub fn new(settings: SolverSettings, interrupt_signal: utils::AtomicFlag) -> Self {
+        Self::with_measure(settings, interrupt_signal, false)
+    }
+
+    /// Same as `new`, but `step_lower_bound` returns a lower bound on the total duration (in seconds) of the remaining actions.
+    pub fn new_duration_lb(settings: SolverSettings, interrupt_signal: utils::AtomicFlag) -> Self {
+        Self::with_measure(settings, interrupt_signal, true)
+    }
+
+    fn with_measure(
+        mut settings: SolverSettings,
+        interrupt_signal: utils::AtomicFlag,
+        measure_duration: bool,
+    ) -> Self {
         ReducedState::optimize_action_mask(&mut settings.simulator_settings);
         Self {
             settings,
@@ -37,6 +53,22 @@ impl StepLbSolver {
                 settings.max_progress(),
                 settings.max_quality(),
             ),
+            measure_duration,
+        }
+    }
+
+    fn action_cost(&self, action: ActionCombo) -> u8 {
+        match self.measure_duration {
+            true => action.duration(),
+            false => action.steps(),
+        }
+    }
+
+    /// Maximum number of steps that can be taken with `budget`. Every action takes at least 2 seconds.
+    fn max_steps(&self, budget: NonZeroU8) -> NonZeroU8 {
+        match self.measure_duration {
+            true => NonZeroU8::new(std::cmp::max(1, budget.get() / 2)).unwrap(),
+            false => budget,
         }
     }
 
@@ -53,7 +85,11 @@ impl StepLbSolver {
             .quality_upper_bound(state, hint)?
             .is_none_or(|quality_ub| quality_ub < self.settings.max_quality())
         {
-            hint = hint.checked_add(1).unwrap();
+            // Quality target cannot be reached in any number of steps, e.g. because of a low `quality_increase_cap`
+            let Some(next_hint) = hint.checked_add(1) else {
+                return Ok(u8::MAX);
+            };
+            hint = next_hint;
         }
         Ok(hint.get())
     }
@@ -70,8 +106,9 @@ impl StepLbSolver {
             )));
         }
 
-        let reduced_state = ReducedState::from_state(state, step_budget);
-        let required_progress = self.settings.max_progress() - state.progress;
+        let reduced_state =
+            ReducedState::from_state(state, step_budget, self.max_steps(step_budget));
+        let required_progress = self.settings.required_progress(state.progress);
 
         if let Some(pareto_front) = self.solved_states.get(&reduced_state) {
             let index = pareto_front.partition_point(|value| value.first < required_progress);
@@ -99,12 +136,18 @@ impl StepLbSolver {
             return Err(SolverException::Interrupted);
         }
         self.pareto_front_builder.push_empty();
+        if self.settings.simulator_settings.progress_optional {
+            // the craft may end in this state
+            self.pareto_front_builder
+                .push_slice(&[ParetoValue::new(0, 0)]);
+            self.pareto_front_builder.merge();
+        }
         let search_actions = match reduced_state.effects.allow_quality_actions() {
             false => PROGRESS_ONLY_SEARCH_ACTIONS,
             true => FULL_SEARCH_ACTIONS,
         };
         for &action in search_actions {
-            if action.steps() <= reduced_state.steps_budget.get() {
+            if self.action_cost(action) <= reduced_state.budget.get() {
                 self.build_child_front(reduced_state, action)?;
                 if self.pareto_front_builder.is_max() {
                     // stop early if both Progress and Quality are maxed out
@@ -129,12 +172,15 @@ impl StepLbSolver {
         {
             let action_progress = new_full_state.progress;
             let action_quality = new_full_state.quality;
-            let new_step_budget = reduced_state.steps_budget.get() - action.steps();
+            let new_step_budget = reduced_state.budget.get() - self.action_cost(action);
             match NonZeroU8::try_from(new_step_budget) {
                 Ok(new_step_budget) if new_full_state.durability > 0 => {
                     // New state is not final
-                    let new_reduced_state =
-                        ReducedState::from_state(new_full_state, new_step_budget);
+                    let new_reduced_state = ReducedState::from_state(
+                        new_full_state,
+                        new_step_budget,
+                        self.max_steps(new_step_budget),
+                    );
                     if let Some(pareto_front) = self.solved_states.get(&new_reduced_state) {
                         self.pareto_front_builder.push_slice(pareto_front);
                     } else {
@@ -150,8 +196,8 @@ impl StepLbSolver {
                         });
                     self.pareto_front_builder.merge();
                 }
-                _ if action_progress != 0 => {
-                    // New state is final and last action increased Progress
+                _ if action_progress != 0 || self.settings.simulator_settings.progress_optional => {
+                    // New state is final and last action increased Progress (or Progress is optional)
                     self.pareto_front_builder
                         .push_slice(&[ParetoValue::new(action_progress, action_quality)]);
                     self.pareto_front_builder.merge();
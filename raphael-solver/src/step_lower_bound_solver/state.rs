@@ -4,7 +4,8 @@ use raphael_sim::*;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct ReducedState {
-    pub steps_budget: NonZeroU8,
+    /// Remaining budget, in steps or in seconds depending on the solver.
+    pub budget: NonZeroU8,
     pub durability: u16,
     pub effects: Effects,
 }
@@ -34,11 +35,12 @@ impl ReducedState {
         }
     }
 
-    pub fn from_state(state: SimulationState, steps_budget: NonZeroU8) -> Self {
+    /// `max_steps` is the maximum number of steps that can be taken with `budget`.
+    pub fn from_state(state: SimulationState, budget: NonZeroU8, max_steps: NonZeroU8) -> Self {
         Self {
-            steps_budget,
-            durability: Self::optimize_durability(state.effects, state.durability, steps_budget),
-            effects: Self::optimize_effects(state.effects, steps_budget),
+            budget,
+            durability: Self::optimize_durability(state.effects, state.durability, max_steps),
+            effects: Self::optimize_effects(state.effects, max_steps),
         }
     }
 
@@ -1,4 +1,4 @@
-use rand::Rng;
+use rand::{Rng, SeedableRng, rngs::StdRng};
 use raphael_sim::*;
 
 use crate::{
@@ -33,6 +33,11 @@ fn test_01() {
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let result = solve(
         settings,
@@ -67,6 +72,11 @@ fn test_adversarial_01() {
             .remove(Action::QuickInnovation),
         adversarial: true,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let result = solve(
         settings,
@@ -101,6 +111,11 @@ fn test_02() {
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let result = solve(
         settings,
@@ -132,6 +147,11 @@ fn test_adversarial_02() {
             .remove(Action::QuickInnovation),
         adversarial: true,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let result = solve(
         settings,
@@ -163,6 +183,11 @@ fn test_03() {
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let result = solve(
         settings,
@@ -199,6 +224,11 @@ fn test_adversarial_03() {
             .remove(Action::QuickInnovation),
         adversarial: true,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let result = solve(
         settings,
@@ -235,6 +265,11 @@ fn test_04() {
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let result = solve(settings, &[Action::MuscleMemory]);
     assert_eq!(result, 19);
@@ -256,6 +291,11 @@ fn test_adversarial_04() {
             .remove(Action::QuickInnovation),
         adversarial: true,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let result = solve(settings, &[Action::MuscleMemory]);
     assert_eq!(result, 14);
@@ -277,6 +317,11 @@ fn test_05() {
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let result = solve(settings, &[Action::MuscleMemory]);
     assert_eq!(result, 12);
@@ -298,6 +343,11 @@ fn test_adversarial_05() {
             .remove(Action::QuickInnovation),
         adversarial: true,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let result = solve(settings, &[Action::MuscleMemory]);
     assert_eq!(result, 12);
@@ -319,6 +369,11 @@ fn test_06() {
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let result = solve(settings, &[Action::MuscleMemory]);
     assert_eq!(result, 16);
@@ -340,6 +395,11 @@ fn test_adversarial_06() {
             .remove(Action::QuickInnovation),
         adversarial: true,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let result = solve(settings, &[Action::MuscleMemory]);
     assert_eq!(result, 11);
@@ -361,6 +421,11 @@ fn test_07() {
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let result = solve(settings, &[Action::Reflect]);
     assert_eq!(result, 15);
@@ -382,6 +447,11 @@ fn test_08() {
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let result = solve(settings, &[Action::PrudentTouch]);
     assert_eq!(result, 1);
@@ -404,6 +474,11 @@ fn test_09() {
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let result = solve(settings, &[]);
     assert_eq!(result, 17);
@@ -426,6 +501,11 @@ fn test_10() {
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let result = solve(settings, &[]);
     assert_eq!(result, 11);
@@ -448,6 +528,11 @@ fn test_11() {
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let result = solve(settings, &[]);
     assert_eq!(result, 11);
@@ -469,52 +554,69 @@ fn test_12() {
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let result = solve(settings, &[]);
     assert_eq!(result, 11);
 }
 
-fn random_effects(settings: &Settings) -> Effects {
+fn random_effects(rng: &mut impl Rng, settings: &Settings) -> Effects {
     Effects::new()
-        .with_inner_quiet(rand::thread_rng().gen_range(0..=10))
-        .with_great_strides(rand::thread_rng().gen_range(0..=3))
-        .with_innovation(rand::thread_rng().gen_range(0..=4))
-        .with_veneration(rand::thread_rng().gen_range(0..=4))
-        .with_waste_not(rand::thread_rng().gen_range(0..=8))
-        .with_manipulation(rand::thread_rng().gen_range(0..=8))
-        .with_quick_innovation_available(rand::random())
+        .with_inner_quiet(rng.gen_range(0..=10))
+        .with_great_strides(rng.gen_range(0..=3))
+        .with_innovation(rng.gen_range(0..=4))
+        .with_veneration(rng.gen_range(0..=4))
+        .with_waste_not(rng.gen_range(0..=8))
+        .with_manipulation(rng.gen_range(0..=8))
+        .with_quick_innovation_available(rng.gen_bool(0.5))
         .with_adversarial_guard(if settings.adversarial {
-            rand::random()
+            rng.gen_bool(0.5)
         } else {
             false
         })
         .with_allow_quality_actions(if settings.backload_progress {
-            rand::random()
+            rng.gen_bool(0.5)
         } else {
             true
         })
 }
 
-fn random_state(settings: &Settings) -> SimulationState {
+fn random_state(rng: &mut impl Rng, settings: &Settings) -> SimulationState {
     SimulationState {
-        cp: rand::thread_rng().gen_range(0..=settings.max_cp),
-        durability: rand::thread_rng().gen_range(1..=(settings.max_durability / 5)) * 5,
-        progress: rand::thread_rng().gen_range(0..u32::from(settings.max_progress)),
+        cp: rng.gen_range(0..=settings.max_cp),
+        durability: rng.gen_range(1..=(settings.max_durability / 5)) * 5,
+        progress: rng.gen_range(0..u32::from(settings.max_progress)),
         quality: 0,
         unreliable_quality: 0,
-        effects: random_effects(settings),
+        effects: random_effects(rng, settings),
     }
     .try_into()
     .unwrap()
 }
 
+/// Seed of the fuzz tests. Set `RAPHAEL_FUZZ_SEED` to replay a failed run with the seed it printed.
+fn fuzz_seed() -> u64 {
+    std::env::var("RAPHAEL_FUZZ_SEED").map_or_else(|_| rand::random(), |seed| seed.parse().unwrap())
+}
+
+fn monotonic_fuzz_check(simulator_settings: Settings) {
+    monotonic_fuzz_check_with_seed(simulator_settings, fuzz_seed());
+}
+
 /// Test that the upper-bound solver is monotonic,
 /// i.e. the quality UB of a state is never less than the quality UB of any of its children.
-fn monotonic_fuzz_check(simulator_settings: Settings) {
+fn monotonic_fuzz_check_with_seed(simulator_settings: Settings, seed: u64) {
+    // Only shown if the test fails
+    eprintln!("RAPHAEL_FUZZ_SEED={seed}");
+    let mut rng = StdRng::seed_from_u64(seed);
     let solver_settings = SolverSettings { simulator_settings };
     let mut solver = StepLbSolver::new(solver_settings, Default::default());
     for _ in 0..10000 {
-        let state = random_state(&simulator_settings);
+        let state = random_state(&mut rng, &simulator_settings);
         let state_lower_bound = solver.step_lower_bound(state, 0).unwrap();
         for action in FULL_SEARCH_ACTIONS {
             let child_lower_bound = match use_action_combo(&solver_settings, state, *action) {
@@ -550,6 +652,11 @@ fn test_monotonic_normal_sim() {
         allowed_actions: ActionMask::all(),
         adversarial: false,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     monotonic_fuzz_check(settings);
 }
@@ -567,6 +674,11 @@ fn test_monotonic_backload_progress_sim() {
         allowed_actions: ActionMask::all(),
         adversarial: false,
         backload_progress: true,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     monotonic_fuzz_check(settings);
 }
@@ -584,6 +696,11 @@ fn test_monotonic_adversarial_sim() {
         allowed_actions: ActionMask::all(),
         adversarial: true,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     monotonic_fuzz_check(settings);
 }
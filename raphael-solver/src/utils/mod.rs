@@ -4,6 +4,35 @@ mod pareto_front_builder;
 pub use atomic_flag::AtomicFlag;
 pub use pareto_front_builder::{ParetoFrontBuilder, ParetoValue};
 
+/// Serializes a map as a sequence of key-value pairs, which also works for keys that are not strings.
+/// Use with `#[serde(with = "crate::utils::map_as_seq")]`.
+#[cfg(feature = "serde")]
+pub mod map_as_seq {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<'a, M, K, V, S>(map: &'a M, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        &'a M: IntoIterator<Item = (&'a K, &'a V)>,
+        K: Serialize + 'a,
+        V: Serialize + 'a,
+        S: Serializer,
+    {
+        serializer.collect_seq(map)
+    }
+
+    pub fn deserialize<'de, M, K, V, D>(deserializer: D) -> Result<M, D::Error>
+    where
+        M: FromIterator<(K, V)>,
+        K: Deserialize<'de>,
+        V: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        Ok(Vec::<(K, V)>::deserialize(deserializer)?
+            .into_iter()
+            .collect())
+    }
+}
+
 pub struct ScopedTimer {
     name: &'static str,
     timer: web_time::Instant,
@@ -28,12 +57,14 @@ impl Drop for ScopedTimer {
     }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct Entry<T> {
     item: T,
     depth: u8,
     parent_index: usize,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Backtracking<T: Copy> {
     entries: Vec<Entry<T>>,
 }
@@ -56,6 +87,14 @@ impl<T: Copy> Backtracking<T> {
         items.into_iter().rev()
     }
 
+    /// Returns the item at `index`, or `None` for the sentinel.
+    pub fn get(&self, index: usize) -> Option<T> {
+        match index {
+            Self::SENTINEL => None,
+            _ => Some(self.entries[index].item),
+        }
+    }
+
     pub fn push(&mut self, item: T, parent_index: usize) -> usize {
         let depth = if parent_index == Self::SENTINEL {
             1
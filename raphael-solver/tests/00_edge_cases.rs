@@ -71,6 +71,11 @@ fn unsolvable() {
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let solver_settings = SolverSettings { simulator_settings };
     let expected_score = expect![[r#"
@@ -113,6 +118,11 @@ fn zero_quality() {
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let solver_settings = SolverSettings { simulator_settings };
     let expected_score = expect![[r#"
@@ -127,7 +137,7 @@ fn zero_quality() {
     "#]];
     let expected_runtime_stats = expect![[r#"
         MacroSolverStats {
-            finish_states: 1660,
+            finish_states: 1653,
             search_queue_stats: SearchQueueStats {
                 processed_nodes: 43,
                 dropped_nodes: 10,
@@ -162,6 +172,11 @@ fn max_quality() {
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let solver_settings = SolverSettings { simulator_settings };
     let expected_score = expect![[r#"
@@ -176,19 +191,19 @@ fn max_quality() {
     "#]];
     let expected_runtime_stats = expect![[r#"
         MacroSolverStats {
-            finish_states: 229983,
+            finish_states: 229167,
             search_queue_stats: SearchQueueStats {
                 processed_nodes: 4472,
-                dropped_nodes: 51456,
+                dropped_nodes: 50613,
                 pareto_buckets_squared_size_sum: 32640,
             },
             quality_ub_stats: QualityUbSolverStats {
-                states: 898686,
-                pareto_values: 6304450,
+                states: 882576,
+                pareto_values: 6285059,
             },
             step_lb_stats: StepLbSolverStats {
-                states: 108565,
-                pareto_values: 964182,
+                states: 108512,
+                pareto_values: 963911,
             },
         }
     "#]];
@@ -208,6 +223,11 @@ fn large_progress_quality_increase() {
         allowed_actions: ActionMask::all(),
         adversarial: false,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let solver_settings = SolverSettings { simulator_settings };
     let expected_score = expect![[r#"
@@ -229,8 +249,8 @@ fn large_progress_quality_increase() {
                 pareto_buckets_squared_size_sum: 0,
             },
             quality_ub_stats: QualityUbSolverStats {
-                states: 413424,
-                pareto_values: 411684,
+                states: 77226,
+                pareto_values: 75486,
             },
             step_lb_stats: StepLbSolverStats {
                 states: 10,
@@ -257,6 +277,11 @@ fn backload_progress_single_delicate_synthesis() {
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: true,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let solver_settings = SolverSettings { simulator_settings };
     let expected_score = expect![[r#"
@@ -278,8 +303,8 @@ fn backload_progress_single_delicate_synthesis() {
                 pareto_buckets_squared_size_sum: 0,
             },
             quality_ub_stats: QualityUbSolverStats {
-                states: 8087,
-                pareto_values: 7205,
+                states: 6812,
+                pareto_values: 5930,
             },
             step_lb_stats: StepLbSolverStats {
                 states: 8,
@@ -289,3 +314,45 @@ fn backload_progress_single_delicate_synthesis() {
     "#]];
     test_with_settings(solver_settings, expected_score, expected_runtime_stats);
 }
+
+#[test]
+fn no_prudent_actions_under_waste_not() {
+    let simulator_settings = Settings {
+        max_cp: 450,
+        max_durability: 35,
+        max_progress: 2000,
+        max_quality: 20000,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 90,
+        allowed_actions: ActionMask::all()
+            .remove(Action::TrainedEye)
+            .remove(Action::HeartAndSoul)
+            .remove(Action::QuickInnovation),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    let settings = SolverSettings { simulator_settings };
+    let mut solver = MacroSolver::new(
+        settings,
+        Box::new(|_| {}),
+        Box::new(|_| {}),
+        AtomicFlag::new(),
+    );
+    let actions = solver.solve().unwrap();
+    assert!(actions.contains(&Action::WasteNot) || actions.contains(&Action::WasteNot2));
+    let mut state = SimulationState::new(&simulator_settings);
+    for action in actions {
+        if matches!(action, Action::PrudentTouch | Action::PrudentSynthesis) {
+            assert_eq!(state.effects.waste_not(), 0);
+        }
+        state = state
+            .use_action(action, Condition::Normal, &simulator_settings)
+            .unwrap();
+    }
+}
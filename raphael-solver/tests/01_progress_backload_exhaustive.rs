@@ -71,6 +71,11 @@ fn rinascita_3700_3280() {
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: true,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let solver_settings = SolverSettings { simulator_settings };
     let expected_score = expect![[r#"
@@ -85,7 +90,7 @@ fn rinascita_3700_3280() {
     "#]];
     let expected_runtime_stats = expect![[r#"
         MacroSolverStats {
-            finish_states: 194590,
+            finish_states: 193605,
             search_queue_stats: SearchQueueStats {
                 processed_nodes: 229,
                 dropped_nodes: 0,
@@ -120,6 +125,11 @@ fn pactmaker_3240_3130() {
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: true,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let solver_settings = SolverSettings { simulator_settings };
     let expected_score = expect![[r#"
@@ -134,7 +144,7 @@ fn pactmaker_3240_3130() {
     "#]];
     let expected_runtime_stats = expect![[r#"
         MacroSolverStats {
-            finish_states: 259220,
+            finish_states: 257664,
             search_queue_stats: SearchQueueStats {
                 processed_nodes: 3280,
                 dropped_nodes: 0,
@@ -168,6 +178,11 @@ fn pactmaker_3240_3130_heart_and_soul() {
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: true,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let solver_settings = SolverSettings { simulator_settings };
     let expected_score = expect![[r#"
@@ -182,7 +197,7 @@ fn pactmaker_3240_3130_heart_and_soul() {
     "#]];
     let expected_runtime_stats = expect![[r#"
         MacroSolverStats {
-            finish_states: 213985,
+            finish_states: 213971,
             search_queue_stats: SearchQueueStats {
                 processed_nodes: 219,
                 dropped_nodes: 0,
@@ -217,6 +232,11 @@ fn diadochos_4021_3660() {
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: true,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let solver_settings = SolverSettings { simulator_settings };
     let expected_score = expect![[r#"
@@ -231,7 +251,7 @@ fn diadochos_4021_3660() {
     "#]];
     let expected_runtime_stats = expect![[r#"
         MacroSolverStats {
-            finish_states: 405309,
+            finish_states: 404893,
             search_queue_stats: SearchQueueStats {
                 processed_nodes: 759,
                 dropped_nodes: 0,
@@ -266,6 +286,11 @@ fn indagator_3858_4057() {
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: true,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let solver_settings = SolverSettings { simulator_settings };
     let expected_score = expect![[r#"
@@ -280,7 +305,7 @@ fn indagator_3858_4057() {
     "#]];
     let expected_runtime_stats = expect![[r#"
         MacroSolverStats {
-            finish_states: 339003,
+            finish_states: 338485,
             search_queue_stats: SearchQueueStats {
                 processed_nodes: 2410,
                 dropped_nodes: 0,
@@ -315,6 +340,11 @@ fn rarefied_tacos_de_carne_asada_4785_4758() {
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: true,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let solver_settings = SolverSettings { simulator_settings };
     let expected_score = expect![[r#"
@@ -329,19 +359,19 @@ fn rarefied_tacos_de_carne_asada_4785_4758() {
     "#]];
     let expected_runtime_stats = expect![[r#"
         MacroSolverStats {
-            finish_states: 1079192,
+            finish_states: 1076888,
             search_queue_stats: SearchQueueStats {
-                processed_nodes: 48136,
-                dropped_nodes: 181517,
-                pareto_buckets_squared_size_sum: 4300260,
+                processed_nodes: 48133,
+                dropped_nodes: 177477,
+                pareto_buckets_squared_size_sum: 4299936,
             },
             quality_ub_stats: QualityUbSolverStats {
-                states: 1891566,
-                pareto_values: 36452805,
+                states: 1891410,
+                pareto_values: 36451915,
             },
             step_lb_stats: StepLbSolverStats {
-                states: 1393327,
-                pareto_values: 17537768,
+                states: 1393266,
+                pareto_values: 17537561,
             },
         }
     "#]];
@@ -366,6 +396,11 @@ fn stuffed_peppers_2() {
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: true,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let solver_settings = SolverSettings { simulator_settings };
     let expected_score = expect![[r#"
@@ -380,15 +415,15 @@ fn stuffed_peppers_2() {
     "#]];
     let expected_runtime_stats = expect![[r#"
         MacroSolverStats {
-            finish_states: 523107,
+            finish_states: 522700,
             search_queue_stats: SearchQueueStats {
                 processed_nodes: 12078,
                 dropped_nodes: 0,
                 pareto_buckets_squared_size_sum: 851424,
             },
             quality_ub_stats: QualityUbSolverStats {
-                states: 1887532,
-                pareto_values: 36307624,
+                states: 1887528,
+                pareto_values: 36307503,
             },
             step_lb_stats: StepLbSolverStats {
                 states: 2095532,
@@ -416,6 +451,11 @@ fn stuffed_peppers_2_heart_and_soul() {
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: true,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let solver_settings = SolverSettings { simulator_settings };
     let expected_score = expect![[r#"
@@ -430,7 +470,7 @@ fn stuffed_peppers_2_heart_and_soul() {
     "#]];
     let expected_runtime_stats = expect![[r#"
         MacroSolverStats {
-            finish_states: 539758,
+            finish_states: 539321,
             search_queue_stats: SearchQueueStats {
                 processed_nodes: 7277,
                 dropped_nodes: 0,
@@ -466,6 +506,11 @@ fn stuffed_peppers_2_quick_innovation() {
             .remove(Action::HeartAndSoul),
         adversarial: false,
         backload_progress: true,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let solver_settings = SolverSettings { simulator_settings };
     let expected_score = expect![[r#"
@@ -480,7 +525,7 @@ fn stuffed_peppers_2_quick_innovation() {
     "#]];
     let expected_runtime_stats = expect![[r#"
         MacroSolverStats {
-            finish_states: 542035,
+            finish_states: 541566,
             search_queue_stats: SearchQueueStats {
                 processed_nodes: 24390,
                 dropped_nodes: 13,
@@ -515,6 +560,11 @@ fn rakaznar_lapidary_hammer_4462_4391() {
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: true,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let solver_settings = SolverSettings { simulator_settings };
     let expected_score = expect![[r#"
@@ -529,19 +579,19 @@ fn rakaznar_lapidary_hammer_4462_4391() {
     "#]];
     let expected_runtime_stats = expect![[r#"
         MacroSolverStats {
-            finish_states: 606818,
+            finish_states: 606209,
             search_queue_stats: SearchQueueStats {
                 processed_nodes: 273,
-                dropped_nodes: 2688,
+                dropped_nodes: 2640,
                 pareto_buckets_squared_size_sum: 688,
             },
             quality_ub_stats: QualityUbSolverStats {
-                states: 1530982,
-                pareto_values: 22678028,
+                states: 1520005,
+                pareto_values: 22664786,
             },
             step_lb_stats: StepLbSolverStats {
-                states: 508270,
-                pareto_values: 5818910,
+                states: 508241,
+                pareto_values: 5818716,
             },
         }
     "#]];
@@ -564,6 +614,11 @@ fn black_star_4048_3997() {
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: true,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let solver_settings = SolverSettings { simulator_settings };
     let expected_score = expect![[r#"
@@ -578,19 +633,19 @@ fn black_star_4048_3997() {
     "#]];
     let expected_runtime_stats = expect![[r#"
         MacroSolverStats {
-            finish_states: 135633,
+            finish_states: 135414,
             search_queue_stats: SearchQueueStats {
                 processed_nodes: 8253,
-                dropped_nodes: 93978,
+                dropped_nodes: 93112,
                 pareto_buckets_squared_size_sum: 390274,
             },
             quality_ub_stats: QualityUbSolverStats {
-                states: 1364169,
-                pareto_values: 7047900,
+                states: 1115324,
+                pareto_values: 6798656,
             },
             step_lb_stats: StepLbSolverStats {
-                states: 104612,
-                pareto_values: 699015,
+                states: 104594,
+                pareto_values: 698945,
             },
         }
     "#]];
@@ -613,6 +668,11 @@ fn claro_walnut_lumber_4900_4800() {
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: true,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let solver_settings = SolverSettings { simulator_settings };
     let expected_score = expect![[r#"
@@ -627,19 +687,19 @@ fn claro_walnut_lumber_4900_4800() {
     "#]];
     let expected_runtime_stats = expect![[r#"
         MacroSolverStats {
-            finish_states: 305792,
+            finish_states: 304980,
             search_queue_stats: SearchQueueStats {
                 processed_nodes: 26394,
-                dropped_nodes: 398612,
-                pareto_buckets_squared_size_sum: 2257854,
+                dropped_nodes: 395063,
+                pareto_buckets_squared_size_sum: 2257743,
             },
             quality_ub_stats: QualityUbSolverStats {
-                states: 1567808,
-                pareto_values: 10548959,
+                states: 1430705,
+                pareto_values: 10410787,
             },
             step_lb_stats: StepLbSolverStats {
-                states: 252324,
-                pareto_values: 1736040,
+                states: 252281,
+                pareto_values: 1735795,
             },
         }
     "#]];
@@ -662,6 +722,11 @@ fn rakaznar_lapidary_hammer_4900_4800() {
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: true,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let solver_settings = SolverSettings { simulator_settings };
     let expected_score = expect![[r#"
@@ -676,19 +741,19 @@ fn rakaznar_lapidary_hammer_4900_4800() {
     "#]];
     let expected_runtime_stats = expect![[r#"
         MacroSolverStats {
-            finish_states: 386757,
+            finish_states: 386554,
             search_queue_stats: SearchQueueStats {
                 processed_nodes: 323,
-                dropped_nodes: 4088,
+                dropped_nodes: 3988,
                 pareto_buckets_squared_size_sum: 824,
             },
             quality_ub_stats: QualityUbSolverStats {
-                states: 1704132,
-                pareto_values: 17986508,
+                states: 1486491,
+                pareto_values: 17768867,
             },
             step_lb_stats: StepLbSolverStats {
-                states: 405288,
-                pareto_values: 4016571,
+                states: 405287,
+                pareto_values: 4016561,
             },
         }
     "#]];
@@ -711,6 +776,11 @@ fn rarefied_tacos_de_carne_asada_4966_4817() {
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: true,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let solver_settings = SolverSettings { simulator_settings };
     let expected_score = expect![[r#"
@@ -725,19 +795,19 @@ fn rarefied_tacos_de_carne_asada_4966_4817() {
     "#]];
     let expected_runtime_stats = expect![[r#"
         MacroSolverStats {
-            finish_states: 435599,
+            finish_states: 434849,
             search_queue_stats: SearchQueueStats {
                 processed_nodes: 1069,
-                dropped_nodes: 13672,
+                dropped_nodes: 13336,
                 pareto_buckets_squared_size_sum: 5056,
             },
             quality_ub_stats: QualityUbSolverStats {
-                states: 1725882,
-                pareto_values: 15649784,
+                states: 1409762,
+                pareto_values: 15333664,
             },
             step_lb_stats: StepLbSolverStats {
-                states: 321078,
-                pareto_values: 3036636,
+                states: 321075,
+                pareto_values: 3036629,
             },
         }
     "#]];
@@ -760,6 +830,11 @@ fn archeo_kingdom_broadsword_4966_4914() {
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: true,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let solver_settings = SolverSettings { simulator_settings };
     let expected_score = expect![[r#"
@@ -774,19 +849,19 @@ fn archeo_kingdom_broadsword_4966_4914() {
     "#]];
     let expected_runtime_stats = expect![[r#"
         MacroSolverStats {
-            finish_states: 1156938,
+            finish_states: 1154702,
             search_queue_stats: SearchQueueStats {
-                processed_nodes: 16180,
-                dropped_nodes: 237115,
-                pareto_buckets_squared_size_sum: 515951,
+                processed_nodes: 16155,
+                dropped_nodes: 231187,
+                pareto_buckets_squared_size_sum: 515562,
             },
             quality_ub_stats: QualityUbSolverStats {
-                states: 2133674,
-                pareto_values: 31413523,
+                states: 1915384,
+                pareto_values: 31195005,
             },
             step_lb_stats: StepLbSolverStats {
-                states: 831864,
-                pareto_values: 9245422,
+                states: 831800,
+                pareto_values: 9245037,
             },
         }
     "#]];
@@ -809,6 +884,11 @@ fn hardened_survey_plank_5558_5216() {
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: true,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let solver_settings = SolverSettings { simulator_settings };
     let expected_score = expect![[r#"
@@ -823,19 +903,19 @@ fn hardened_survey_plank_5558_5216() {
     "#]];
     let expected_runtime_stats = expect![[r#"
         MacroSolverStats {
-            finish_states: 593905,
+            finish_states: 592323,
             search_queue_stats: SearchQueueStats {
-                processed_nodes: 166495,
-                dropped_nodes: 421307,
-                pareto_buckets_squared_size_sum: 39824729,
+                processed_nodes: 166478,
+                dropped_nodes: 416889,
+                pareto_buckets_squared_size_sum: 39823993,
             },
             quality_ub_stats: QualityUbSolverStats {
-                states: 2281656,
-                pareto_values: 23980767,
+                states: 2281550,
+                pareto_values: 23980406,
             },
             step_lb_stats: StepLbSolverStats {
-                states: 294515,
-                pareto_values: 2737980,
+                states: 294481,
+                pareto_values: 2737906,
             },
         }
     "#]];
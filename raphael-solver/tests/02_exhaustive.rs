@@ -71,6 +71,11 @@ fn rinascita_3700_3280() {
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let solver_settings = SolverSettings { simulator_settings };
     let expected_score = expect![[r#"
@@ -85,10 +90,10 @@ fn rinascita_3700_3280() {
     "#]];
     let expected_runtime_stats = expect![[r#"
         MacroSolverStats {
-            finish_states: 285821,
+            finish_states: 285501,
             search_queue_stats: SearchQueueStats {
                 processed_nodes: 1146,
-                dropped_nodes: 1006,
+                dropped_nodes: 1004,
                 pareto_buckets_squared_size_sum: 8399,
             },
             quality_ub_stats: QualityUbSolverStats {
@@ -120,6 +125,11 @@ fn pactmaker_3240_3130() {
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let solver_settings = SolverSettings { simulator_settings };
     let expected_score = expect![[r#"
@@ -134,10 +144,10 @@ fn pactmaker_3240_3130() {
     "#]];
     let expected_runtime_stats = expect![[r#"
         MacroSolverStats {
-            finish_states: 291698,
+            finish_states: 291014,
             search_queue_stats: SearchQueueStats {
                 processed_nodes: 2910,
-                dropped_nodes: 1505,
+                dropped_nodes: 1504,
                 pareto_buckets_squared_size_sum: 31706,
             },
             quality_ub_stats: QualityUbSolverStats {
@@ -168,6 +178,11 @@ fn pactmaker_3240_3130_heart_and_soul() {
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let solver_settings = SolverSettings { simulator_settings };
     let expected_score = expect![[r#"
@@ -182,7 +197,7 @@ fn pactmaker_3240_3130_heart_and_soul() {
     "#]];
     let expected_runtime_stats = expect![[r#"
         MacroSolverStats {
-            finish_states: 213985,
+            finish_states: 213971,
             search_queue_stats: SearchQueueStats {
                 processed_nodes: 219,
                 dropped_nodes: 0,
@@ -217,6 +232,11 @@ fn diadochos_4021_3660() {
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let solver_settings = SolverSettings { simulator_settings };
     let expected_score = expect![[r#"
@@ -231,7 +251,7 @@ fn diadochos_4021_3660() {
     "#]];
     let expected_runtime_stats = expect![[r#"
         MacroSolverStats {
-            finish_states: 463967,
+            finish_states: 463511,
             search_queue_stats: SearchQueueStats {
                 processed_nodes: 1986,
                 dropped_nodes: 1035,
@@ -266,6 +286,11 @@ fn indagator_3858_4057() {
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let solver_settings = SolverSettings { simulator_settings };
     let expected_score = expect![[r#"
@@ -280,10 +305,10 @@ fn indagator_3858_4057() {
     "#]];
     let expected_runtime_stats = expect![[r#"
         MacroSolverStats {
-            finish_states: 365439,
+            finish_states: 365377,
             search_queue_stats: SearchQueueStats {
                 processed_nodes: 230,
-                dropped_nodes: 812,
+                dropped_nodes: 809,
                 pareto_buckets_squared_size_sum: 382,
             },
             quality_ub_stats: QualityUbSolverStats {
@@ -315,6 +340,11 @@ fn rarefied_tacos_de_carne_asada_4785_4758() {
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let solver_settings = SolverSettings { simulator_settings };
     let expected_score = expect![[r#"
@@ -329,19 +359,19 @@ fn rarefied_tacos_de_carne_asada_4785_4758() {
     "#]];
     let expected_runtime_stats = expect![[r#"
         MacroSolverStats {
-            finish_states: 2257865,
+            finish_states: 2255591,
             search_queue_stats: SearchQueueStats {
-                processed_nodes: 1314945,
-                dropped_nodes: 8143050,
-                pareto_buckets_squared_size_sum: 70342159,
+                processed_nodes: 1314899,
+                dropped_nodes: 8052030,
+                pareto_buckets_squared_size_sum: 70338888,
             },
             quality_ub_stats: QualityUbSolverStats {
-                states: 1902738,
-                pareto_values: 48797927,
+                states: 1901900,
+                pareto_values: 48789260,
             },
             step_lb_stats: StepLbSolverStats {
-                states: 1627601,
-                pareto_values: 35914698,
+                states: 1627447,
+                pareto_values: 35913805,
             },
         }
     "#]];
@@ -366,6 +396,11 @@ fn stuffed_peppers_2() {
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let solver_settings = SolverSettings { simulator_settings };
     let expected_score = expect![[r#"
@@ -380,10 +415,10 @@ fn stuffed_peppers_2() {
     "#]];
     let expected_runtime_stats = expect![[r#"
         MacroSolverStats {
-            finish_states: 834381,
+            finish_states: 833030,
             search_queue_stats: SearchQueueStats {
                 processed_nodes: 334421,
-                dropped_nodes: 1323845,
+                dropped_nodes: 1301586,
                 pareto_buckets_squared_size_sum: 9996352,
             },
             quality_ub_stats: QualityUbSolverStats {
@@ -416,6 +451,11 @@ fn stuffed_peppers_2_heart_and_soul() {
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let solver_settings = SolverSettings { simulator_settings };
     let expected_score = expect![[r#"
@@ -430,10 +470,10 @@ fn stuffed_peppers_2_heart_and_soul() {
     "#]];
     let expected_runtime_stats = expect![[r#"
         MacroSolverStats {
-            finish_states: 745947,
+            finish_states: 745593,
             search_queue_stats: SearchQueueStats {
                 processed_nodes: 60458,
-                dropped_nodes: 181401,
+                dropped_nodes: 179257,
                 pareto_buckets_squared_size_sum: 961604,
             },
             quality_ub_stats: QualityUbSolverStats {
@@ -466,6 +506,11 @@ fn stuffed_peppers_2_quick_innovation() {
             .remove(Action::HeartAndSoul),
         adversarial: false,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let solver_settings = SolverSettings { simulator_settings };
     let expected_score = expect![[r#"
@@ -480,10 +525,10 @@ fn stuffed_peppers_2_quick_innovation() {
     "#]];
     let expected_runtime_stats = expect![[r#"
         MacroSolverStats {
-            finish_states: 869097,
+            finish_states: 867257,
             search_queue_stats: SearchQueueStats {
                 processed_nodes: 607522,
-                dropped_nodes: 2874931,
+                dropped_nodes: 2827552,
                 pareto_buckets_squared_size_sum: 18751495,
             },
             quality_ub_stats: QualityUbSolverStats {
@@ -515,6 +560,11 @@ fn rakaznar_lapidary_hammer_4462_4391() {
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let solver_settings = SolverSettings { simulator_settings };
     let expected_score = expect![[r#"
@@ -529,19 +579,19 @@ fn rakaznar_lapidary_hammer_4462_4391() {
     "#]];
     let expected_runtime_stats = expect![[r#"
         MacroSolverStats {
-            finish_states: 1209741,
+            finish_states: 1208860,
             search_queue_stats: SearchQueueStats {
                 processed_nodes: 20282,
-                dropped_nodes: 296196,
+                dropped_nodes: 293504,
                 pareto_buckets_squared_size_sum: 209669,
             },
             quality_ub_stats: QualityUbSolverStats {
-                states: 1531018,
-                pareto_values: 27021469,
+                states: 1500612,
+                pareto_values: 26980290,
             },
             step_lb_stats: StepLbSolverStats {
-                states: 755810,
-                pareto_values: 13913641,
+                states: 755706,
+                pareto_values: 13912731,
             },
         }
     "#]];
@@ -564,6 +614,11 @@ fn black_star_4048_3997() {
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let solver_settings = SolverSettings { simulator_settings };
     let expected_score = expect![[r#"
@@ -578,19 +633,19 @@ fn black_star_4048_3997() {
     "#]];
     let expected_runtime_stats = expect![[r#"
         MacroSolverStats {
-            finish_states: 44875,
+            finish_states: 44725,
             search_queue_stats: SearchQueueStats {
                 processed_nodes: 1459,
-                dropped_nodes: 21759,
+                dropped_nodes: 21613,
                 pareto_buckets_squared_size_sum: 15150,
             },
             quality_ub_stats: QualityUbSolverStats {
-                states: 1346442,
-                pareto_values: 7817546,
+                states: 1047718,
+                pareto_values: 7518819,
             },
             step_lb_stats: StepLbSolverStats {
-                states: 84672,
-                pareto_values: 850968,
+                states: 84657,
+                pareto_values: 850849,
             },
         }
     "#]];
@@ -613,6 +668,11 @@ fn claro_walnut_lumber_4900_4800() {
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let solver_settings = SolverSettings { simulator_settings };
     let expected_score = expect![[r#"
@@ -627,19 +687,19 @@ fn claro_walnut_lumber_4900_4800() {
     "#]];
     let expected_runtime_stats = expect![[r#"
         MacroSolverStats {
-            finish_states: 102346,
+            finish_states: 102178,
             search_queue_stats: SearchQueueStats {
                 processed_nodes: 6540,
-                dropped_nodes: 127664,
+                dropped_nodes: 127238,
                 pareto_buckets_squared_size_sum: 77195,
             },
             quality_ub_stats: QualityUbSolverStats {
-                states: 1524205,
-                pareto_values: 12436758,
+                states: 1356026,
+                pareto_values: 12266510,
             },
             step_lb_stats: StepLbSolverStats {
-                states: 222828,
-                pareto_values: 2206962,
+                states: 222798,
+                pareto_values: 2206770,
             },
         }
     "#]];
@@ -662,6 +722,11 @@ fn rakaznar_lapidary_hammer_4900_4800() {
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let solver_settings = SolverSettings { simulator_settings };
     let expected_score = expect![[r#"
@@ -676,19 +741,19 @@ fn rakaznar_lapidary_hammer_4900_4800() {
     "#]];
     let expected_runtime_stats = expect![[r#"
         MacroSolverStats {
-            finish_states: 285458,
+            finish_states: 285364,
             search_queue_stats: SearchQueueStats {
                 processed_nodes: 72,
-                dropped_nodes: 1455,
+                dropped_nodes: 1433,
                 pareto_buckets_squared_size_sum: 100,
             },
             quality_ub_stats: QualityUbSolverStats {
-                states: 1700326,
-                pareto_values: 20254488,
+                states: 1401458,
+                pareto_values: 19955620,
             },
             step_lb_stats: StepLbSolverStats {
-                states: 331102,
-                pareto_values: 5695344,
+                states: 331092,
+                pareto_values: 5695253,
             },
         }
     "#]];
@@ -711,6 +776,11 @@ fn rarefied_tacos_de_carne_asada_4966_4817() {
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let solver_settings = SolverSettings { simulator_settings };
     let expected_score = expect![[r#"
@@ -725,19 +795,19 @@ fn rarefied_tacos_de_carne_asada_4966_4817() {
     "#]];
     let expected_runtime_stats = expect![[r#"
         MacroSolverStats {
-            finish_states: 668396,
+            finish_states: 667718,
             search_queue_stats: SearchQueueStats {
                 processed_nodes: 6342,
-                dropped_nodes: 120543,
+                dropped_nodes: 118544,
                 pareto_buckets_squared_size_sum: 29510,
             },
             quality_ub_stats: QualityUbSolverStats {
-                states: 1716689,
-                pareto_values: 17523599,
+                states: 1314133,
+                pareto_values: 17121042,
             },
             step_lb_stats: StepLbSolverStats {
-                states: 435523,
-                pareto_values: 6644352,
+                states: 435465,
+                pareto_values: 6643933,
             },
         }
     "#]];
@@ -760,6 +830,11 @@ fn archeo_kingdom_broadsword_4966_4914() {
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let solver_settings = SolverSettings { simulator_settings };
     let expected_score = expect![[r#"
@@ -774,19 +849,19 @@ fn archeo_kingdom_broadsword_4966_4914() {
     "#]];
     let expected_runtime_stats = expect![[r#"
         MacroSolverStats {
-            finish_states: 865407,
+            finish_states: 864872,
             search_queue_stats: SearchQueueStats {
                 processed_nodes: 7247,
-                dropped_nodes: 143736,
+                dropped_nodes: 141744,
                 pareto_buckets_squared_size_sum: 41020,
             },
             quality_ub_stats: QualityUbSolverStats {
-                states: 2112472,
-                pareto_values: 37998717,
+                states: 1810636,
+                pareto_values: 37696876,
             },
             step_lb_stats: StepLbSolverStats {
-                states: 801669,
-                pareto_values: 16087443,
+                states: 801592,
+                pareto_values: 16086798,
             },
         }
     "#]];
@@ -809,6 +884,11 @@ fn hardened_survey_plank_5558_5216() {
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let solver_settings = SolverSettings { simulator_settings };
     let expected_score = expect![[r#"
@@ -823,19 +903,19 @@ fn hardened_survey_plank_5558_5216() {
     "#]];
     let expected_runtime_stats = expect![[r#"
         MacroSolverStats {
-            finish_states: 867536,
+            finish_states: 866317,
             search_queue_stats: SearchQueueStats {
-                processed_nodes: 1886441,
-                dropped_nodes: 12369594,
-                pareto_buckets_squared_size_sum: 186115309,
+                processed_nodes: 1886404,
+                dropped_nodes: 12317152,
+                pareto_buckets_squared_size_sum: 186113708,
             },
             quality_ub_stats: QualityUbSolverStats {
-                states: 2042638,
-                pareto_values: 34781603,
+                states: 2041056,
+                pareto_values: 34759450,
             },
             step_lb_stats: StepLbSolverStats {
-                states: 342379,
-                pareto_values: 5323872,
+                states: 342266,
+                pareto_values: 5323141,
             },
         }
     "#]];
@@ -855,6 +935,11 @@ fn hardened_survey_plank_5558_5216_heart_and_soul_quick_innovation() {
         allowed_actions: ActionMask::all().remove(Action::TrainedEye),
         adversarial: false,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let solver_settings = SolverSettings { simulator_settings };
     let expected_score = expect![[r#"
@@ -869,10 +954,10 @@ fn hardened_survey_plank_5558_5216_heart_and_soul_quick_innovation() {
     "#]];
     let expected_runtime_stats = expect![[r#"
         MacroSolverStats {
-            finish_states: 278336,
+            finish_states: 277879,
             search_queue_stats: SearchQueueStats {
                 processed_nodes: 20519,
-                dropped_nodes: 35735,
+                dropped_nodes: 35669,
                 pareto_buckets_squared_size_sum: 221587,
             },
             quality_ub_stats: QualityUbSolverStats {
@@ -904,6 +989,11 @@ fn ceviche_4900_4800_no_quality() {
             .remove(Action::QuickInnovation),
         adversarial: false,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let solver_settings = SolverSettings { simulator_settings };
     let expected_score = expect![[r#"
@@ -918,19 +1008,19 @@ fn ceviche_4900_4800_no_quality() {
     "#]];
     let expected_runtime_stats = expect![[r#"
         MacroSolverStats {
-            finish_states: 516117,
+            finish_states: 516045,
             search_queue_stats: SearchQueueStats {
                 processed_nodes: 29,
-                dropped_nodes: 248,
+                dropped_nodes: 234,
                 pareto_buckets_squared_size_sum: 37,
             },
             quality_ub_stats: QualityUbSolverStats {
-                states: 25637,
-                pareto_values: 25597,
+                states: 10464,
+                pareto_values: 10424,
             },
             step_lb_stats: StepLbSolverStats {
-                states: 1245,
-                pareto_values: 1245,
+                states: 1242,
+                pareto_values: 1242,
             },
         }
     "#]];
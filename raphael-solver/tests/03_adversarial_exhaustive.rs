@@ -69,6 +69,11 @@ const SETTINGS: Settings = Settings {
         .remove(Action::QuickInnovation),
     adversarial: true,
     backload_progress: false,
+    quality_increase_cap: None,
+    progress_target: None,
+    progress_optional: false,
+    rounding_mode: RoundingMode::Floor,
+    cp_cost_overrides: CpCostOverrides::none(),
 };
 
 #[test]
@@ -97,19 +102,19 @@ fn stuffed_peppers() {
     "#]];
     let expected_runtime_stats = expect![[r#"
         MacroSolverStats {
-            finish_states: 886551,
+            finish_states: 885144,
             search_queue_stats: SearchQueueStats {
-                processed_nodes: 79712,
-                dropped_nodes: 1501578,
-                pareto_buckets_squared_size_sum: 1172823,
+                processed_nodes: 79695,
+                dropped_nodes: 1481741,
+                pareto_buckets_squared_size_sum: 1172799,
             },
             quality_ub_stats: QualityUbSolverStats {
-                states: 4722074,
-                pareto_values: 77746538,
+                states: 4504092,
+                pareto_values: 77519073,
             },
             step_lb_stats: StepLbSolverStats {
-                states: 842949,
-                pareto_values: 14857558,
+                states: 842843,
+                pareto_values: 14856801,
             },
         }
     "#]];
@@ -134,6 +139,11 @@ fn test_rare_tacos_2() {
             .remove(Action::QuickInnovation),
         adversarial: true,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let solver_settings = SolverSettings { simulator_settings };
     let expected_score = expect![[r#"
@@ -148,19 +158,19 @@ fn test_rare_tacos_2() {
     "#]];
     let expected_runtime_stats = expect![[r#"
         MacroSolverStats {
-            finish_states: 1376236,
+            finish_states: 1371998,
             search_queue_stats: SearchQueueStats {
-                processed_nodes: 2332448,
-                dropped_nodes: 512436,
-                pareto_buckets_squared_size_sum: 143370684,
+                processed_nodes: 2332553,
+                dropped_nodes: 508936,
+                pareto_buckets_squared_size_sum: 143370686,
             },
             quality_ub_stats: QualityUbSolverStats {
-                states: 5030738,
-                pareto_values: 135274246,
+                states: 5030666,
+                pareto_values: 135272480,
             },
             step_lb_stats: StepLbSolverStats {
-                states: 1388845,
-                pareto_values: 31625292,
+                states: 1388686,
+                pareto_values: 31623941,
             },
         }
     "#]];
@@ -186,6 +196,11 @@ fn test_mountain_chromite_ingot_no_manipulation() {
             .remove(Action::QuickInnovation),
         adversarial: true,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let solver_settings = SolverSettings { simulator_settings };
     let expected_score = expect![[r#"
@@ -200,19 +215,19 @@ fn test_mountain_chromite_ingot_no_manipulation() {
     "#]];
     let expected_runtime_stats = expect![[r#"
         MacroSolverStats {
-            finish_states: 75899,
+            finish_states: 75155,
             search_queue_stats: SearchQueueStats {
-                processed_nodes: 26294,
-                dropped_nodes: 296454,
-                pareto_buckets_squared_size_sum: 463009,
+                processed_nodes: 26222,
+                dropped_nodes: 287721,
+                pareto_buckets_squared_size_sum: 462299,
             },
             quality_ub_stats: QualityUbSolverStats {
-                states: 3772842,
-                pareto_values: 33011766,
+                states: 3721026,
+                pareto_values: 32932526,
             },
             step_lb_stats: StepLbSolverStats {
-                states: 58468,
-                pareto_values: 520164,
+                states: 58394,
+                pareto_values: 519774,
             },
         }
     "#]];
@@ -235,6 +250,11 @@ fn test_indagator_3858_4057() {
             .remove(Action::QuickInnovation),
         adversarial: true,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let solver_settings = SolverSettings { simulator_settings };
     let expected_score = expect![[r#"
@@ -249,10 +269,10 @@ fn test_indagator_3858_4057() {
     "#]];
     let expected_runtime_stats = expect![[r#"
         MacroSolverStats {
-            finish_states: 449667,
+            finish_states: 449113,
             search_queue_stats: SearchQueueStats {
                 processed_nodes: 8953,
-                dropped_nodes: 10680,
+                dropped_nodes: 10659,
                 pareto_buckets_squared_size_sum: 99526,
             },
             quality_ub_stats: QualityUbSolverStats {
@@ -285,6 +305,11 @@ fn test_rare_tacos_4628_4410() {
             .remove(Action::QuickInnovation),
         adversarial: true,
         backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
     };
     let solver_settings = SolverSettings { simulator_settings };
     let expected_score = expect![[r#"
@@ -299,9 +324,9 @@ fn test_rare_tacos_4628_4410() {
     "#]];
     let expected_runtime_stats = expect![[r#"
         MacroSolverStats {
-            finish_states: 502169,
+            finish_states: 499458,
             search_queue_stats: SearchQueueStats {
-                processed_nodes: 553135,
+                processed_nodes: 553137,
                 dropped_nodes: 0,
                 pareto_buckets_squared_size_sum: 23891886,
             },
@@ -310,8 +335,8 @@ fn test_rare_tacos_4628_4410() {
                 pareto_values: 151000601,
             },
             step_lb_stats: StepLbSolverStats {
-                states: 319254,
-                pareto_values: 6969627,
+                states: 319226,
+                pareto_values: 6969400,
             },
         }
     "#]];
@@ -0,0 +1,1056 @@
+use expect_test::expect;
+use raphael_sim::*;
+use raphael_solver::{ActionOrdering, AtomicFlag, MacroSolver, SolverException, SolverSettings};
+
+#[derive(Debug, Clone, Copy)]
+#[allow(dead_code)]
+struct SolutionScore {
+    pub capped_quality: u32,
+    pub steps: u8,
+    pub buffs: u8,
+}
+
+fn solution_score(settings: &SolverSettings, actions: &[Action]) -> SolutionScore {
+    let final_state = SimulationState::from_macro(&settings.simulator_settings, actions).unwrap();
+    assert!(final_state.progress >= settings.max_progress());
+    SolutionScore {
+        capped_quality: std::cmp::min(final_state.quality, settings.max_quality()),
+        steps: actions.len() as u8,
+        buffs: actions
+            .iter()
+            .filter(|action| {
+                matches!(
+                    action,
+                    Action::Innovation
+                        | Action::Veneration
+                        | Action::GreatStrides
+                        | Action::Manipulation
+                )
+            })
+            .count() as u8,
+    }
+}
+
+fn new_solver<'a>(settings: SolverSettings) -> MacroSolver<'a> {
+    MacroSolver::new(
+        settings,
+        Box::new(|_| {}),
+        Box::new(|_| {}),
+        AtomicFlag::new(),
+    )
+}
+
+#[test]
+fn fewer_buffs() {
+    let simulator_settings = Settings {
+        max_cp: 400,
+        max_durability: 60,
+        max_progress: 2000,
+        max_quality: 2500,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 90,
+        allowed_actions: ActionMask::all()
+            .remove(Action::TrainedEye)
+            .remove(Action::HeartAndSoul)
+            .remove(Action::QuickInnovation),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    let solver_settings = SolverSettings { simulator_settings };
+
+    let actions = new_solver(solver_settings).solve().unwrap();
+    let optimal_score = solution_score(&solver_settings, &actions);
+    expect![[r#"
+        SolutionScore {
+            capped_quality: 2013,
+            steps: 15,
+            buffs: 3,
+        }
+    "#]]
+    .assert_debug_eq(&optimal_score);
+
+    let actions = new_solver(solver_settings)
+        .solve_with_fewer_buffs(250)
+        .unwrap();
+    let fewer_buffs_score = solution_score(&solver_settings, &actions);
+    expect![[r#"
+        SolutionScore {
+            capped_quality: 1827,
+            steps: 14,
+            buffs: 2,
+        }
+    "#]]
+    .assert_debug_eq(&fewer_buffs_score);
+
+    assert!(fewer_buffs_score.buffs < optimal_score.buffs);
+    assert!(fewer_buffs_score.capped_quality + 250 >= optimal_score.capped_quality);
+}
+
+#[test]
+fn restrict_actions() {
+    let simulator_settings = Settings {
+        max_cp: 400,
+        max_durability: 60,
+        max_progress: 2000,
+        max_quality: 2500,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 90,
+        allowed_actions: ActionMask::all()
+            .remove(Action::TrainedEye)
+            .remove(Action::HeartAndSoul)
+            .remove(Action::QuickInnovation),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    let solver_settings = SolverSettings { simulator_settings };
+    let allowed_actions = simulator_settings
+        .allowed_actions
+        .remove(Action::Manipulation);
+    let restricted_settings = SolverSettings {
+        simulator_settings: Settings {
+            allowed_actions,
+            ..simulator_settings
+        },
+    };
+
+    let mut solver = new_solver(solver_settings);
+    solver.solve().unwrap();
+    solver.restrict_actions(allowed_actions);
+    let actions = solver.solve().unwrap();
+    assert!(!actions.contains(&Action::Manipulation));
+    let restricted_score = solution_score(&restricted_settings, &actions);
+
+    let actions = new_solver(restricted_settings).solve().unwrap();
+    let fresh_score = solution_score(&restricted_settings, &actions);
+    assert_eq!(restricted_score.capped_quality, fresh_score.capped_quality);
+    assert_eq!(restricted_score.steps, fresh_score.steps);
+}
+
+#[test]
+fn progress_optional() {
+    let simulator_settings = Settings {
+        max_cp: 400,
+        max_durability: 60,
+        max_progress: 9000, // cannot be reached
+        max_quality: 10000,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 90,
+        allowed_actions: ActionMask::all()
+            .remove(Action::TrainedEye)
+            .remove(Action::HeartAndSoul)
+            .remove(Action::QuickInnovation),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    let solver_settings = SolverSettings { simulator_settings };
+    assert_eq!(
+        new_solver(solver_settings).solve(),
+        Err(SolverException::NoSolution)
+    );
+
+    let solver_settings = SolverSettings {
+        simulator_settings: Settings {
+            progress_optional: true,
+            ..simulator_settings
+        },
+    };
+    let actions = new_solver(solver_settings).solve().unwrap();
+    let final_state =
+        SimulationState::from_macro(&solver_settings.simulator_settings, &actions).unwrap();
+    // no action is spent on Progress
+    assert_eq!(final_state.progress, 0);
+    expect!["4495"].assert_eq(&final_state.quality.to_string());
+
+    // the best rotation that does complete Progress reaches less Quality
+    let solver_settings = SolverSettings {
+        simulator_settings: Settings {
+            max_progress: 2000,
+            ..simulator_settings
+        },
+    };
+    let actions = new_solver(solver_settings).solve().unwrap();
+    let quality_with_progress =
+        SimulationState::from_macro(&solver_settings.simulator_settings, &actions)
+            .unwrap()
+            .quality;
+    assert!(quality_with_progress < final_state.quality);
+}
+
+#[test]
+fn worst_case_conditions() {
+    let simulator_settings = Settings {
+        max_cp: 400,
+        max_durability: 60,
+        max_progress: 2000,
+        max_quality: 2500,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 90,
+        allowed_actions: ActionMask::all()
+            .remove(Action::TrainedEye)
+            .remove(Action::HeartAndSoul)
+            .remove(Action::QuickInnovation),
+        adversarial: true,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    let solver_settings = SolverSettings { simulator_settings };
+
+    let (actions, conditions) = new_solver(solver_settings)
+        .solve_with_worst_case_conditions()
+        .unwrap();
+    let guaranteed_quality = solution_score(&solver_settings, &actions).capped_quality;
+    expect![[r#"
+        1908
+    "#]]
+    .assert_debug_eq(&guaranteed_quality);
+
+    let settings = Settings {
+        adversarial: false,
+        ..simulator_settings
+    };
+    let mut state = SimulationState::new(&settings);
+    for (action, condition) in actions.iter().zip(&conditions) {
+        state = state.use_action(*action, *condition, &settings).unwrap();
+    }
+    assert_eq!(std::cmp::min(state.quality, 2500), guaranteed_quality);
+}
+
+#[test]
+fn initial_qualities() {
+    let simulator_settings = Settings {
+        max_cp: 400,
+        max_durability: 60,
+        max_progress: 2000,
+        max_quality: 2500,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 90,
+        allowed_actions: ActionMask::all()
+            .remove(Action::TrainedEye)
+            .remove(Action::HeartAndSoul)
+            .remove(Action::QuickInnovation),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    let solver_settings = SolverSettings { simulator_settings };
+
+    let results = new_solver(solver_settings)
+        .solve_for_initial_qualities(&[0, 1000, 2000])
+        .unwrap();
+    let scores: Vec<_> = results
+        .iter()
+        .map(|(initial_quality, actions)| {
+            let score = solution_score(&solver_settings, actions.as_ref().unwrap());
+            (*initial_quality, score.capped_quality, score.steps)
+        })
+        .collect();
+    expect![[r#"
+        [
+            (
+                0,
+                2013,
+                15,
+            ),
+            (
+                1000,
+                1530,
+                13,
+            ),
+            (
+                2000,
+                540,
+                8,
+            ),
+        ]
+    "#]]
+    .assert_debug_eq(&scores);
+    // Higher initial Quality requires fewer steps
+    assert!(scores.windows(2).all(|window| window[0].2 >= window[1].2));
+
+    // Same result as a fresh solve with the reduced target
+    let fresh_settings = SolverSettings {
+        simulator_settings: Settings {
+            max_quality: 1500,
+            ..simulator_settings
+        },
+    };
+    let fresh_actions = new_solver(fresh_settings).solve().unwrap();
+    let fresh_score = solution_score(&fresh_settings, &fresh_actions);
+    assert_eq!(std::cmp::min(scores[1].1, 1500), fresh_score.capped_quality);
+    assert_eq!(scores[1].2, fresh_score.steps);
+}
+
+#[test]
+fn quick_estimate() {
+    let simulator_settings = Settings {
+        max_cp: 400,
+        max_durability: 60,
+        max_progress: 2000,
+        max_quality: 10000,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 90,
+        allowed_actions: ActionMask::all()
+            .remove(Action::TrainedEye)
+            .remove(Action::HeartAndSoul)
+            .remove(Action::QuickInnovation),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    let fixtures = [
+        simulator_settings,
+        Settings {
+            max_cp: 250,
+            max_durability: 35,
+            ..simulator_settings
+        },
+        Settings {
+            max_quality: 1500,
+            ..simulator_settings
+        },
+    ];
+    for simulator_settings in fixtures {
+        let solver_settings = SolverSettings { simulator_settings };
+        let mut solver = new_solver(solver_settings);
+        let estimate = solver
+            .quick_estimate(SimulationState::new(&simulator_settings))
+            .unwrap();
+        let actions = solver.solve().unwrap();
+        let score = solution_score(&solver_settings, &actions);
+        assert!(estimate >= score.capped_quality);
+        assert!(estimate <= solver_settings.max_quality());
+    }
+}
+
+/// Expands actions in descending order of their ids.
+struct ReversedOrdering;
+
+impl ActionOrdering for ReversedOrdering {
+    fn sort_key(&self, _state: &SimulationState, actions: &[Action]) -> i64 {
+        let key = actions
+            .iter()
+            .fold(0, |key, action| key * 64 + i64::from(action.id()) + 1);
+        -key
+    }
+}
+
+#[test]
+fn action_ordering() {
+    let simulator_settings = Settings {
+        max_cp: 400,
+        max_durability: 60,
+        max_progress: 2000,
+        max_quality: 2500,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 90,
+        allowed_actions: ActionMask::all()
+            .remove(Action::TrainedEye)
+            .remove(Action::HeartAndSoul)
+            .remove(Action::QuickInnovation),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    let solver_settings = SolverSettings { simulator_settings };
+    let actions = new_solver(solver_settings).solve().unwrap();
+    let score = solution_score(&solver_settings, &actions);
+
+    let mut solver = new_solver(solver_settings);
+    solver.set_action_ordering(Box::new(ReversedOrdering));
+    let reversed_actions = solver.solve().unwrap();
+    let reversed_score = solution_score(&solver_settings, &reversed_actions);
+    assert_eq!(reversed_score.capped_quality, score.capped_quality);
+    assert_eq!(reversed_score.steps, score.steps);
+}
+
+#[test]
+fn locked_suffix() {
+    let simulator_settings = Settings {
+        max_cp: 400,
+        max_durability: 60,
+        max_progress: 2000,
+        max_quality: 2500,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 90,
+        allowed_actions: ActionMask::all()
+            .remove(Action::TrainedEye)
+            .remove(Action::HeartAndSoul)
+            .remove(Action::QuickInnovation),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    let solver_settings = SolverSettings { simulator_settings };
+    let actions = new_solver(solver_settings).solve().unwrap();
+    let score = solution_score(&solver_settings, &actions);
+
+    let suffix = [Action::ByregotsBlessing, Action::CarefulSynthesis];
+    let suffix_actions = new_solver(solver_settings)
+        .solve_with_suffix(&suffix)
+        .unwrap();
+    assert!(suffix_actions.ends_with(&suffix));
+    let suffix_score = solution_score(&solver_settings, &suffix_actions);
+    assert!(suffix_score.capped_quality <= score.capped_quality);
+    expect![[r#"
+        SolutionScore {
+            capped_quality: 2013,
+            steps: 15,
+            buffs: 3,
+        }
+    "#]]
+    .assert_debug_eq(&suffix_score);
+}
+
+#[test]
+fn quality_finisher() {
+    let simulator_settings = Settings {
+        max_cp: 400,
+        max_durability: 60,
+        max_progress: 2000,
+        max_quality: 2500,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 90,
+        allowed_actions: ActionMask::all()
+            .remove(Action::TrainedEye)
+            .remove(Action::HeartAndSoul)
+            .remove(Action::QuickInnovation),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    let solver_settings = SolverSettings { simulator_settings };
+    let last_quality_action = |actions: &[Action]| {
+        let mut state = SimulationState::new(&simulator_settings);
+        let mut last_quality_action = None;
+        for action in actions {
+            let new_state = state
+                .use_action(*action, Condition::Normal, &simulator_settings)
+                .unwrap();
+            if new_state.quality > state.quality {
+                last_quality_action = Some(*action);
+            }
+            state = new_state;
+        }
+        last_quality_action
+    };
+    let actions = new_solver(solver_settings).solve().unwrap();
+    let score = solution_score(&solver_settings, &actions);
+
+    let suffix = [Action::ByregotsBlessing, Action::CarefulSynthesis];
+    let suffix_actions = new_solver(solver_settings)
+        .solve_with_suffix(&suffix)
+        .unwrap();
+    let suffix_score = solution_score(&solver_settings, &suffix_actions);
+
+    let finisher_actions = new_solver(solver_settings)
+        .solve_with_quality_finisher(Action::ByregotsBlessing)
+        .unwrap();
+    assert_eq!(
+        last_quality_action(&finisher_actions),
+        Some(Action::ByregotsBlessing)
+    );
+    let finisher_score = solution_score(&solver_settings, &finisher_actions);
+    assert!(finisher_score.capped_quality <= score.capped_quality);
+    // Every rotation that ends with the locked suffix also ends its Quality with Byregot's Blessing
+    assert!(finisher_score.capped_quality >= suffix_score.capped_quality);
+    expect![[r#"
+        SolutionScore {
+            capped_quality: 2013,
+            steps: 15,
+            buffs: 3,
+        }
+    "#]]
+    .assert_debug_eq(&finisher_score);
+}
+
+#[test]
+fn explanation() {
+    let simulator_settings = Settings {
+        max_cp: 400,
+        max_durability: 60,
+        max_progress: 2000,
+        max_quality: 2500,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 90,
+        allowed_actions: ActionMask::all()
+            .remove(Action::TrainedEye)
+            .remove(Action::HeartAndSoul)
+            .remove(Action::QuickInnovation),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    let solver_settings = SolverSettings { simulator_settings };
+    let (actions, explanations) = new_solver(solver_settings)
+        .solve_with_explanation()
+        .unwrap();
+    let score = solution_score(&solver_settings, &actions);
+    // every action of the rotation belongs to exactly one explained step
+    let explained_actions: Vec<Action> = explanations
+        .iter()
+        .flat_map(|explanation| explanation.chosen.iter().copied())
+        .collect();
+    assert_eq!(explained_actions, actions);
+    for explanation in &explanations {
+        assert!(explanation.chosen_bound >= score.capped_quality);
+    }
+    expect![[r#"
+        StepExplanation {
+            chosen: [
+                Reflect,
+            ],
+            chosen_bound: 2031,
+            best_alternative_bound: Some(
+                1955,
+            ),
+        }
+    "#]]
+    .assert_debug_eq(&explanations[0]);
+}
+
+#[test]
+fn fewer_durability_actions() {
+    let simulator_settings = Settings {
+        max_cp: 500,
+        max_durability: 60,
+        max_progress: 2000,
+        max_quality: 1800,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 90,
+        allowed_actions: ActionMask::all()
+            .remove(Action::TrainedEye)
+            .remove(Action::HeartAndSoul)
+            .remove(Action::QuickInnovation),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    let solver_settings = SolverSettings { simulator_settings };
+    let durability_actions = |actions: &[Action]| {
+        actions
+            .iter()
+            .filter(|action| {
+                matches!(
+                    action,
+                    Action::MasterMend
+                        | Action::ImmaculateMend
+                        | Action::Manipulation
+                        | Action::WasteNot
+                        | Action::WasteNot2
+                )
+            })
+            .count()
+    };
+
+    let actions = new_solver(solver_settings).solve().unwrap();
+    let optimal_score = solution_score(&solver_settings, &actions);
+    let optimal_durability_actions = durability_actions(&actions);
+    expect!["2"].assert_eq(&optimal_durability_actions.to_string());
+
+    let actions = new_solver(solver_settings)
+        .solve_with_fewer_durability_actions()
+        .unwrap();
+    let score = solution_score(&solver_settings, &actions);
+    expect!["1"].assert_eq(&durability_actions(&actions).to_string());
+
+    assert!(durability_actions(&actions) < optimal_durability_actions);
+    assert_eq!(score.capped_quality, optimal_score.capped_quality);
+}
+
+#[test]
+fn minimize_duration() {
+    let simulator_settings = Settings {
+        max_cp: 400,
+        max_durability: 60,
+        max_progress: 2000,
+        max_quality: 2500,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 90,
+        allowed_actions: ActionMask::all()
+            .remove(Action::TrainedEye)
+            .remove(Action::HeartAndSoul)
+            .remove(Action::QuickInnovation),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    let solver_settings = SolverSettings { simulator_settings };
+    let duration = |actions: &[Action]| {
+        actions
+            .iter()
+            .map(|action| u32::from(action.time_cost()))
+            .sum::<u32>()
+    };
+
+    let actions = new_solver(solver_settings).solve().unwrap();
+    let optimal_score = solution_score(&solver_settings, &actions);
+    let optimal_duration = duration(&actions);
+
+    let actions = new_solver(solver_settings)
+        .solve_minimize_duration()
+        .unwrap();
+    let score = solution_score(&solver_settings, &actions);
+    expect!["41"].assert_eq(&duration(&actions).to_string());
+
+    assert!(duration(&actions) <= optimal_duration);
+    assert!(score.steps >= optimal_score.steps);
+    assert_eq!(score.capped_quality, optimal_score.capped_quality);
+}
+
+#[test]
+fn warm_start() {
+    let simulator_settings = Settings {
+        max_cp: 400,
+        max_durability: 60,
+        max_progress: 2000,
+        max_quality: 2500,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 90,
+        allowed_actions: ActionMask::all()
+            .remove(Action::TrainedEye)
+            .remove(Action::HeartAndSoul)
+            .remove(Action::QuickInnovation),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    let solver_settings = SolverSettings { simulator_settings };
+
+    let mut solver = new_solver(solver_settings);
+    let hint = solver.solve().unwrap();
+    let cold_score = solution_score(&solver_settings, &hint);
+    let cold_stats = solver.runtime_stats().search_queue_stats;
+
+    let mut solver = new_solver(solver_settings);
+    let actions = solver.solve_warm(&hint).unwrap();
+    let warm_score = solution_score(&solver_settings, &actions);
+    let warm_stats = solver.runtime_stats().search_queue_stats;
+
+    assert_eq!(warm_score.capped_quality, cold_score.capped_quality);
+    assert_eq!(warm_score.steps, cold_score.steps);
+    // Nodes that are worse than the hint are never queued
+    assert!(warm_stats.processed_nodes <= cold_stats.processed_nodes);
+    assert!(warm_stats.dropped_nodes < cold_stats.dropped_nodes);
+
+    // A hint that doesn't complete the craft is ignored
+    let actions = new_solver(solver_settings)
+        .solve_warm(&hint[..hint.len() - 1])
+        .unwrap();
+    let score = solution_score(&solver_settings, &actions);
+    assert_eq!(score.capped_quality, cold_score.capped_quality);
+    assert_eq!(score.steps, cold_score.steps);
+}
+
+#[test]
+fn solve_from_buffed_state() {
+    let simulator_settings = Settings {
+        max_cp: 400,
+        max_durability: 60,
+        max_progress: 2000,
+        max_quality: 5000,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 90,
+        allowed_actions: ActionMask::all()
+            .remove(Action::TrainedEye)
+            .remove(Action::HeartAndSoul)
+            .remove(Action::QuickInnovation),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    let solver_settings = SolverSettings { simulator_settings };
+    let state =
+        SimulationState::from_parts(&simulator_settings, 200, 30, 1000, 0, Combo::None).unwrap();
+    let buffed_state = SimulationState {
+        effects: state.effects.with_innovation(4).with_inner_quiet(10),
+        ..state
+    };
+
+    let actions = new_solver(solver_settings).solve_from_state(state).unwrap();
+    let final_state = state
+        .use_actions_with_condition_schedule(&actions, &[], &simulator_settings)
+        .unwrap();
+    let buffed_actions = new_solver(solver_settings)
+        .solve_from_state(buffed_state)
+        .unwrap();
+    let buffed_final_state = buffed_state
+        .use_actions_with_condition_schedule(&buffed_actions, &[], &simulator_settings)
+        .unwrap();
+    expect![[r#"
+        [
+            Innovation,
+            GreatStrides,
+            Veneration,
+            WasteNot,
+            PreparatoryTouch,
+            Groundwork,
+            Groundwork,
+        ]
+    "#]]
+    .assert_debug_eq(&actions);
+    expect![[r#"
+        [
+            TrainedFinesse,
+            TrainedFinesse,
+            GreatStrides,
+            ByregotsBlessing,
+            Veneration,
+            PrudentSynthesis,
+            PrudentSynthesis,
+            PrudentSynthesis,
+            CarefulSynthesis,
+        ]
+    "#]]
+    .assert_debug_eq(&buffed_actions);
+    assert!(buffed_final_state.progress >= solver_settings.max_progress());
+    assert!(buffed_final_state.quality > final_state.quality);
+    // The active Innovation is used instead of refreshing it
+    assert!(!buffed_actions.contains(&Action::Innovation));
+}
+
+#[test]
+fn two_pass() {
+    let simulator_settings = Settings {
+        max_cp: 400,
+        max_durability: 60,
+        max_progress: 2000,
+        max_quality: 2500,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 90,
+        allowed_actions: ActionMask::all()
+            .remove(Action::TrainedEye)
+            .remove(Action::HeartAndSoul)
+            .remove(Action::QuickInnovation),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    let solver_settings = SolverSettings { simulator_settings };
+
+    let actions = new_solver(solver_settings).solve().unwrap();
+    let optimal_score = solution_score(&solver_settings, &actions);
+
+    let actions = new_solver(solver_settings).solve_two_pass(10).unwrap();
+    let two_pass_score = solution_score(&solver_settings, &actions);
+    assert_eq!(two_pass_score.capped_quality, optimal_score.capped_quality);
+    assert_eq!(two_pass_score.steps, optimal_score.steps);
+
+    let actions = new_solver(solver_settings).solve_two_pass(0).unwrap();
+    let single_pass_score = solution_score(&solver_settings, &actions);
+    assert_eq!(
+        single_pass_score.capped_quality,
+        optimal_score.capped_quality
+    );
+    assert_eq!(single_pass_score.steps, optimal_score.steps);
+}
+
+#[test]
+fn redundant_transitions() {
+    let simulator_settings = Settings {
+        max_cp: 400,
+        max_durability: 60,
+        max_progress: 2000,
+        max_quality: 2500,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 90,
+        allowed_actions: ActionMask::all()
+            .remove(Action::TrainedEye)
+            .remove(Action::HeartAndSoul)
+            .remove(Action::QuickInnovation),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    let solver_settings = SolverSettings { simulator_settings };
+    let mut solver = new_solver(solver_settings);
+    let actions = solver.solve().unwrap();
+    let score = solution_score(&solver_settings, &actions);
+    expect![[r#"
+        SolutionScore {
+            capped_quality: 2013,
+            steps: 15,
+            buffs: 3,
+        }
+    "#]]
+    .assert_debug_eq(&score);
+    // Skipping redundant transitions saves finish states compared to the unpruned search (126446)
+    expect![[r#"
+        126083
+    "#]]
+    .assert_debug_eq(&solver.runtime_stats().finish_states);
+    // Redundant transitions never appear in the solution
+    let mut state = SimulationState::new(&simulator_settings);
+    for window in actions.windows(2) {
+        state = state
+            .use_action(window[0], Condition::Normal, &simulator_settings)
+            .unwrap();
+        assert!(!window[1].redundant_after(window[0], state.effects));
+    }
+}
+
+#[test]
+fn extra_quality_action() {
+    let simulator_settings = Settings {
+        max_cp: 60,
+        max_durability: 40,
+        max_progress: 200,
+        max_quality: 2000,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 90,
+        allowed_actions: ActionMask::all()
+            .remove(Action::TrainedEye)
+            .remove(Action::HeartAndSoul)
+            .remove(Action::QuickInnovation),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    let solver_settings = SolverSettings { simulator_settings };
+    let solver = new_solver(solver_settings);
+    let rotation = [Action::BasicSynthesis, Action::BasicSynthesis];
+    assert_eq!(
+        solver.cheapest_extra_quality_action(&rotation),
+        Some(Action::Reflect)
+    );
+    let solver = new_solver(SolverSettings {
+        simulator_settings: Settings {
+            max_cp: 5,
+            ..simulator_settings
+        },
+    });
+    assert_eq!(solver.cheapest_extra_quality_action(&rotation), None);
+
+    // Rotations don't need to complete Progress if Progress is optional
+    let solver = new_solver(SolverSettings {
+        simulator_settings: Settings {
+            max_progress: 9000, // cannot be reached
+            progress_optional: true,
+            ..simulator_settings
+        },
+    });
+    let rotation = [Action::BasicTouch, Action::BasicTouch];
+    assert_eq!(
+        solver.cheapest_extra_quality_action(&rotation),
+        Some(Action::Reflect)
+    );
+
+    // An optimal rotation already uses all CP and durability that can add Quality
+    let result = new_solver(solver_settings).solve_with_result().unwrap();
+    assert_eq!(result.extra_quality_action, None);
+}
+
+#[test]
+fn minimize_quality_overshoot() {
+    let simulator_settings = Settings {
+        max_cp: 400,
+        max_durability: 60,
+        max_progress: 2000,
+        max_quality: 1500,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 90,
+        allowed_actions: ActionMask::all()
+            .remove(Action::TrainedEye)
+            .remove(Action::HeartAndSoul)
+            .remove(Action::QuickInnovation),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    let solver_settings = SolverSettings { simulator_settings };
+    let overshoot = |actions: &[Action]| {
+        SimulationState::from_macro(&simulator_settings, actions)
+            .unwrap()
+            .quality_overkill(&simulator_settings)
+    };
+
+    let actions = new_solver(solver_settings).solve().unwrap();
+    let default_overshoot = overshoot(&actions);
+
+    let actions = new_solver(solver_settings)
+        .solve_minimize_quality_overshoot()
+        .unwrap();
+    let score = solution_score(&solver_settings, &actions);
+    assert_eq!(score.capped_quality, 1500);
+    assert!(overshoot(&actions) < default_overshoot);
+    expect![[r#"
+        (
+            30,
+            0,
+            13,
+        )
+    "#]]
+    .assert_debug_eq(&(default_overshoot, overshoot(&actions), score.steps));
+}
+
+#[test]
+fn minimize_quality_overshoot_without_max_quality() {
+    let simulator_settings = Settings {
+        max_cp: 400,
+        max_durability: 60,
+        max_progress: 2000,
+        max_quality: 0,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 90,
+        allowed_actions: ActionMask::all()
+            .remove(Action::TrainedEye)
+            .remove(Action::HeartAndSoul)
+            .remove(Action::QuickInnovation),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    let solver_settings = SolverSettings { simulator_settings };
+    let actions = new_solver(solver_settings)
+        .solve_minimize_quality_overshoot()
+        .unwrap();
+    let optimal_actions = new_solver(solver_settings).solve().unwrap();
+    assert_eq!(
+        solution_score(&solver_settings, &actions).steps,
+        solution_score(&solver_settings, &optimal_actions).steps
+    );
+}
+
+#[test]
+fn shorter_alternative() {
+    let simulator_settings = Settings {
+        max_cp: 500,
+        max_durability: 60,
+        max_progress: 2000,
+        max_quality: 3000,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 90,
+        allowed_actions: ActionMask::all()
+            .remove(Action::TrainedEye)
+            .remove(Action::HeartAndSoul)
+            .remove(Action::QuickInnovation),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    let solver_settings = SolverSettings { simulator_settings };
+    let (optimal_actions, shorter_actions) = new_solver(solver_settings)
+        .solve_with_shorter_alternative(2)
+        .unwrap();
+    let optimal_score = solution_score(&solver_settings, &optimal_actions);
+    let shorter_score = solution_score(&solver_settings, &shorter_actions.unwrap());
+    expect![[r#"
+        (
+            SolutionScore {
+                capped_quality: 3000,
+                steps: 20,
+                buffs: 5,
+            },
+            SolutionScore {
+                capped_quality: 2962,
+                steps: 18,
+                buffs: 4,
+            },
+        )
+    "#]]
+    .assert_debug_eq(&(optimal_score, shorter_score));
+    assert!(shorter_score.steps < optimal_score.steps);
+    assert!(shorter_score.capped_quality * 100 >= optimal_score.capped_quality * 98);
+}
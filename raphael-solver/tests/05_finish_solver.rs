@@ -0,0 +1,213 @@
+use raphael_sim::*;
+use raphael_solver::{FinishBlocker, FinishSolver, SolverSettings};
+
+const SETTINGS: Settings = Settings {
+    max_cp: 500,
+    max_durability: 60,
+    max_progress: 3000,
+    max_quality: 20000,
+    base_progress: 200,
+    base_quality: 200,
+    job_level: 100,
+    allowed_actions: ActionMask::all()
+        .remove(Action::TrainedEye)
+        .remove(Action::HeartAndSoul)
+        .remove(Action::QuickInnovation),
+    adversarial: false,
+    backload_progress: false,
+    quality_increase_cap: None,
+    progress_target: None,
+    progress_optional: false,
+    rounding_mode: RoundingMode::Floor,
+    cp_cost_overrides: CpCostOverrides::none(),
+};
+
+#[test]
+fn finish_sequence_mid_craft() {
+    let solver_settings = SolverSettings {
+        simulator_settings: SETTINGS,
+    };
+    let mut finish_solver = FinishSolver::new(solver_settings);
+    let actions = [
+        Action::MuscleMemory,
+        Action::Manipulation,
+        Action::Innovation,
+        Action::PreparatoryTouch,
+        Action::PreparatoryTouch,
+        Action::BasicTouch,
+        Action::StandardTouch,
+    ];
+    let state = SimulationState::from_macro(&SETTINGS, &actions).unwrap();
+    assert!(state.progress < u32::from(SETTINGS.max_progress));
+
+    let finish_sequence = finish_solver.finish_sequence(&state).unwrap();
+    assert!(!finish_sequence.is_empty());
+    let mut final_state = state;
+    for action in &finish_sequence {
+        final_state = final_state
+            .use_action(*action, Condition::Normal, &SETTINGS)
+            .unwrap();
+    }
+    assert!(final_state.progress >= u32::from(SETTINGS.max_progress));
+    assert!(final_state.quality >= state.quality);
+}
+
+#[test]
+fn finish_sequence_impossible() {
+    let solver_settings = SolverSettings {
+        simulator_settings: Settings {
+            max_progress: 30000,
+            ..SETTINGS
+        },
+    };
+    let mut finish_solver = FinishSolver::new(solver_settings);
+    let state = SimulationState::new(&solver_settings.simulator_settings);
+    assert_eq!(finish_solver.finish_sequence(&state), None);
+}
+
+#[test]
+fn finish_sequence_already_finished() {
+    let solver_settings = SolverSettings {
+        simulator_settings: Settings {
+            max_progress: 100,
+            ..SETTINGS
+        },
+    };
+    let mut finish_solver = FinishSolver::new(solver_settings);
+    let state = SimulationState::from_macro(
+        &solver_settings.simulator_settings,
+        &[Action::BasicSynthesis],
+    )
+    .unwrap();
+    assert_eq!(finish_solver.finish_sequence(&state), Some(Vec::new()));
+}
+
+#[test]
+fn finish_sequence_minimal_steps() {
+    let settings = Settings {
+        max_progress: 600,
+        ..SETTINGS
+    };
+    let mut finish_solver = FinishSolver::new(SolverSettings {
+        simulator_settings: settings,
+    });
+    // Three Basic Synthesis would also finish, but a single Groundwork (720 Progress) is enough.
+    let state = SimulationState::from_parts(&settings, 500, 60, 0, 0, Combo::None).unwrap();
+    assert_eq!(
+        finish_solver.finish_sequence(&state),
+        Some(vec![Action::Groundwork])
+    );
+}
+
+#[test]
+fn finish_blocker_none() {
+    let solver_settings = SolverSettings {
+        simulator_settings: SETTINGS,
+    };
+    let mut finish_solver = FinishSolver::new(solver_settings);
+    let state = SimulationState::new(&SETTINGS);
+    assert_eq!(finish_solver.finish_blocker(&state), FinishBlocker::None);
+}
+
+#[test]
+fn finish_blocker_cp() {
+    let settings = Settings {
+        max_progress: 250,
+        base_progress: 100,
+        allowed_actions: SETTINGS.allowed_actions.remove(Action::TrainedPerfection),
+        ..SETTINGS
+    };
+    let solver_settings = SolverSettings {
+        simulator_settings: settings,
+    };
+    let mut finish_solver = FinishSolver::new(solver_settings);
+    // The cheapest way to finish is Prudent Synthesis (18 CP) followed by Basic Synthesis.
+    let state = SimulationState::from_parts(&settings, 10, 10, 0, 0, Combo::None).unwrap();
+    assert_eq!(
+        finish_solver.finish_blocker(&state),
+        FinishBlocker::Cp { missing_cp: 8 }
+    );
+}
+
+#[test]
+fn finish_blocker_durability() {
+    // Without durability restoration or reduction, additional CP cannot make up for missing durability.
+    let settings = Settings {
+        max_progress: 1000,
+        base_progress: 100,
+        allowed_actions: SETTINGS
+            .allowed_actions
+            .remove(Action::MasterMend)
+            .remove(Action::Manipulation)
+            .remove(Action::ImmaculateMend)
+            .remove(Action::WasteNot)
+            .remove(Action::WasteNot2)
+            .remove(Action::TrainedPerfection),
+        ..SETTINGS
+    };
+    let solver_settings = SolverSettings {
+        simulator_settings: settings,
+    };
+    let mut finish_solver = FinishSolver::new(solver_settings);
+    // 9 Basic Synthesis are needed, the last of which can be used with only 5 durability left
+    let state = SimulationState::from_parts(&settings, 0, 10, 0, 0, Combo::None).unwrap();
+    assert_eq!(
+        finish_solver.finish_blocker(&state),
+        FinishBlocker::Durability {
+            missing_durability: 75
+        }
+    );
+}
+
+#[test]
+fn finish_blocker_progress() {
+    let settings = Settings {
+        max_progress: 60000,
+        base_progress: 100,
+        allowed_actions: SETTINGS
+            .allowed_actions
+            .remove(Action::MasterMend)
+            .remove(Action::Manipulation)
+            .remove(Action::ImmaculateMend),
+        ..SETTINGS
+    };
+    let solver_settings = SolverSettings {
+        simulator_settings: settings,
+    };
+    let mut finish_solver = FinishSolver::new(solver_settings);
+    let state = SimulationState::new(&settings);
+    assert_eq!(
+        finish_solver.finish_blocker(&state),
+        FinishBlocker::Progress
+    );
+}
+
+#[test]
+fn heart_and_soul_intensive_synthesis_finish() {
+    // With only 10 durability and 20 CP left, the only way to get 800 Progress in a single action
+    // is Intensive Synthesis (400% efficiency), which requires Heart and Soul outside of Good conditions.
+    let settings = Settings {
+        max_progress: 800,
+        allowed_actions: SETTINGS.allowed_actions.remove(Action::TrainedPerfection),
+        ..SETTINGS
+    };
+    let state = SimulationState::from_parts(&settings, 20, 10, 0, 0, Combo::None).unwrap();
+    let mut finish_solver = FinishSolver::new(SolverSettings {
+        simulator_settings: settings,
+    });
+    assert!(!finish_solver.can_finish(&state));
+
+    let settings = Settings {
+        allowed_actions: settings.allowed_actions.add(Action::HeartAndSoul),
+        ..settings
+    };
+    let state = SimulationState::from_parts(&settings, 20, 10, 0, 0, Combo::None).unwrap();
+    let mut finish_solver = FinishSolver::new(SolverSettings {
+        simulator_settings: settings,
+    });
+    assert!(finish_solver.can_finish(&state));
+    assert_eq!(
+        finish_solver.finish_sequence(&state),
+        Some(vec![Action::HeartAndSoul, Action::IntensiveSynthesis])
+    );
+}
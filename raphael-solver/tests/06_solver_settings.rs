@@ -0,0 +1,356 @@
+use raphael_sim::*;
+use raphael_solver::{
+    AtomicFlag, MacroSolver, SolverException, SolverSettings, max_possible_quality,
+};
+
+#[test]
+fn accessors() {
+    let solver_settings = SolverSettings {
+        simulator_settings: Settings {
+            max_cp: 553,
+            max_durability: 70,
+            max_progress: 2400,
+            max_quality: 20000,
+            base_progress: 123,
+            base_quality: 145,
+            job_level: 90,
+            allowed_actions: ActionMask::all(),
+            adversarial: false,
+            backload_progress: false,
+            quality_increase_cap: None,
+            progress_target: None,
+            progress_optional: false,
+            rounding_mode: RoundingMode::Floor,
+            cp_cost_overrides: CpCostOverrides::none(),
+        },
+    };
+    assert_eq!(solver_settings.max_cp(), 553);
+    assert_eq!(solver_settings.max_durability(), 70);
+    assert_eq!(solver_settings.max_progress(), 2400);
+    assert_eq!(solver_settings.max_quality(), 20000);
+    assert_eq!(solver_settings.base_progress(), 123);
+    assert_eq!(solver_settings.base_quality(), 145);
+    assert_eq!(solver_settings.required_progress(400), 2000);
+}
+
+#[test]
+fn builder() {
+    let simulator_settings = Settings {
+        max_cp: 553,
+        max_durability: 70,
+        max_progress: 2400,
+        max_quality: 20000,
+        base_progress: 123,
+        base_quality: 145,
+        job_level: 90,
+        allowed_actions: ActionMask::all(),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    let solver_settings = SolverSettings::builder(simulator_settings).build();
+    assert_eq!(solver_settings.simulator_settings, simulator_settings);
+
+    let other_settings = Settings {
+        max_cp: 600,
+        ..simulator_settings
+    };
+    let solver_settings = SolverSettings::builder(simulator_settings)
+        .simulator_settings(other_settings)
+        .build();
+    assert_eq!(solver_settings.simulator_settings, other_settings);
+}
+
+#[test]
+fn trivially_infeasible() {
+    let simulator_settings = Settings {
+        max_cp: 50,
+        max_durability: 20,
+        max_progress: 5000,
+        max_quality: 20000,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 90,
+        allowed_actions: ActionMask::all(),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    let solver_settings = SolverSettings { simulator_settings };
+    assert!(solver_settings.is_trivially_infeasible());
+
+    let mut solver = MacroSolver::new(
+        solver_settings,
+        Box::new(|_| {}),
+        Box::new(|_| {}),
+        AtomicFlag::new(),
+    );
+    assert_eq!(solver.solve(), Err(SolverException::NoSolution));
+    // the solve returns before the Quality upper bounds are precomputed
+    assert_eq!(solver.runtime_stats().quality_ub_stats.states, 0);
+
+    let solver_settings = SolverSettings {
+        simulator_settings: Settings {
+            max_progress: 500,
+            ..simulator_settings
+        },
+    };
+    assert!(!solver_settings.is_trivially_infeasible());
+}
+
+#[test]
+fn durability_not_multiple_of_five() {
+    let simulator_settings = Settings {
+        max_cp: 400,
+        max_durability: 18,
+        max_progress: 2000,
+        max_quality: 2500,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 90,
+        allowed_actions: ActionMask::all(),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    assert!(simulator_settings.validate().is_err());
+    let mut solver = MacroSolver::new(
+        SolverSettings { simulator_settings },
+        Box::new(|_| {}),
+        Box::new(|_| {}),
+        AtomicFlag::new(),
+    );
+    assert!(matches!(
+        solver.solve(),
+        Err(SolverException::InternalError(_))
+    ));
+
+    let simulator_settings = Settings {
+        max_durability: 20,
+        ..simulator_settings
+    };
+    assert!(simulator_settings.validate().is_ok());
+}
+
+#[test]
+fn no_progress_actions_allowed() {
+    let simulator_settings = Settings {
+        max_cp: 400,
+        max_durability: 60,
+        max_progress: 2000,
+        max_quality: 2500,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 90,
+        allowed_actions: ActionMask::all()
+            .remove(Action::BasicSynthesis)
+            .remove(Action::MuscleMemory)
+            .remove(Action::CarefulSynthesis)
+            .remove(Action::Groundwork)
+            .remove(Action::DelicateSynthesis)
+            .remove(Action::IntensiveSynthesis)
+            .remove(Action::PrudentSynthesis),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    assert!(simulator_settings.validate().is_err());
+    let mut solver = MacroSolver::new(
+        SolverSettings { simulator_settings },
+        Box::new(|_| {}),
+        Box::new(|_| {}),
+        AtomicFlag::new(),
+    );
+    assert!(matches!(
+        solver.solve(),
+        Err(SolverException::InternalError(_))
+    ));
+
+    let simulator_settings = Settings {
+        progress_optional: true,
+        ..simulator_settings
+    };
+    assert!(simulator_settings.validate().is_ok());
+
+    let simulator_settings = Settings {
+        progress_optional: false,
+        allowed_actions: simulator_settings
+            .allowed_actions
+            .add(Action::BasicSynthesis),
+        ..simulator_settings
+    };
+    assert!(simulator_settings.validate().is_ok());
+}
+
+#[test]
+fn malformed_settings() {
+    let simulator_settings = Settings {
+        max_cp: 200,
+        max_durability: 40,
+        max_progress: 1000,
+        max_quality: 1000,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 90,
+        allowed_actions: ActionMask::all(),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    let malformed_settings = [
+        Settings {
+            max_durability: 0,
+            ..simulator_settings
+        },
+        Settings {
+            max_durability: 5,
+            ..simulator_settings
+        },
+        Settings {
+            max_durability: 65535,
+            ..simulator_settings
+        },
+        Settings {
+            max_cp: u16::MAX,
+            ..simulator_settings
+        },
+    ];
+    for simulator_settings in malformed_settings {
+        let solver_settings = SolverSettings { simulator_settings };
+        assert!(matches!(
+            max_possible_quality(solver_settings),
+            Err(SolverException::InternalError(_))
+        ));
+        let mut solver = MacroSolver::new(
+            solver_settings,
+            Box::new(|_| {}),
+            Box::new(|_| {}),
+            AtomicFlag::new(),
+        );
+        assert!(matches!(
+            solver.solve(),
+            Err(SolverException::InternalError(_))
+        ));
+    }
+
+    // Valid settings that could previously cause a panic
+    let simulator_settings = Settings {
+        quality_increase_cap: Some(0),
+        ..simulator_settings
+    };
+    let solver_settings = SolverSettings { simulator_settings };
+    let mut solver = MacroSolver::new(
+        solver_settings,
+        Box::new(|_| {}),
+        Box::new(|_| {}),
+        AtomicFlag::new(),
+    );
+    assert!(solver.solve().is_ok());
+    let simulator_settings = Settings {
+        base_quality: 0,
+        adversarial: true,
+        ..simulator_settings
+    };
+    assert_eq!(
+        max_possible_quality(SolverSettings { simulator_settings }),
+        Ok(0)
+    );
+}
+
+#[test]
+fn cp_cost_overrides() {
+    let simulator_settings = Settings {
+        max_cp: 10,
+        max_durability: 40,
+        max_progress: 100,
+        max_quality: 100,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 90,
+        allowed_actions: ActionMask::none()
+            .add(Action::BasicSynthesis)
+            .add(Action::BasicTouch),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    let solve = |simulator_settings: Settings| {
+        let mut solver = MacroSolver::new(
+            SolverSettings { simulator_settings },
+            Box::new(|_| {}),
+            Box::new(|_| {}),
+            AtomicFlag::new(),
+        );
+        let actions = solver.solve().unwrap();
+        SimulationState::from_macro(&simulator_settings, &actions)
+            .unwrap()
+            .quality
+    };
+    // Basic Touch costs 18 CP by default
+    assert_eq!(solve(simulator_settings), 0);
+    let simulator_settings = Settings {
+        cp_cost_overrides: CpCostOverrides::none().with(Action::BasicTouch, 10),
+        ..simulator_settings
+    };
+    assert_eq!(solve(simulator_settings), 100);
+}
+
+#[test]
+fn precompute_key() {
+    let simulator_settings = Settings {
+        max_cp: 553,
+        max_durability: 70,
+        max_progress: 2400,
+        max_quality: 20000,
+        base_progress: 123,
+        base_quality: 145,
+        job_level: 90,
+        allowed_actions: ActionMask::all(),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    let key = SolverSettings { simulator_settings }.precompute_key();
+    let lower_target = SolverSettings {
+        simulator_settings: Settings {
+            max_quality: 15000,
+            ..simulator_settings
+        },
+    };
+    assert_eq!(lower_target.precompute_key(), key);
+    let lower_durability = SolverSettings {
+        simulator_settings: Settings {
+            max_durability: 35,
+            ..simulator_settings
+        },
+    };
+    assert_ne!(lower_durability.precompute_key(), key);
+}
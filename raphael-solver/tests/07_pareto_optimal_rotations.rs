@@ -0,0 +1,92 @@
+use raphael_sim::*;
+use raphael_solver::{SolverSettings, pareto_optimal_rotations};
+
+const SETTINGS: SolverSettings = SolverSettings {
+    simulator_settings: Settings {
+        max_cp: 300,
+        max_durability: 60,
+        max_progress: 200,
+        max_quality: 2000,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 100,
+        allowed_actions: ActionMask::all(),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    },
+};
+
+fn score(actions: &[Action]) -> (u32, usize, u16) {
+    let state = SimulationState::from_macro(&SETTINGS.simulator_settings, actions).unwrap();
+    (
+        std::cmp::min(state.quality, SETTINGS.max_quality()),
+        actions.len(),
+        SETTINGS.max_cp() - state.cp,
+    )
+}
+
+#[test]
+fn no_rotation_dominates_another() {
+    let rotations = vec![
+        // no Quality, but fewest steps and no CP
+        vec![Action::BasicSynthesis, Action::BasicSynthesis],
+        // dominated by the rotation above
+        vec![
+            Action::Observe,
+            Action::BasicSynthesis,
+            Action::BasicSynthesis,
+        ],
+        // more Quality for more steps and CP
+        vec![
+            Action::BasicTouch,
+            Action::BasicSynthesis,
+            Action::BasicSynthesis,
+        ],
+        // same score as the rotation above
+        vec![
+            Action::BasicTouch,
+            Action::BasicSynthesis,
+            Action::BasicSynthesis,
+        ],
+        // dominated by the Basic Touch rotation
+        vec![
+            Action::BasicTouch,
+            Action::Observe,
+            Action::BasicSynthesis,
+            Action::BasicSynthesis,
+        ],
+        // most Quality
+        vec![
+            Action::Reflect,
+            Action::BasicTouch,
+            Action::BasicSynthesis,
+            Action::BasicSynthesis,
+        ],
+        // invalid rotation
+        vec![Action::ByregotsBlessing, Action::BasicSynthesis],
+    ];
+    let filtered_rotations = pareto_optimal_rotations(&SETTINGS, rotations.clone());
+    assert_eq!(
+        filtered_rotations,
+        vec![
+            rotations[0].clone(),
+            rotations[2].clone(),
+            rotations[5].clone()
+        ]
+    );
+    for a in &filtered_rotations {
+        for b in &filtered_rotations {
+            let (score_a, score_b) = (score(a), score(b));
+            let dominates = score_a.0 >= score_b.0
+                && score_a.1 <= score_b.1
+                && score_a.2 <= score_b.2
+                && score_a != score_b;
+            assert!(!dominates, "{a:?} dominates {b:?}");
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use raphael_sim::*;
+use raphael_solver::{
+    AtomicFlag, MacroSolver, SolverSettings, min_control_for_target, solve_collectable_tiers,
+};
+
+const SETTINGS: Settings = Settings {
+    max_cp: 300,
+    max_durability: 60,
+    max_progress: 1500,
+    max_quality: 20000,
+    base_progress: 200,
+    base_quality: 0,
+    job_level: 90,
+    allowed_actions: ActionMask::all()
+        .remove(Action::TrainedEye)
+        .remove(Action::HeartAndSoul)
+        .remove(Action::QuickInnovation),
+    adversarial: false,
+    backload_progress: false,
+    quality_increase_cap: None,
+    progress_target: None,
+    progress_optional: false,
+    rounding_mode: RoundingMode::Floor,
+    cp_cost_overrides: CpCostOverrides::none(),
+};
+
+/// Base Quality formula for a recipe without level-based modifiers.
+fn settings_for(control: u16) -> SolverSettings {
+    SolverSettings {
+        simulator_settings: Settings {
+            base_quality: control * 10 / 300 + 35,
+            ..SETTINGS
+        },
+    }
+}
+
+fn max_quality(control: u16) -> u32 {
+    let settings = settings_for(control);
+    let mut solver = MacroSolver::new(
+        settings,
+        Box::new(|_| {}),
+        Box::new(|_| {}),
+        AtomicFlag::new(),
+    );
+    let actions = solver.solve().unwrap();
+    SimulationState::from_macro(&settings.simulator_settings, &actions)
+        .unwrap()
+        .quality
+}
+
+#[test]
+fn min_control_clears_target() {
+    let control = min_control_for_target(settings_for, 2000, 3000)
+        .unwrap()
+        .unwrap();
+    assert!(max_quality(control) >= 2000);
+    assert!(max_quality(control - 1) < 2000);
+}
+
+#[test]
+fn unreachable_target() {
+    assert_eq!(min_control_for_target(settings_for, 15000, 1000), Ok(None));
+}
+
+#[test]
+fn collectable_tiers() {
+    let settings = SolverSettings {
+        simulator_settings: Settings {
+            max_cp: 200,
+            ..settings_for(3000).simulator_settings
+        },
+    };
+    let tiers = [400, 800, 15000];
+    let rotations = solve_collectable_tiers(settings, tiers).unwrap();
+    let mut prev_cp_cost = 0;
+    for (rotation, target_quality) in rotations.iter().zip(tiers).take(2) {
+        let actions = rotation.as_ref().unwrap();
+        let state = SimulationState::from_macro(&settings.simulator_settings, actions).unwrap();
+        assert!(state.progress >= settings.max_progress());
+        assert!(state.quality >= u32::from(target_quality));
+        let cp_cost = settings.max_cp() - state.cp;
+        assert!(cp_cost > prev_cp_cost);
+        prev_cp_cost = cp_cost;
+    }
+    // The highest tier cannot be reached
+    assert!(rotations[2].is_none());
+}
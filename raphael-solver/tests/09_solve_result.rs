@@ -0,0 +1,46 @@
+#![cfg(feature = "serde")]
+
+use raphael_sim::*;
+use raphael_solver::{AtomicFlag, MacroSolver, SolveResult, SolverSettings};
+
+#[test]
+fn serde_round_trip() {
+    let simulator_settings = Settings {
+        max_cp: 300,
+        max_durability: 60,
+        max_progress: 1500,
+        max_quality: 2000,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 90,
+        allowed_actions: ActionMask::all()
+            .remove(Action::TrainedEye)
+            .remove(Action::HeartAndSoul)
+            .remove(Action::QuickInnovation),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    let solver_settings = SolverSettings { simulator_settings };
+    let mut solver = MacroSolver::new(
+        solver_settings,
+        Box::new(|_| {}),
+        Box::new(|_| {}),
+        AtomicFlag::new(),
+    );
+    let result = solver.solve_with_result().unwrap();
+    let state = SimulationState::from_macro(&simulator_settings, &result.actions).unwrap();
+    assert!(result.progress >= 1500);
+    assert_eq!(result.quality, state.quality);
+    assert_eq!(result.remaining_cp, state.cp);
+    assert_eq!(result.remaining_durability, state.durability);
+    assert!(result.stats.search_queue_stats.processed_nodes != 0);
+
+    let json = serde_json::to_string(&result).unwrap();
+    let deserialized: SolveResult = serde_json::from_str(&json).unwrap();
+    assert_eq!(format!("{deserialized:?}"), format!("{result:?}"));
+}
@@ -0,0 +1,90 @@
+use raphael_sim::*;
+use raphael_solver::{SolverSettings, canonicalize_rotation};
+
+const SETTINGS: SolverSettings = SolverSettings {
+    simulator_settings: Settings {
+        max_cp: 500,
+        max_durability: 80,
+        max_progress: 900,
+        max_quality: 20000,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 100,
+        allowed_actions: ActionMask::all(),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    },
+};
+
+fn final_state(actions: &[Action]) -> SimulationState {
+    SimulationState::from_macro(&SETTINGS.simulator_settings, actions).unwrap()
+}
+
+#[test]
+fn buffs_are_moved_to_their_consumers() {
+    let actions = vec![
+        Action::Reflect,
+        Action::Innovation,
+        Action::Veneration,
+        Action::Groundwork,
+        Action::PreparatoryTouch,
+        Action::GreatStrides,
+        Action::BasicSynthesis,
+        Action::ByregotsBlessing,
+        Action::CarefulSynthesis,
+    ];
+    let canonical_actions = canonicalize_rotation(&SETTINGS, actions.clone());
+    assert_eq!(
+        canonical_actions,
+        [
+            Action::Reflect,
+            Action::Innovation,
+            Action::Veneration,
+            Action::Groundwork,
+            Action::PreparatoryTouch,
+            Action::BasicSynthesis,
+            // Great Strides now directly precedes the action it boosts
+            Action::GreatStrides,
+            Action::ByregotsBlessing,
+            Action::CarefulSynthesis,
+        ]
+    );
+    let state = final_state(&actions);
+    let canonical_state = final_state(&canonical_actions);
+    assert_eq!(canonical_state.progress, state.progress);
+    assert_eq!(canonical_state.quality, state.quality);
+    assert_eq!(canonical_state.cp, state.cp);
+    assert_eq!(canonical_state.durability, state.durability);
+    // Canonicalizing again doesn't change anything
+    assert_eq!(
+        canonicalize_rotation(&SETTINGS, canonical_actions.clone()),
+        canonical_actions
+    );
+}
+
+#[test]
+fn combos_are_not_broken() {
+    let actions = vec![
+        Action::Innovation,
+        Action::BasicTouch,
+        Action::StandardTouch,
+        Action::AdvancedTouch,
+        Action::Groundwork,
+    ];
+    assert_eq!(canonicalize_rotation(&SETTINGS, actions.clone()), actions);
+}
+
+#[test]
+fn invalid_rotation_is_unchanged() {
+    let actions = vec![
+        Action::Innovation,
+        Action::BasicSynthesis,
+        Action::MuscleMemory,
+    ];
+    assert_eq!(canonicalize_rotation(&SETTINGS, actions.clone()), actions);
+}
@@ -0,0 +1,64 @@
+use raphael_sim::*;
+use raphael_solver::{
+    SolverSettings, backloaded_and_unconstrained_quality, guaranteed_and_best_case_quality,
+};
+
+#[test]
+fn indagator_3858_4057() {
+    // Same settings as `indagator_3858_4057` in 02_exhaustive and `test_indagator_3858_4057` in 03_adversarial_exhaustive
+    let simulator_settings = Settings {
+        max_cp: 687,
+        max_durability: 70,
+        max_progress: 5720,
+        max_quality: 12900,
+        base_progress: 239,
+        base_quality: 271,
+        job_level: 90,
+        allowed_actions: ActionMask::all()
+            .remove(Action::TrainedEye)
+            .remove(Action::HeartAndSoul)
+            .remove(Action::QuickInnovation),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    let solver_settings = SolverSettings { simulator_settings };
+    let (guaranteed_quality, best_case_quality) =
+        guaranteed_and_best_case_quality(solver_settings).unwrap();
+    assert_eq!(guaranteed_quality, 10686);
+    assert_eq!(best_case_quality, 12793);
+}
+
+#[test]
+fn rinascita_3700_3280_backload_progress() {
+    // Same settings as `rinascita_3700_3280` in 01_progress_backload_exhaustive and 02_exhaustive
+    let simulator_settings = Settings {
+        max_cp: 680,
+        max_durability: 70,
+        max_progress: 5060,
+        max_quality: 12628,
+        base_progress: 229,
+        base_quality: 224,
+        job_level: 90,
+        allowed_actions: ActionMask::all()
+            .remove(Action::TrainedEye)
+            .remove(Action::HeartAndSoul)
+            .remove(Action::QuickInnovation),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    let solver_settings = SolverSettings { simulator_settings };
+    let (backloaded_quality, unconstrained_quality) =
+        backloaded_and_unconstrained_quality(solver_settings).unwrap();
+    assert_eq!(backloaded_quality, 10492);
+    assert_eq!(unconstrained_quality, 10623);
+}
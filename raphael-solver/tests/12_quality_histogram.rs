@@ -0,0 +1,43 @@
+use raphael_sim::*;
+use raphael_solver::{AtomicFlag, MacroSolver, SolverSettings, terminal_quality_histogram};
+
+#[test]
+fn contains_optimum() {
+    let simulator_settings = Settings {
+        max_cp: 150,
+        max_durability: 40,
+        max_progress: 300,
+        max_quality: 2000,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 26,
+        allowed_actions: ActionMask::all(),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    let solver_settings = SolverSettings { simulator_settings };
+
+    let mut solver = MacroSolver::new(
+        solver_settings,
+        Box::new(|_| {}),
+        Box::new(|_| {}),
+        AtomicFlag::new(),
+    );
+    let actions = solver.solve().unwrap();
+    let optimal_quality = SimulationState::from_macro(&simulator_settings, &actions)
+        .unwrap()
+        .quality;
+
+    let histogram = terminal_quality_histogram(solver_settings, actions.len() as u8);
+    assert_eq!(histogram.last_key_value().unwrap().0, &optimal_quality);
+    assert!(histogram.contains_key(&0));
+
+    // Fewer steps than the optimal rotation cannot reach the optimal Quality
+    let histogram = terminal_quality_histogram(solver_settings, actions.len() as u8 - 1);
+    assert!(histogram.keys().all(|quality| *quality < optimal_quality));
+}
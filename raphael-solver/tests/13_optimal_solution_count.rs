@@ -0,0 +1,111 @@
+use std::collections::HashSet;
+
+use expect_test::expect;
+use raphael_sim::*;
+use raphael_solver::{AtomicFlag, MacroSolver, SolverSettings};
+
+const ALLOWED_ACTIONS: [Action; 6] = [
+    Action::BasicSynthesis,
+    Action::CarefulSynthesis,
+    Action::BasicTouch,
+    Action::PrudentTouch,
+    Action::Innovation,
+    Action::MasterMend,
+];
+
+/// Enumerates all rotations with at most `max_steps` steps and collects the action multisets
+/// of the rotations that complete the craft with at least `target_quality`.
+fn brute_force_rotations(
+    settings: &Settings,
+    state: SimulationState,
+    actions: &mut Vec<Action>,
+    max_steps: usize,
+    target_quality: u32,
+    rotations: &mut HashSet<Vec<Action>>,
+) {
+    if actions.len() == max_steps {
+        return;
+    }
+    for action in ALLOWED_ACTIONS {
+        let Ok(state) = state.use_action(action, Condition::Normal, settings) else {
+            continue;
+        };
+        actions.push(action);
+        if state.progress >= u32::from(settings.max_progress) {
+            if state.quality >= target_quality {
+                let mut rotation = actions.clone();
+                rotation.sort_unstable_by_key(|action| action.id());
+                rotations.insert(rotation);
+            }
+        } else if state.durability > 0 {
+            brute_force_rotations(
+                settings,
+                state,
+                actions,
+                max_steps,
+                target_quality,
+                rotations,
+            );
+        }
+        actions.pop();
+    }
+}
+
+#[test]
+fn matches_brute_force() {
+    let simulator_settings = Settings {
+        max_cp: 250,
+        max_durability: 40,
+        max_progress: 500,
+        max_quality: 600,
+        base_progress: 100,
+        base_quality: 100,
+        job_level: 90,
+        allowed_actions: ALLOWED_ACTIONS
+            .into_iter()
+            .fold(ActionMask::none(), ActionMask::add),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    };
+    let solver_settings = SolverSettings { simulator_settings };
+    let mut solver = MacroSolver::new(
+        solver_settings,
+        Box::new(|_| {}),
+        Box::new(|_| {}),
+        AtomicFlag::new(),
+    );
+    solver.set_count_optimal_solutions(true);
+    let result = solver.solve_with_result().unwrap();
+
+    let mut rotations = HashSet::new();
+    brute_force_rotations(
+        &simulator_settings,
+        SimulationState::new(&simulator_settings),
+        &mut Vec::new(),
+        result.actions.len(),
+        std::cmp::min(result.quality, u32::from(simulator_settings.max_quality)),
+        &mut rotations,
+    );
+    assert_eq!(result.optimal_solution_count, Some(rotations.len()));
+    expect![[r#"
+        (
+            690,
+            Some(
+                10,
+            ),
+        )
+    "#]]
+    .assert_debug_eq(&(result.quality, result.optimal_solution_count));
+
+    // Not counted by default
+    solver.set_count_optimal_solutions(false);
+    assert_eq!(
+        solver.solve_with_result().unwrap().optimal_solution_count,
+        None
+    );
+}
@@ -0,0 +1,98 @@
+use rand::{Rng, SeedableRng, rngs::StdRng};
+use raphael_sim::*;
+use raphael_solver::{AtomicFlag, MacroSolver, SolverException, SolverSettings};
+
+const ALLOWED_ACTIONS: [Action; 11] = [
+    Action::BasicSynthesis,
+    Action::CarefulSynthesis,
+    Action::Veneration,
+    Action::BasicTouch,
+    Action::StandardTouch,
+    Action::PrudentTouch,
+    Action::ByregotsBlessing,
+    Action::Innovation,
+    Action::GreatStrides,
+    Action::MasterMend,
+    Action::WasteNot,
+];
+
+/// Returns the best capped Quality of all rotations from `state` that complete the craft,
+/// or `None` if no rotation completes the craft.
+/// Every action costs CP or durability, so all rotations are finite.
+fn brute_force_quality(settings: &Settings, state: SimulationState) -> Option<u32> {
+    let mut best_quality = None;
+    for action in ALLOWED_ACTIONS {
+        let Ok(state) = state.use_action(action, Condition::Normal, settings) else {
+            continue;
+        };
+        let quality = if state.progress >= u32::from(settings.max_progress) {
+            Some(std::cmp::min(
+                state.quality,
+                u32::from(settings.max_quality),
+            ))
+        } else if state.durability > 0 {
+            brute_force_quality(settings, state)
+        } else {
+            None
+        };
+        best_quality = std::cmp::max(best_quality, quality);
+    }
+    best_quality
+}
+
+fn random_settings(rng: &mut impl Rng) -> Settings {
+    Settings {
+        max_cp: rng.gen_range(50..=200),
+        max_durability: rng.gen_range(3..=6) * 5,
+        max_progress: rng.gen_range(2..=6) * 50,
+        max_quality: rng.gen_range(1..=20) * 50,
+        base_progress: 50,
+        base_quality: 50,
+        job_level: 90,
+        allowed_actions: ALLOWED_ACTIONS
+            .into_iter()
+            .fold(ActionMask::none(), ActionMask::add),
+        adversarial: false,
+        backload_progress: false,
+        quality_increase_cap: None,
+        progress_target: None,
+        progress_optional: false,
+        rounding_mode: RoundingMode::Floor,
+        cp_cost_overrides: CpCostOverrides::none(),
+    }
+}
+
+#[test]
+fn matches_brute_force() {
+    let mut rng = StdRng::seed_from_u64(0x5eed);
+    for _ in 0..20 {
+        let simulator_settings = random_settings(&mut rng);
+        let solver_settings = SolverSettings { simulator_settings };
+        let mut solver = MacroSolver::new(
+            solver_settings,
+            Box::new(|_| {}),
+            Box::new(|_| {}),
+            AtomicFlag::new(),
+        );
+        let solver_quality = match solver.solve() {
+            Ok(actions) => {
+                let state = SimulationState::from_macro(&simulator_settings, &actions).unwrap();
+                assert!(state.progress >= u32::from(simulator_settings.max_progress));
+                Some(std::cmp::min(
+                    state.quality,
+                    u32::from(simulator_settings.max_quality),
+                ))
+            }
+            Err(SolverException::NoSolution) => None,
+            Err(exception) => panic!("{exception:?}"),
+        };
+        let brute_force_quality = brute_force_quality(
+            &simulator_settings,
+            SimulationState::new(&simulator_settings),
+        );
+        assert_eq!(
+            solver_quality, brute_force_quality,
+            "{simulator_settings:?}"
+        );
+    }
+}
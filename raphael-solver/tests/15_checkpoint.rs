@@ -0,0 +1,93 @@
+use std::cell::Cell;
+
+use raphael_sim::*;
+use raphael_solver::{AtomicFlag, MacroSolver, SolverCheckpoint, SolverException, SolverSettings};
+
+const SETTINGS: Settings = Settings {
+    max_cp: 300,
+    max_durability: 60,
+    max_progress: 1500,
+    max_quality: 2000,
+    base_progress: 100,
+    base_quality: 100,
+    job_level: 90,
+    allowed_actions: ActionMask::all()
+        .remove(Action::TrainedEye)
+        .remove(Action::HeartAndSoul)
+        .remove(Action::QuickInnovation),
+    adversarial: false,
+    backload_progress: false,
+    quality_increase_cap: None,
+    progress_target: None,
+    progress_optional: false,
+    rounding_mode: RoundingMode::Floor,
+    cp_cost_overrides: CpCostOverrides::none(),
+};
+
+/// Solves `SETTINGS` and interrupts the search as soon as the first solution is found.
+fn interrupted_solve() -> SolverCheckpoint {
+    let interrupt_signal = AtomicFlag::new();
+    let solutions_found = Cell::new(0);
+    let mut solver = MacroSolver::new(
+        SolverSettings {
+            simulator_settings: SETTINGS,
+        },
+        Box::new(|_| {
+            solutions_found.set(solutions_found.get() + 1);
+            interrupt_signal.set();
+        }),
+        Box::new(|_| {}),
+        interrupt_signal.clone(),
+    );
+    assert_eq!(solver.solve(), Err(SolverException::Interrupted));
+    assert_eq!(solutions_found.get(), 1);
+    solver.take_checkpoint().unwrap()
+}
+
+#[test]
+fn resume_matches_uninterrupted_solve() {
+    let solver_settings = SolverSettings {
+        simulator_settings: SETTINGS,
+    };
+    let mut solver = MacroSolver::new(
+        solver_settings,
+        Box::new(|_| {}),
+        Box::new(|_| {}),
+        AtomicFlag::new(),
+    );
+    let expected_actions = solver.solve().unwrap();
+    assert!(solver.take_checkpoint().is_none());
+
+    let checkpoint = interrupted_solve();
+    #[cfg(feature = "serde")]
+    let checkpoint: SolverCheckpoint =
+        serde_json::from_str(&serde_json::to_string(&checkpoint).unwrap()).unwrap();
+
+    let mut solver = MacroSolver::new(
+        solver_settings,
+        Box::new(|_| {}),
+        Box::new(|_| {}),
+        AtomicFlag::new(),
+    );
+    assert_eq!(solver.resume(checkpoint).unwrap(), expected_actions);
+}
+
+#[test]
+fn resume_with_different_settings() {
+    let checkpoint = interrupted_solve();
+    let mut solver = MacroSolver::new(
+        SolverSettings {
+            simulator_settings: Settings {
+                max_cp: 250,
+                ..SETTINGS
+            },
+        },
+        Box::new(|_| {}),
+        Box::new(|_| {}),
+        AtomicFlag::new(),
+    );
+    assert!(matches!(
+        solver.resume(checkpoint),
+        Err(SolverException::InternalError(_))
+    ));
+}
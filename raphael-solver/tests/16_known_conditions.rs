@@ -0,0 +1,59 @@
+use raphael_sim::*;
+use raphael_solver::{SolverException, SolverSettings, solve_known_conditions};
+
+const SETTINGS: Settings = Settings {
+    max_cp: 100,
+    max_durability: 40,
+    max_progress: 400,
+    max_quality: 2000,
+    base_progress: 100,
+    base_quality: 100,
+    job_level: 90,
+    allowed_actions: ActionMask::all()
+        .remove(Action::TrainedEye)
+        .remove(Action::HeartAndSoul)
+        .remove(Action::QuickInnovation),
+    adversarial: false,
+    backload_progress: false,
+    quality_increase_cap: None,
+    progress_target: None,
+    progress_optional: false,
+    rounding_mode: RoundingMode::Floor,
+    cp_cost_overrides: CpCostOverrides::none(),
+};
+
+fn final_quality(actions: &[Action], conditions: &[Condition]) -> u32 {
+    SimulationState::new(&SETTINGS)
+        .use_actions_with_condition_schedule(actions, conditions, &SETTINGS)
+        .unwrap()
+        .quality
+}
+
+#[test]
+fn good_condition_is_used_for_precise_touch() {
+    let solver_settings = SolverSettings {
+        simulator_settings: SETTINGS,
+    };
+    let normal_conditions = [Condition::Normal; 5];
+    let normal_actions = solve_known_conditions(solver_settings, &normal_conditions).unwrap();
+    assert!(!normal_actions.contains(&Action::PreciseTouch));
+
+    let mut conditions = normal_conditions;
+    conditions[2] = Condition::Good;
+    let actions = solve_known_conditions(solver_settings, &conditions).unwrap();
+    assert_eq!(actions[2], Action::PreciseTouch);
+    assert!(
+        final_quality(&actions, &conditions) > final_quality(&normal_actions, &normal_conditions)
+    );
+}
+
+#[test]
+fn too_few_steps() {
+    let solver_settings = SolverSettings {
+        simulator_settings: SETTINGS,
+    };
+    assert_eq!(
+        solve_known_conditions(solver_settings, &[Condition::Normal]),
+        Err(SolverException::NoSolution)
+    );
+}
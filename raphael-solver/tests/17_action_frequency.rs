@@ -0,0 +1,122 @@
+use expect_test::expect;
+use raphael_sim::*;
+use raphael_solver::{ActionFrequency, AtomicFlag, MacroSolver, SolverSettings};
+
+const SETTINGS: Settings = Settings {
+    max_cp: 300,
+    max_durability: 40,
+    max_progress: 1000,
+    max_quality: 2000,
+    base_progress: 100,
+    base_quality: 100,
+    job_level: 90,
+    allowed_actions: ActionMask::all()
+        .remove(Action::TrainedEye)
+        .remove(Action::HeartAndSoul)
+        .remove(Action::QuickInnovation),
+    adversarial: false,
+    backload_progress: false,
+    quality_increase_cap: None,
+    progress_target: None,
+    progress_optional: false,
+    rounding_mode: RoundingMode::Floor,
+    cp_cost_overrides: CpCostOverrides::none(),
+};
+
+fn solve(simulator_settings: Settings) -> Vec<Action> {
+    MacroSolver::new(
+        SolverSettings { simulator_settings },
+        Box::new(|_| {}),
+        Box::new(|_| {}),
+        AtomicFlag::new(),
+    )
+    .solve()
+    .unwrap()
+}
+
+#[test]
+fn two_solves() {
+    let first_actions = solve(SETTINGS);
+    let second_actions = solve(Settings {
+        max_durability: 70,
+        ..SETTINGS
+    });
+    let mut action_frequency = ActionFrequency::new();
+    action_frequency.add_rotation(&first_actions);
+    action_frequency.add_rotation(&second_actions);
+    assert_eq!(action_frequency.rotations(), 2);
+
+    let histogram = action_frequency.histogram();
+    for (action, count) in &histogram {
+        let expected_count = first_actions
+            .iter()
+            .chain(second_actions.iter())
+            .filter(|other| *other == action)
+            .count();
+        assert_eq!(*count, expected_count);
+        assert_eq!(action_frequency.count(*action), expected_count);
+    }
+    assert_eq!(
+        histogram.iter().map(|(_, count)| count).sum::<usize>(),
+        first_actions.len() + second_actions.len()
+    );
+    assert!(histogram.is_sorted_by_key(|(_, count)| std::cmp::Reverse(*count)));
+    assert_eq!(action_frequency.count(Action::TrainedEye), 0);
+    expect![[r#"
+        [
+            (
+                BasicTouch,
+                2,
+            ),
+            (
+                Veneration,
+                2,
+            ),
+            (
+                StandardTouch,
+                2,
+            ),
+            (
+                Innovation,
+                2,
+            ),
+            (
+                ByregotsBlessing,
+                2,
+            ),
+            (
+                CarefulSynthesis,
+                2,
+            ),
+            (
+                Manipulation,
+                2,
+            ),
+            (
+                AdvancedTouch,
+                2,
+            ),
+            (
+                Reflect,
+                2,
+            ),
+            (
+                Groundwork,
+                2,
+            ),
+            (
+                PrudentSynthesis,
+                2,
+            ),
+            (
+                PrudentTouch,
+                1,
+            ),
+            (
+                PreparatoryTouch,
+                1,
+            ),
+        ]
+    "#]]
+    .assert_debug_eq(&histogram);
+}
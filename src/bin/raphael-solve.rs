@@ -0,0 +1,53 @@
+//! Reads a solve request as JSON from stdin and prints the solved rotation as a JSON list of actions to stdout.
+//!
+//! Input format: `{ "settings": <raphael_sim::Settings>, "actions": [<raphael_sim::Action>, ...] }`
+//! where `actions` are the actions that have already been used. The printed rotation includes these actions.
+//! Exits with a nonzero code if the input is invalid or if the craft cannot be finished.
+
+use std::io::Read;
+
+use raphael_sim::{Action, Settings};
+use raphael_solver::{AtomicFlag, MacroSolver, SolverSettings};
+
+#[derive(serde::Deserialize)]
+struct SolveRequest {
+    settings: Settings,
+    #[serde(default)]
+    actions: Vec<Action>,
+}
+
+fn main() -> std::process::ExitCode {
+    let mut input = String::new();
+    if let Err(err) = std::io::stdin().read_to_string(&mut input) {
+        eprintln!("Failed to read stdin: {err}");
+        return std::process::ExitCode::FAILURE;
+    }
+    let request: SolveRequest = match serde_json::from_str(&input) {
+        Ok(request) => request,
+        Err(err) => {
+            eprintln!("Invalid solve request: {err}");
+            return std::process::ExitCode::FAILURE;
+        }
+    };
+
+    let solver_settings = SolverSettings {
+        simulator_settings: request.settings,
+    };
+    let mut solver = MacroSolver::new(
+        solver_settings,
+        Box::new(|_| {}),
+        Box::new(|_| {}),
+        AtomicFlag::new(),
+    );
+    match solver.solve_continuation(&request.actions) {
+        Ok(actions) => {
+            let rotation: Vec<Action> = request.actions.into_iter().chain(actions).collect();
+            println!("{}", serde_json::to_string(&rotation).unwrap());
+            std::process::ExitCode::SUCCESS
+        }
+        Err(err) => {
+            eprintln!("Solver error: {err:?}");
+            std::process::ExitCode::FAILURE
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use std::num::NonZeroUsize;
 
-use raphael_data::{CrafterStats, CustomRecipeOverrides, Recipe};
+use raphael_data::{CrafterStats, CustomRecipeOverrides, Locale, Recipe, collectability_tiers};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize)]
@@ -60,6 +60,10 @@ impl CrafterConfig {
     pub fn active_stats_mut(&mut self) -> &mut CrafterStats {
         &mut self.crafter_stats[self.selected_job as usize]
     }
+
+    pub fn job_name(&self, locale: Locale) -> &'static str {
+        raphael_data::get_job_full_name(self.selected_job, locale)
+    }
 }
 
 impl Default for CrafterConfig {
@@ -85,9 +89,9 @@ impl QualityTarget {
     pub fn get_target(self, max_quality: u16) -> u16 {
         match self {
             Self::Zero => 0,
-            Self::CollectableT1 => (max_quality as u32 * 55 / 100) as u16,
-            Self::CollectableT2 => (max_quality as u32 * 75 / 100) as u16,
-            Self::CollectableT3 => (max_quality as u32 * 95 / 100) as u16,
+            Self::CollectableT1 => collectability_tiers(max_quality)[0] as u16,
+            Self::CollectableT2 => collectability_tiers(max_quality)[1] as u16,
+            Self::CollectableT3 => collectability_tiers(max_quality)[2] as u16,
             Self::Full => max_quality,
             Self::Custom(quality) => quality,
         }
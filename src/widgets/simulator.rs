@@ -157,7 +157,7 @@ impl Simulator<'_> {
                         }));
                         if !state.is_final(self.settings) {
                             // do nothing
-                        } else if state.progress < u32::from(self.settings.max_progress) {
+                        } else if state.progress < self.settings.completion_progress() {
                             ui.label("Synthesis failed");
                         } else if self.item.always_collectable {
                             let (t1, t2, t3) = (
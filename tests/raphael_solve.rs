@@ -0,0 +1,59 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use raphael_sim::{Action, Settings, SimulationState};
+
+const SETTINGS_JSON: &str = r#"{
+    "max_cp": 400,
+    "max_durability": 60,
+    "max_progress": 2000,
+    "max_quality": 2500,
+    "base_progress": 100,
+    "base_quality": 100,
+    "job_level": 90,
+    "allowed_actions": { "mask": 1853882367 },
+    "adversarial": false,
+    "backload_progress": false,
+    "quality_increase_cap": null,
+    "progress_target": null,
+    "progress_optional": false,
+    "rounding_mode": "Floor"
+}"#;
+
+fn run_raphael_solve(input: &str) -> std::process::Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_raphael-solve"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn solve_from_json() {
+    let input = format!(r#"{{ "settings": {SETTINGS_JSON}, "actions": ["MuscleMemory"] }}"#);
+    let output = run_raphael_solve(&input);
+    assert!(output.status.success());
+
+    let settings: Settings = serde_json::from_str(SETTINGS_JSON).unwrap();
+    let actions: Vec<Action> = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(actions.first(), Some(&Action::MuscleMemory));
+    let state = SimulationState::from_macro(&settings, &actions).unwrap();
+    assert!(state.progress >= u32::from(settings.max_progress));
+}
+
+#[test]
+fn unfinishable() {
+    let settings_json = SETTINGS_JSON.replace("\"max_progress\": 2000", "\"max_progress\": 30000");
+    let input = format!(r#"{{ "settings": {settings_json} }}"#);
+    let output = run_raphael_solve(&input);
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+}